@@ -10,18 +10,22 @@ fn main() {
         .expect("HYPOBENCH_PORT must be a valid port number");
 
     let mut registry = BenchmarkRegistry::new();
+    registry.set_info(hypobench_harness::harness_info!());
 
     // Register benchmarks for different input sizes
     for size in [100, 1000, 10000] {
         let input: String = "a".repeat(size);
         let name = format!("char_counting/count_char/{}", size);
 
-        registry.register(name, move |n| {
+        // Returning the count lets hypobench check that baseline and
+        // candidate agree on it before comparing their speed.
+        registry.register_with_output(name, move |n| {
             let start = Instant::now();
+            let mut count = 0;
             for _ in 0..n {
-                black_box(count_char(black_box(&input), black_box('a')));
+                count = black_box(count_char(black_box(&input), black_box('a')));
             }
-            start.elapsed()
+            (start.elapsed(), count)
         });
     }
 
@@ -9,8 +9,17 @@ pub mod stats;
 
 // Re-export main types for convenience
 pub use protocol::{
-    BenchmarkListResponse, HealthResponse, RunIterationRequest, RunIterationResponse,
-    ShutdownResponse,
+    AllocationStats, BenchmarkListResponse, HarnessInfo, HealthResponse, ResourceUsage,
+    RunIterationRequest, RunIterationResponse, ShutdownResponse, VerifyResponse,
+};
+pub use report::{
+    correct_secondary_tests, AllocationComparison, AllocationSummary, BenchmarkComparison,
+    ConfigSnapshot, Contamination, CountTest, HarnessPlacement, MetricComparison, OutputCheck,
+    QuantileComparison, Report, ReportMetadata, SampleQuantile, SampleStats, Stationarity,
+    SuiteSummary, SystemCheck,
+};
+pub use stats::{
+    BayesianTest, Bootstrap, BootstrapInterval, ChangeInterval, Changepoint, Drift, Equivalence,
+    LinearFit, Modality, Mode, Posterior, PowerAnalysis, Side, SlopeTest, StatisticalTest,
+    TestResult, Trend, Verdict, WelchTTest,
 };
-pub use report::{BenchmarkComparison, ConfigSnapshot, Report, ReportMetadata, SampleStats};
-pub use stats::{Side, StatisticalTest, TestResult, WelchTTest};
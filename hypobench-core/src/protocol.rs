@@ -1,18 +1,44 @@
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
 
 /// Protocol version spoken by this crate.
 ///
 /// Bump this integer whenever the wire format of any request/response changes
-/// in a way that is not forward- or backward-compatible. Orchestrators check
-/// the harness's reported version at connect time and refuse to proceed on
-/// mismatch so the failure surfaces as a clear upgrade instruction rather
-/// than a cryptic JSON parse error.
+/// in a way that is not forward- or backward-compatible. Harnesses advertise
+/// the range of versions they serve, and orchestrators speak the highest
+/// version both sides support (see [`negotiate_version`]), refusing to
+/// proceed only when the ranges don't overlap, so the failure surfaces as a
+/// clear upgrade instruction rather than a cryptic JSON parse error.
 ///
 /// History:
 /// - `1` — original protocol (single `Fn() -> Duration` samples).
 /// - `2` — harness accepts `iterations` and returns total elapsed for a batch.
-pub const PROTOCOL_VERSION: u32 = 2;
+/// - `3` — `POST /run_batch` runs several samples in one request.
+/// - `4` — `GET /info` reports the harness binary's build provenance.
+/// - `5` — `POST /verify` returns a digest of each benchmark's output.
+pub const PROTOCOL_VERSION: u32 = 5;
+
+/// Oldest protocol version this crate still speaks.
+///
+/// Orchestrators emulate newer requests on older harnesses (e.g. `/run_batch`
+/// as repeated `/run` calls), and harnesses accept requests from older
+/// orchestrators, so this stays at 1 until a version is deliberately dropped.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The protocol versions this crate speaks.
+pub fn supported_versions() -> RangeInclusive<u32> {
+    MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION
+}
+
+/// Pick the highest protocol version in both ranges, if they overlap.
+pub fn negotiate_version(ours: RangeInclusive<u32>, theirs: RangeInclusive<u32>) -> Option<u32> {
+    let highest = (*ours.end()).min(*theirs.end());
+    let lowest = (*ours.start()).max(*theirs.start());
+    (highest >= lowest).then_some(highest)
+}
 
 /// Default protocol version assumed when the harness doesn't report one.
 /// This is 1 so that a v2 orchestrator talking to a pre-versioning harness
@@ -25,18 +51,82 @@ fn default_protocol_version() -> u32 {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthResponse {
     pub status: String,
-    /// Protocol version the harness implements.
+    /// Newest protocol version the harness implements.
     #[serde(default = "default_protocol_version")]
     pub protocol_version: u32,
+    /// Oldest protocol version the harness still serves. Harnesses that
+    /// predate negotiation omit it and serve only `protocol_version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_protocol_version: Option<u32>,
 }
 
 impl HealthResponse {
-    /// Create a healthy response advertising this crate's protocol version.
+    /// Create a healthy response advertising this crate's protocol versions.
     pub fn healthy() -> Self {
         Self {
             status: "healthy".to_string(),
             protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: Some(MIN_PROTOCOL_VERSION),
+        }
+    }
+
+    /// The protocol versions the harness serves.
+    pub fn supported_versions(&self) -> RangeInclusive<u32> {
+        self.min_protocol_version
+            .unwrap_or(self.protocol_version)
+            .min(self.protocol_version)..=self.protocol_version
+    }
+}
+
+/// Build provenance of a harness binary, served by `GET /info`.
+///
+/// Everything except `pid` is fixed when the binary is compiled, so two
+/// harnesses reporting the same info were built from the same source with the
+/// same toolchain and settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessInfo {
+    /// Name of the crate the benchmarks belong to, if the harness declared it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crate_name: Option<String>,
+    /// Version of that crate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crate_version: Option<String>,
+    /// Commit the binary was built from (`HYPOBENCH_GIT_SHA` at build time).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// Output of `rustc -V` for the compiler that built the binary.
+    pub rustc_version: String,
+    /// Target triple the binary was compiled for.
+    pub target: String,
+    /// Cargo profile kind (`debug` or `release`).
+    pub profile: String,
+    /// Optimization level (`0`–`3`, `s` or `z`).
+    pub opt_level: String,
+    /// Cargo features selected for the build (`HYPOBENCH_FEATURES` at build time).
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// Version of `hypobench-harness` compiled into the binary.
+    pub harness_version: String,
+    /// Process ID of the running harness.
+    pub pid: u32,
+}
+
+impl HarnessInfo {
+    /// Short human-readable summary, e.g. `my-crate 0.3.1 @ 1a2b3c4d (release, opt-level 3)`.
+    pub fn summary(&self) -> String {
+        let mut summary = match (&self.crate_name, &self.crate_version) {
+            (Some(name), Some(version)) => format!("{} {}", name, version),
+            (Some(name), None) => name.clone(),
+            _ => "unknown crate".to_string(),
+        };
+        if let Some(sha) = &self.git_sha {
+            summary.push_str(&format!(" @ {}", &sha[..sha.len().min(8)]));
         }
+        summary.push_str(&format!(
+            " ({}, opt-level {})",
+            self.profile, self.opt_level
+        ));
+        summary
     }
 }
 
@@ -58,8 +148,18 @@ impl BenchmarkListResponse {
 pub struct RunIterationRequest {
     pub benchmark_id: String,
     /// Number of inner iterations the harness should loop the benchmark before
-    /// reporting elapsed. Must be >= 1.
+    /// reporting elapsed. Must be >= 1. v1 orchestrators don't send it and
+    /// get a single iteration.
+    #[serde(default = "default_iterations")]
     pub iterations: u64,
+    /// Ask the harness to count perf events (instructions, cycles, ...) around
+    /// the run. Harnesses that can't, or that predate counters, ignore it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub perf_counters: bool,
+}
+
+fn default_iterations() -> u64 {
+    1
 }
 
 impl RunIterationRequest {
@@ -68,8 +168,15 @@ impl RunIterationRequest {
         Self {
             benchmark_id: benchmark_id.into(),
             iterations,
+            perf_counters: false,
         }
     }
+
+    /// Ask for perf counters around the run.
+    pub fn with_perf_counters(mut self, perf_counters: bool) -> Self {
+        self.perf_counters = perf_counters;
+        self
+    }
 }
 
 /// Response from running a benchmark for some number of inner iterations.
@@ -77,10 +184,27 @@ impl RunIterationRequest {
 pub struct RunIterationResponse {
     /// Whether the iteration completed successfully.
     pub success: bool,
-    /// Echo of the iteration count the harness actually ran.
+    /// Echo of the iteration count the harness actually ran. v1 harnesses
+    /// don't report it.
+    #[serde(default)]
     pub iterations: u64,
     /// Total elapsed duration across all `iterations` calls, in nanoseconds.
     pub duration_ns: u64,
+    /// Heap allocations made during the run. Only reported by harnesses
+    /// whose binary installs the counting allocator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<AllocationStats>,
+    /// Perf event totals across all iterations, keyed by counter name
+    /// (`instructions`, `cycles`, `branch_misses`, `cache_misses`, or the
+    /// software fallbacks `task_clock_ns`, `page_faults`, `context_switches`).
+    /// Empty unless the request asked for counters and the harness could open
+    /// them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub counters: BTreeMap<String, u64>,
+    /// Resource usage of the thread that ran the benchmark, over the run.
+    /// Only reported by harnesses on platforms with per-thread `getrusage`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rusage: Option<ResourceUsage>,
     /// Error message if the iteration failed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -93,6 +217,9 @@ impl RunIterationResponse {
             success: true,
             iterations,
             duration_ns: duration.as_nanos() as u64,
+            allocations: None,
+            counters: BTreeMap::new(),
+            rusage: None,
             error: None,
         }
     }
@@ -103,10 +230,38 @@ impl RunIterationResponse {
             success: false,
             iterations: 0,
             duration_ns: 0,
+            allocations: None,
+            counters: BTreeMap::new(),
+            rusage: None,
             error: Some(error.into()),
         }
     }
 
+    /// Attach the allocations counted during the run.
+    pub fn with_allocations(mut self, allocations: Option<AllocationStats>) -> Self {
+        self.allocations = allocations;
+        self
+    }
+
+    /// Attach the resource usage measured over the run.
+    pub fn with_rusage(mut self, rusage: Option<ResourceUsage>) -> Self {
+        self.rusage = rusage;
+        self
+    }
+
+    /// Attach the perf counters read during the run.
+    pub fn with_counters(mut self, counters: BTreeMap<String, u64>) -> Self {
+        self.counters = counters;
+        self
+    }
+
+    /// A counter's total divided by the iteration count, if it was reported.
+    pub fn counter_per_iter(&self, name: &str) -> Option<f64> {
+        self.counters
+            .get(name)
+            .map(|&total| total as f64 / self.iterations.max(1) as f64)
+    }
+
     /// Get the total elapsed duration as a `Duration`.
     pub fn duration(&self) -> Duration {
         Duration::from_nanos(self.duration_ns)
@@ -122,6 +277,110 @@ impl RunIterationResponse {
     }
 }
 
+/// Heap allocations counted while a harness ran one sample.
+///
+/// `count` and `bytes` are totals across all of the sample's iterations;
+/// divide by `iterations` for per-iteration figures. Reallocations count as
+/// an allocation of the new size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocationStats {
+    /// Number of allocations.
+    pub count: u64,
+    /// Total bytes allocated.
+    pub bytes: u64,
+    /// Highest live heap size reached during the sample, above the live size
+    /// when it started.
+    pub peak_live_bytes: u64,
+}
+
+impl AllocationStats {
+    /// Allocations per iteration.
+    pub fn count_per_iter(&self, iterations: u64) -> f64 {
+        self.count as f64 / iterations.max(1) as f64
+    }
+
+    /// Bytes allocated per iteration.
+    pub fn bytes_per_iter(&self, iterations: u64) -> f64 {
+        self.bytes as f64 / iterations.max(1) as f64
+    }
+}
+
+/// `getrusage` deltas of the benchmark thread over one sample.
+///
+/// Like [`AllocationStats`], these are totals across all of the sample's
+/// iterations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// CPU time spent in user mode, in nanoseconds.
+    pub user_time_ns: u64,
+    /// CPU time spent in the kernel, in nanoseconds.
+    pub system_time_ns: u64,
+    /// Page faults serviced without I/O.
+    pub minor_faults: u64,
+    /// Page faults that needed I/O.
+    pub major_faults: u64,
+    /// Times the thread gave up the CPU itself, e.g. to block.
+    pub voluntary_context_switches: u64,
+    /// Times the thread was preempted. A spike here means something else ran
+    /// in the middle of the sample.
+    pub involuntary_context_switches: u64,
+}
+
+/// Request to run several samples back to back in one round-trip.
+///
+/// Each entry is run in order, exactly as if it had been sent to `/run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunBatchRequest {
+    pub runs: Vec<RunIterationRequest>,
+}
+
+impl RunBatchRequest {
+    /// Create a batch from individual run requests.
+    pub fn new(runs: Vec<RunIterationRequest>) -> Self {
+        Self { runs }
+    }
+
+    /// Ask for perf counters around every run in the batch.
+    pub fn with_perf_counters(mut self, perf_counters: bool) -> Self {
+        for run in &mut self.runs {
+            run.perf_counters = perf_counters;
+        }
+        self
+    }
+}
+
+/// Response to a [`RunBatchRequest`]: one result per requested run, in order.
+///
+/// A failed entry doesn't abort the batch; its result carries the error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunBatchResponse {
+    pub results: Vec<RunIterationResponse>,
+}
+
+impl RunBatchResponse {
+    /// Create a batch response.
+    pub fn new(results: Vec<RunIterationResponse>) -> Self {
+        Self { results }
+    }
+}
+
+/// Response to a verify request: a digest of each benchmark's output.
+///
+/// Only benchmarks registered with an output are listed. Digests are
+/// lowercase hex, and equal digests from two harnesses mean the benchmarks
+/// produced equal output.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyResponse {
+    pub digests: BTreeMap<String, String>,
+}
+
+impl VerifyResponse {
+    /// Create a verify response.
+    pub fn new(digests: BTreeMap<String, String>) -> Self {
+        Self { digests }
+    }
+}
+
 /// Response to a shutdown request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShutdownResponse {
@@ -142,15 +401,44 @@ impl ShutdownResponse {
 pub struct ClaimRequest {
     /// Unique nonce identifying this orchestrator session.
     pub nonce: String,
+    /// Lease duration in milliseconds. The harness drops the claim if no
+    /// heartbeat arrives within this window. `None` keeps the claim until it
+    /// is explicitly released (the behaviour of pre-lease orchestrators).
+    /// Must be positive; harnesses reject a zero lease.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_ms: Option<u64>,
+    /// Take over the harness even if another orchestrator holds a live claim.
+    /// Intended for recovering a harness wedged by a crashed orchestrator.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub force: bool,
 }
 
 impl ClaimRequest {
-    /// Create a new claim request.
+    /// Create a new claim request with no expiry.
     pub fn new(nonce: impl Into<String>) -> Self {
         Self {
             nonce: nonce.into(),
+            ttl_ms: None,
+            force: false,
         }
     }
+
+    /// Attach a lease duration to the claim.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl_ms = Some(ttl.as_millis() as u64);
+        self
+    }
+
+    /// Request a forced takeover of an existing claim.
+    pub fn forced(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// The requested lease duration, if any.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl_ms.map(Duration::from_millis)
+    }
 }
 
 /// Response to a claim request.
@@ -158,6 +446,10 @@ impl ClaimRequest {
 pub struct ClaimResponse {
     /// Whether the claim was successful.
     pub success: bool,
+    /// Remaining lease time in milliseconds. `None` if the claim never expires
+    /// (or the harness predates leases), in which case no heartbeat is needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_ms: Option<u64>,
     /// Error message if claim failed (e.g., already claimed).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -165,9 +457,10 @@ pub struct ClaimResponse {
 
 impl ClaimResponse {
     /// Create a successful claim response.
-    pub fn success() -> Self {
+    pub fn success(expires_in: Option<Duration>) -> Self {
         Self {
             success: true,
+            expires_in_ms: expires_in.map(|d| d.as_millis() as u64),
             error: None,
         }
     }
@@ -176,9 +469,116 @@ impl ClaimResponse {
     pub fn already_claimed() -> Self {
         Self {
             success: false,
+            expires_in_ms: None,
             error: Some("Harness is already claimed by another orchestrator".to_string()),
         }
     }
+
+    /// Create a failed claim response for a malformed request.
+    pub fn invalid(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            expires_in_ms: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Request to extend a claim lease.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatRequest {
+    /// The nonce that was used to claim the harness.
+    pub nonce: String,
+}
+
+impl HeartbeatRequest {
+    /// Create a new heartbeat request.
+    pub fn new(nonce: impl Into<String>) -> Self {
+        Self {
+            nonce: nonce.into(),
+        }
+    }
+}
+
+/// Response to a heartbeat request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatResponse {
+    /// Whether the lease was extended.
+    pub success: bool,
+    /// Remaining lease time in milliseconds after the extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in_ms: Option<u64>,
+    /// Error message if the lease could not be extended.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HeartbeatResponse {
+    /// Create a successful heartbeat response.
+    pub fn success(expires_in: Option<Duration>) -> Self {
+        Self {
+            success: true,
+            expires_in_ms: expires_in.map(|d| d.as_millis() as u64),
+            error: None,
+        }
+    }
+
+    /// Create a failed heartbeat response (the lease expired or was taken over).
+    pub fn lost() -> Self {
+        Self {
+            success: false,
+            expires_in_ms: None,
+            error: Some("Claim is no longer held by this nonce".to_string()),
+        }
+    }
+}
+
+/// A claim held on a harness, with an optional expiry.
+///
+/// This is harness-side bookkeeping rather than a wire type, but it lives
+/// here so the lease rules are defined next to the messages that drive them.
+/// Every method takes `now` explicitly so expiry can be tested without sleeping.
+#[derive(Debug, Clone)]
+pub struct ClaimLease {
+    nonce: String,
+    ttl: Option<Duration>,
+    expires_at: Option<Instant>,
+}
+
+impl ClaimLease {
+    /// Start a lease for `nonce`. A `ttl` of `None` never expires.
+    pub fn new(nonce: impl Into<String>, ttl: Option<Duration>, now: Instant) -> Self {
+        Self {
+            nonce: nonce.into(),
+            ttl,
+            expires_at: ttl.map(|ttl| now + ttl),
+        }
+    }
+
+    /// The nonce that holds this lease.
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Whether the lease is held by `nonce`.
+    pub fn is_held_by(&self, nonce: &str) -> bool {
+        self.nonce == nonce
+    }
+
+    /// Whether the lease has lapsed at `now`.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| now >= at)
+    }
+
+    /// Time left on the lease at `now`, or `None` if it never expires.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.expires_at.map(|at| at.saturating_duration_since(now))
+    }
+
+    /// Extend the lease by its original TTL, measured from `now`.
+    pub fn renew(&mut self, now: Instant) {
+        self.expires_at = self.ttl.map(|ttl| now + ttl);
+    }
 }
 
 /// Request to release a claim on the harness.
@@ -214,6 +614,152 @@ impl ReleaseResponse {
 /// Header name for the claim nonce.
 pub const CLAIM_HEADER: &str = "X-Harness-Claim";
 
+/// Environment variable carrying the port a harness should listen on.
+pub const PORT_ENV: &str = "HYPOBENCH_PORT";
+
+/// Environment variable carrying the address a harness should bind to.
+/// Harnesses default to loopback when it is unset.
+pub const BIND_ADDR_ENV: &str = "HYPOBENCH_BIND_ADDR";
+
+/// Environment variable carrying the shared secret a harness requires as a
+/// bearer token on every request. No authentication when unset or empty.
+pub const AUTH_TOKEN_ENV: &str = "HYPOBENCH_AUTH_TOKEN";
+
+/// Prefix of the stdout line a harness prints once its server is listening.
+///
+/// Spawned harnesses are asked to bind port 0; the orchestrator scans their
+/// stdout for this line to learn the port the OS actually assigned.
+pub const LISTEN_ANNOUNCEMENT_PREFIX: &str = "HYPOBENCH_LISTENING ";
+
+/// Format the stdout line announcing the address a harness is listening on.
+pub fn listen_announcement(addr: SocketAddr) -> String {
+    format!("{}{}", LISTEN_ANNOUNCEMENT_PREFIX, addr)
+}
+
+/// Parse a line produced by [`listen_announcement`].
+///
+/// Returns `None` for any other line, so callers can feed every stdout line
+/// through this without caring what else the benchmark binary prints.
+pub fn parse_listen_announcement(line: &str) -> Option<SocketAddr> {
+    line.trim_end()
+        .strip_prefix(LISTEN_ANNOUNCEMENT_PREFIX)?
+        .parse()
+        .ok()
+}
+
+/// Environment variable carrying the commit a harness binary is built from.
+/// Read at compile time, so it must be set for the `cargo build`.
+pub const GIT_SHA_ENV: &str = "HYPOBENCH_GIT_SHA";
+
+/// Environment variable carrying the comma-separated cargo features a harness
+/// binary is built with. Read at compile time, like [`GIT_SHA_ENV`].
+pub const FEATURES_ENV: &str = "HYPOBENCH_FEATURES";
+
+/// Environment variable selecting how a spawned harness talks to the
+/// orchestrator (see [`Transport`]). Harnesses serve HTTP when it is unset.
+pub const TRANSPORT_ENV: &str = "HYPOBENCH_TRANSPORT";
+
+/// How an orchestrator and a harness exchange protocol messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// HTTP server on a TCP port. Required for harnesses we didn't spawn.
+    #[default]
+    Http,
+    /// Newline-delimited JSON over the child's stdin/stdout. No ports, so no
+    /// conflicts or firewall prompts; only usable by the spawning process.
+    Pipe,
+}
+
+impl Transport {
+    /// The value of [`TRANSPORT_ENV`] that selects this transport.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Transport::Http => "http",
+            Transport::Pipe => "pipe",
+        }
+    }
+
+    /// Parse a [`TRANSPORT_ENV`] value.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "http" => Some(Transport::Http),
+            "pipe" => Some(Transport::Pipe),
+            _ => None,
+        }
+    }
+}
+
+/// A harness operation, independent of the transport carrying it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    Health,
+    Benchmarks,
+    Run,
+    RunBatch,
+    Info,
+    Verify,
+    Claim,
+    Heartbeat,
+    Release,
+    Shutdown,
+}
+
+impl Endpoint {
+    /// The HTTP path serving this operation.
+    pub fn path(&self) -> &'static str {
+        match self {
+            Endpoint::Health => "/health",
+            Endpoint::Benchmarks => "/benchmarks",
+            Endpoint::Run => "/run",
+            Endpoint::RunBatch => "/run_batch",
+            Endpoint::Info => "/info",
+            Endpoint::Verify => "/verify",
+            Endpoint::Claim => "/claim",
+            Endpoint::Heartbeat => "/heartbeat",
+            Endpoint::Release => "/release",
+            Endpoint::Shutdown => "/shutdown",
+        }
+    }
+
+    /// Whether the HTTP route is a POST (otherwise GET).
+    pub fn is_post(&self) -> bool {
+        !matches!(
+            self,
+            Endpoint::Health | Endpoint::Benchmarks | Endpoint::Info
+        )
+    }
+}
+
+/// One line written to a harness's stdin under [`Transport::Pipe`].
+///
+/// `body` is the same JSON the HTTP endpoint takes as its request body, and
+/// `claim` stands in for the [`CLAIM_HEADER`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipeRequest {
+    /// Correlates the response; requests may be answered out of order.
+    pub id: u64,
+    pub endpoint: Endpoint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim: Option<String>,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub body: serde_json::Value,
+}
+
+/// One line a harness writes to stdout in answer to a [`PipeRequest`].
+///
+/// `status` and `body` are what the HTTP endpoint would have returned.
+/// Benchmarks are free to print to stdout too, so readers should treat lines
+/// that don't parse as a `PipeResponse` as ordinary output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipeResponse {
+    pub id: u64,
+    pub status: u16,
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +780,91 @@ mod tests {
         assert_eq!(legacy.protocol_version, 1);
     }
 
+    #[test]
+    fn test_health_response_supported_versions() {
+        let response = HealthResponse::healthy();
+        assert_eq!(response.supported_versions(), supported_versions());
+
+        // v2 harnesses report a single version and no minimum.
+        let legacy: HealthResponse =
+            serde_json::from_str(r#"{"status":"healthy","protocol_version":2}"#).unwrap();
+        assert_eq!(legacy.supported_versions(), 2..=2);
+    }
+
+    #[test]
+    fn test_negotiate_version() {
+        assert_eq!(negotiate_version(1..=3, 1..=3), Some(3));
+        assert_eq!(negotiate_version(1..=3, 2..=2), Some(2));
+        assert_eq!(negotiate_version(1..=3, 1..=5), Some(3));
+        assert_eq!(negotiate_version(2..=3, 1..=1), None);
+        assert_eq!(negotiate_version(1..=3, 4..=6), None);
+    }
+
+    #[test]
+    fn test_v1_run_messages() {
+        // A v1 orchestrator omits `iterations`; a v1 harness omits the echo.
+        let request: RunIterationRequest = serde_json::from_str(r#"{"benchmark_id":"b"}"#).unwrap();
+        assert_eq!(request.iterations, 1);
+
+        let response: RunIterationResponse =
+            serde_json::from_str(r#"{"success":true,"duration_ns":500}"#).unwrap();
+        assert_eq!(response.duration(), Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn test_run_response_allocations() {
+        let plain = RunIterationResponse::success(4, Duration::from_nanos(400));
+        assert!(!serde_json::to_string(&plain)
+            .unwrap()
+            .contains("allocations"));
+
+        let stats = AllocationStats {
+            count: 12,
+            bytes: 480,
+            peak_live_bytes: 256,
+        };
+        let counted = plain.with_allocations(Some(stats));
+        let parsed: RunIterationResponse =
+            serde_json::from_str(&serde_json::to_string(&counted).unwrap()).unwrap();
+        assert_eq!(parsed.allocations, Some(stats));
+        assert_eq!(stats.count_per_iter(4), 3.0);
+        assert_eq!(stats.bytes_per_iter(4), 120.0);
+    }
+
+    #[test]
+    fn test_run_perf_counters() {
+        let request = RunIterationRequest::new("b", 1);
+        assert!(!serde_json::to_string(&request)
+            .unwrap()
+            .contains("perf_counters"));
+        let batch = RunBatchRequest::new(vec![request.clone(), request]).with_perf_counters(true);
+        assert!(batch.runs.iter().all(|run| run.perf_counters));
+
+        let counters = BTreeMap::from([("instructions".to_string(), 8_000)]);
+        let response =
+            RunIterationResponse::success(8, Duration::from_nanos(800)).with_counters(counters);
+        let parsed: RunIterationResponse =
+            serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+        assert_eq!(parsed.counter_per_iter("instructions"), Some(1000.0));
+        assert_eq!(parsed.counter_per_iter("cycles"), None);
+    }
+
+    #[test]
+    fn test_run_response_rusage() {
+        let plain = RunIterationResponse::success(1, Duration::from_nanos(100));
+        assert!(!serde_json::to_string(&plain).unwrap().contains("rusage"));
+
+        let rusage = ResourceUsage {
+            user_time_ns: 90,
+            involuntary_context_switches: 2,
+            ..ResourceUsage::default()
+        };
+        let measured = plain.with_rusage(Some(rusage));
+        let parsed: RunIterationResponse =
+            serde_json::from_str(&serde_json::to_string(&measured).unwrap()).unwrap();
+        assert_eq!(parsed.rusage, Some(rusage));
+    }
+
     #[test]
     fn test_health_response_roundtrip_preserves_version() {
         let response = HealthResponse::healthy();
@@ -318,6 +949,110 @@ mod tests {
         assert_eq!(response.status, "shutting_down");
     }
 
+    #[test]
+    fn test_claim_request_legacy_has_no_ttl() {
+        // A pre-lease orchestrator sends only the nonce; the claim must be
+        // treated as permanent and non-forced.
+        let legacy: ClaimRequest = serde_json::from_str(r#"{"nonce":"abc"}"#).unwrap();
+        assert_eq!(legacy.nonce, "abc");
+        assert!(legacy.ttl().is_none());
+        assert!(!legacy.force);
+    }
+
+    #[test]
+    fn test_claim_request_roundtrip_with_ttl_and_force() {
+        let request = ClaimRequest::new("abc")
+            .with_ttl(Duration::from_secs(30))
+            .forced();
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: ClaimRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.ttl(), Some(Duration::from_secs(30)));
+        assert!(parsed.force);
+    }
+
+    #[test]
+    fn test_claim_request_omits_defaults() {
+        let json = serde_json::to_string(&ClaimRequest::new("abc")).unwrap();
+        assert!(!json.contains("ttl_ms"));
+        assert!(!json.contains("force"));
+    }
+
+    #[test]
+    fn test_claim_response_legacy_has_no_expiry() {
+        let legacy: ClaimResponse = serde_json::from_str(r#"{"success":true}"#).unwrap();
+        assert!(legacy.success);
+        assert!(legacy.expires_in_ms.is_none());
+    }
+
+    #[test]
+    fn test_heartbeat_response_roundtrip() {
+        let ok = HeartbeatResponse::success(Some(Duration::from_millis(1500)));
+        let parsed: HeartbeatResponse =
+            serde_json::from_str(&serde_json::to_string(&ok).unwrap()).unwrap();
+        assert!(parsed.success);
+        assert_eq!(parsed.expires_in_ms, Some(1500));
+
+        let lost = HeartbeatResponse::lost();
+        assert!(!lost.success);
+        assert!(lost.error.is_some());
+    }
+
+    #[test]
+    fn test_claim_lease_expires_after_ttl() {
+        let t0 = Instant::now();
+        let lease = ClaimLease::new("abc", Some(Duration::from_secs(10)), t0);
+        assert!(lease.is_held_by("abc"));
+        assert!(!lease.is_held_by("xyz"));
+        assert!(!lease.is_expired(t0 + Duration::from_secs(9)));
+        assert!(lease.is_expired(t0 + Duration::from_secs(10)));
+        assert_eq!(
+            lease.remaining(t0 + Duration::from_secs(4)),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(
+            lease.remaining(t0 + Duration::from_secs(20)),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_claim_lease_renew_extends_from_now() {
+        let t0 = Instant::now();
+        let mut lease = ClaimLease::new("abc", Some(Duration::from_secs(10)), t0);
+        lease.renew(t0 + Duration::from_secs(8));
+        assert!(!lease.is_expired(t0 + Duration::from_secs(17)));
+        assert!(lease.is_expired(t0 + Duration::from_secs(18)));
+    }
+
+    #[test]
+    fn test_claim_lease_without_ttl_never_expires() {
+        let t0 = Instant::now();
+        let mut lease = ClaimLease::new("abc", None, t0);
+        assert!(!lease.is_expired(t0 + Duration::from_secs(1_000_000)));
+        assert!(lease.remaining(t0).is_none());
+        lease.renew(t0 + Duration::from_secs(5));
+        assert!(lease.remaining(t0).is_none());
+    }
+
+    #[test]
+    fn test_listen_announcement_roundtrip() {
+        let addr: SocketAddr = "0.0.0.0:41234".parse().unwrap();
+        let line = listen_announcement(addr);
+        assert_eq!(line, "HYPOBENCH_LISTENING 0.0.0.0:41234");
+        assert_eq!(parse_listen_announcement(&line), Some(addr));
+        assert_eq!(
+            parse_listen_announcement(&format!("{line}\r\n")),
+            Some(addr)
+        );
+    }
+
+    #[test]
+    fn test_parse_listen_announcement_ignores_other_lines() {
+        assert!(parse_listen_announcement("Benchmark harness listening on 0.0.0.0:1").is_none());
+        assert!(parse_listen_announcement("HYPOBENCH_LISTENING not-an-addr").is_none());
+        assert!(parse_listen_announcement("").is_none());
+    }
+
     #[test]
     fn test_serialization_roundtrip() {
         let response = RunIterationResponse::success(5, Duration::from_nanos(12345));
@@ -338,4 +1073,110 @@ mod tests {
         // The error field should not be present in the JSON
         assert!(!json.contains("error"));
     }
+
+    #[test]
+    fn test_transport_env_roundtrip() {
+        for transport in [Transport::Http, Transport::Pipe] {
+            assert_eq!(Transport::parse(transport.as_str()), Some(transport));
+        }
+        assert_eq!(Transport::parse("carrier-pigeon"), None);
+        assert_eq!(Transport::default(), Transport::Http);
+    }
+
+    #[test]
+    fn test_run_batch_serialization() {
+        let batch = RunBatchRequest::new(vec![
+            RunIterationRequest::new("b", 1),
+            RunIterationRequest::new("b", 2),
+        ]);
+        let json = serde_json::to_string(&batch).unwrap();
+        assert_eq!(
+            json,
+            r#"{"runs":[{"benchmark_id":"b","iterations":1},{"benchmark_id":"b","iterations":2}]}"#
+        );
+    }
+
+    #[test]
+    fn test_endpoint_routes() {
+        assert_eq!(Endpoint::Run.path(), "/run");
+        assert_eq!(Endpoint::RunBatch.path(), "/run_batch");
+        assert!(Endpoint::RunBatch.is_post());
+        assert!(Endpoint::Run.is_post());
+        assert!(Endpoint::Shutdown.is_post());
+        assert!(!Endpoint::Health.is_post());
+        assert!(!Endpoint::Benchmarks.is_post());
+        assert_eq!(Endpoint::Info.path(), "/info");
+        assert!(!Endpoint::Info.is_post());
+        assert_eq!(Endpoint::Verify.path(), "/verify");
+        assert!(Endpoint::Verify.is_post());
+    }
+
+    fn sample_info() -> HarnessInfo {
+        HarnessInfo {
+            crate_name: Some("char-counter".to_string()),
+            crate_version: Some("0.1.0".to_string()),
+            git_sha: Some("1a2b3c4d5e6f".to_string()),
+            rustc_version: "rustc 1.80.0 (051478957 2024-07-21)".to_string(),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            profile: "release".to_string(),
+            opt_level: "3".to_string(),
+            features: vec!["default".to_string()],
+            harness_version: "0.5.1".to_string(),
+            pid: 4242,
+        }
+    }
+
+    #[test]
+    fn test_harness_info_roundtrip_and_summary() {
+        let info = sample_info();
+        let json = serde_json::to_string(&info).unwrap();
+        let parsed: HarnessInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, info);
+        assert_eq!(
+            info.summary(),
+            "char-counter 0.1.0 @ 1a2b3c4d (release, opt-level 3)"
+        );
+
+        let anonymous = HarnessInfo {
+            crate_name: None,
+            crate_version: None,
+            git_sha: None,
+            ..info
+        };
+        let json = serde_json::to_string(&anonymous).unwrap();
+        assert!(!json.contains("crate_name"));
+        assert_eq!(anonymous.summary(), "unknown crate (release, opt-level 3)");
+    }
+
+    #[test]
+    fn test_pipe_request_wire_format() {
+        let request = PipeRequest {
+            id: 7,
+            endpoint: Endpoint::Health,
+            claim: None,
+            body: serde_json::Value::Null,
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"id":7,"endpoint":"health"}"#);
+
+        let parsed: PipeRequest = serde_json::from_str(
+            r#"{"id":8,"endpoint":"run","claim":"abc","body":{"benchmark_id":"b","iterations":3}}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.endpoint, Endpoint::Run);
+        assert_eq!(parsed.claim.as_deref(), Some("abc"));
+        let run: RunIterationRequest = serde_json::from_value(parsed.body).unwrap();
+        assert_eq!(run.iterations, 3);
+    }
+
+    #[test]
+    fn test_pipe_response_rejects_benchmark_output() {
+        assert!(serde_json::from_str::<PipeResponse>("hello from a benchmark").is_err());
+        assert!(serde_json::from_str::<PipeResponse>(r#"{"answer":42}"#).is_err());
+
+        let response: PipeResponse =
+            serde_json::from_str(r#"{"id":1,"status":200,"body":{"status":"healthy"}}"#).unwrap();
+        assert_eq!(response.id, 1);
+        assert_eq!(response.status, 200);
+    }
 }
@@ -1,4 +1,8 @@
-use crate::stats::TestResult;
+use crate::protocol::AllocationStats;
+use crate::stats::bootstrap::{geomean_change, quantile, Bootstrap};
+use crate::stats::{
+    apply_bonferroni, Drift, LinearFit, Modality, Side, StatisticalTest, TestResult, Verdict,
+};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SampleStats {
@@ -7,6 +11,163 @@ pub struct SampleStats {
     pub min_ns: u64,
     pub max_ns: u64,
     pub sample_count: usize,
+    /// Median sample. Zero in reports that predate it.
+    #[serde(default)]
+    pub median_ns: f64,
+    /// The configured quantiles, in ascending order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quantiles: Vec<SampleQuantile>,
+    /// Fit of elapsed time against iteration count, when samples were taken
+    /// at varying counts. The other fields then summarize each sample's
+    /// elapsed time divided by its count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fit: Option<LinearFit>,
+    /// Modes of the samples' distribution, when there were enough samples
+    /// to estimate them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modality: Option<Modality>,
+}
+
+impl SampleStats {
+    /// Summarize samples in nanoseconds, with the given `quantiles` (each in
+    /// `0..=1`).
+    pub fn from_samples(samples: &[f64], quantiles: &[f64]) -> Self {
+        let n = samples.len();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        let mut quantiles: Vec<SampleQuantile> = quantiles
+            .iter()
+            .map(|&q| SampleQuantile {
+                quantile: q,
+                value_ns: quantile(samples, q),
+            })
+            .collect();
+        quantiles.sort_by(|a, b| a.quantile.total_cmp(&b.quantile));
+
+        Self {
+            mean_ns: mean,
+            std_dev_ns: variance.sqrt(),
+            min_ns: samples.iter().copied().reduce(f64::min).unwrap_or(0.0) as u64,
+            max_ns: samples.iter().copied().reduce(f64::max).unwrap_or(0.0) as u64,
+            sample_count: n,
+            median_ns: quantile(samples, 0.5),
+            quantiles,
+            fit: None,
+            modality: Modality::estimate(samples),
+        }
+    }
+
+    /// Whether the samples have more than one mode.
+    pub fn multimodal(&self) -> bool {
+        self.modality.as_ref().is_some_and(Modality::multimodal)
+    }
+
+    /// Best estimate of time per iteration: the fitted slope under linear
+    /// sampling, else the mean.
+    pub fn per_iter_ns(&self) -> f64 {
+        self.fit.map_or(self.mean_ns, |fit| fit.slope_ns)
+    }
+
+    /// The value of quantile `q`, if it was computed.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.quantiles
+            .iter()
+            .find(|s| same_quantile(s.quantile, q))
+            .map(|s| s.value_ns)
+    }
+}
+
+/// One quantile of a side's samples.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SampleQuantile {
+    /// The quantile, e.g. `0.99` for p99.
+    pub quantile: f64,
+    /// Its value, in nanoseconds.
+    pub value_ns: f64,
+}
+
+/// Change in one quantile of the timing distribution, e.g. p99 latency.
+///
+/// Same sign convention as [`TestResult::effect_size`]: a positive `change`
+/// means the candidate's quantile is lower.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QuantileComparison {
+    /// The quantile, e.g. `0.99` for p99.
+    pub quantile: f64,
+    /// Baseline value of the quantile, in nanoseconds.
+    pub baseline_ns: f64,
+    /// Candidate value of the quantile, in nanoseconds.
+    pub candidate_ns: f64,
+    /// Relative change in the quantile, in percent.
+    pub change: f64,
+    /// Lower bound of the bootstrap confidence interval on `change`.
+    pub change_ci_low: f64,
+    /// Upper bound of the bootstrap confidence interval on `change`.
+    pub change_ci_high: f64,
+    /// Whether the interval excludes zero and `change` reaches the minimum
+    /// effect size.
+    pub statistically_significant: bool,
+}
+
+impl QuantileComparison {
+    /// Compare quantile `q` of baseline and candidate samples, with a
+    /// `bootstrap` interval at `confidence_level`. As with the mean, a change
+    /// smaller than `minimum_effect_size` percent is not significant.
+    pub fn analyze(
+        baseline: &[f64],
+        candidate: &[f64],
+        q: f64,
+        confidence_level: f64,
+        minimum_effect_size: f64,
+        bootstrap: &Bootstrap,
+    ) -> Self {
+        let baseline_ns = quantile(baseline, q);
+        let candidate_ns = quantile(candidate, q);
+        let change = if baseline_ns == 0.0 {
+            0.0
+        } else {
+            (baseline_ns - candidate_ns) / baseline_ns * 100.0
+        };
+        let (change_ci_low, change_ci_high) =
+            bootstrap.quantile_change_ci(baseline, candidate, q, confidence_level);
+        let excludes_zero = change_ci_low > 0.0 || change_ci_high < 0.0;
+        Self {
+            quantile: q,
+            baseline_ns,
+            candidate_ns,
+            change,
+            change_ci_low,
+            change_ci_high,
+            statistically_significant: excludes_zero && change.abs() >= minimum_effect_size,
+        }
+    }
+
+    /// Classify this quantile's change. There is no equivalence test for
+    /// quantiles, so it is never [`Verdict::Equivalent`].
+    pub fn verdict(&self) -> Verdict {
+        match (self.statistically_significant, self.change > 0.0) {
+            (false, _) => Verdict::Inconclusive,
+            (true, true) => Verdict::Faster,
+            (true, false) => Verdict::Slower,
+        }
+    }
+
+    /// Short name of the quantile, e.g. `p99`.
+    pub fn label(&self) -> String {
+        quantile_label(self.quantile)
+    }
+}
+
+/// Short name of quantile `q`, e.g. `p50` for 0.5 or `p99.9` for 0.999.
+pub fn quantile_label(q: f64) -> String {
+    let percent = format!("{:.3}", q * 100.0);
+    format!("p{}", percent.trim_end_matches('0').trim_end_matches('.'))
+}
+
+/// Whether two quantiles are the same, allowing for float round-off in
+/// parsed or deserialized values.
+fn same_quantile(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -15,10 +176,421 @@ pub struct BenchmarkComparison {
     pub baseline_stats: SampleStats,
     pub candidate_stats: SampleStats,
     pub test_result: TestResult,
+    /// Heap allocation comparison, when both harnesses count allocations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<AllocationComparison>,
+    /// Secondary metrics, such as perf counters, compared alongside time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metrics: Vec<MetricComparison>,
+    /// Samples flagged as likely preempted, if any were.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contamination: Option<Contamination>,
+    /// Whether both sides produced the same output, when both harnesses
+    /// digest it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_check: Option<OutputCheck>,
+    /// Changes in the configured quantiles of the timing distribution.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quantiles: Vec<QuantileComparison>,
+    /// Drift checks on the sample order, when both sides had enough samples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stationarity: Option<Stationarity>,
+}
+
+impl BenchmarkComparison {
+    /// Whether the samples drifted during the run, which breaks the tests'
+    /// assumption that they're exchangeable.
+    pub fn unreliable(&self) -> bool {
+        self.stationarity.is_some_and(|s| !s.stationary())
+    }
+
+    /// Whether either side's samples have more than one mode, which makes
+    /// the means and the tests on them misleading.
+    pub fn multimodal(&self) -> bool {
+        self.baseline_stats.multimodal() || self.candidate_stats.multimodal()
+    }
+
+    /// The comparison of quantile `q`, if it was computed.
+    pub fn quantile(&self, q: f64) -> Option<&QuantileComparison> {
+        self.quantiles.iter().find(|c| same_quantile(c.quantile, q))
+    }
+}
+
+/// Result of comparing a benchmark's output digests before sampling.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OutputCheck {
+    /// Both sides produced the same output.
+    Match,
+    /// The outputs differ, so a speed difference may come from doing
+    /// different work.
+    Mismatch { baseline: String, candidate: String },
+}
+
+impl OutputCheck {
+    /// Compare two output digests.
+    pub fn compare(baseline: &str, candidate: &str) -> Self {
+        if baseline == candidate {
+            OutputCheck::Match
+        } else {
+            OutputCheck::Mismatch {
+                baseline: baseline.to_string(),
+                candidate: candidate.to_string(),
+            }
+        }
+    }
+
+    /// Whether the outputs differ.
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, OutputCheck::Mismatch { .. })
+    }
+}
+
+/// Samples whose involuntary context switches spiked, suggesting something
+/// else ran in the middle of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Contamination {
+    /// Flagged baseline samples.
+    pub baseline_flagged: usize,
+    /// Flagged candidate samples.
+    pub candidate_flagged: usize,
+    /// Whether the flagged samples were dropped before analysis.
+    pub rejected: bool,
+}
+
+/// Trend and changepoint checks on the order a benchmark's samples were
+/// taken in, for each side and for their paired differences.
+///
+/// Each of the six checks runs at `alpha`, the family-wise level split
+/// Bonferroni-style, so a stationary benchmark is flagged with probability
+/// at most the family-wise level.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Stationarity {
+    pub baseline: Drift,
+    pub candidate: Drift,
+    /// Candidate minus baseline, sample by sample. Interleaving exposes both
+    /// sides to the same drift, so a shift here means it hit one side more.
+    pub difference: Drift,
+    /// Significance level of each check.
+    pub alpha: f64,
+}
+
+impl Stationarity {
+    /// Check baseline and candidate samples, in collection order, at a
+    /// family-wise level of `family_alpha`. Shifts are sized in percent of
+    /// the baseline mean. `None` if either side has too few samples.
+    pub fn analyze(baseline: &[f64], candidate: &[f64], family_alpha: f64) -> Option<Self> {
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+        let reference = mean(baseline);
+        let difference: Vec<f64> = baseline.iter().zip(candidate).map(|(b, c)| c - b).collect();
+        Some(Self {
+            baseline: Drift::analyze(baseline, reference)?,
+            candidate: Drift::analyze(candidate, reference)?,
+            difference: Drift::analyze(&difference, reference)?,
+            alpha: family_alpha / 6.0,
+        })
+    }
+
+    /// Whether no check found a trend or shift.
+    pub fn stationary(&self) -> bool {
+        self.findings().is_empty()
+    }
+
+    /// What the checks found, e.g. `"candidate trend (tau +0.41, p=0.0002)"`
+    /// or `"difference shift +8.2% at sample 57 (p=0.0031)"`.
+    pub fn findings(&self) -> Vec<String> {
+        let mut findings = Vec::new();
+        for (label, drift) in [
+            ("baseline", &self.baseline),
+            ("candidate", &self.candidate),
+            ("difference", &self.difference),
+        ] {
+            if drift.trending(self.alpha) {
+                findings.push(format!(
+                    "{label} trend (tau {:+.2}, p={:.4})",
+                    drift.trend.tau, drift.trend.p_value
+                ));
+            }
+            if drift.shifted(self.alpha) {
+                let changepoint = &drift.changepoint;
+                findings.push(format!(
+                    "{label} shift {:+.1}% at sample {} (p={:.4})",
+                    changepoint.shift_percent, changepoint.index, changepoint.p_value
+                ));
+            }
+        }
+        findings
+    }
+}
+
+/// Per-side summary of heap allocations across a benchmark's samples.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AllocationSummary {
+    /// Mean allocations per iteration.
+    pub count_per_iter: f64,
+    /// Mean bytes allocated per iteration.
+    pub bytes_per_iter: f64,
+    /// Highest peak live heap size seen in any sample.
+    pub peak_live_bytes: u64,
+}
+
+impl AllocationSummary {
+    /// Summarize the allocations of samples of `iterations` iterations each.
+    pub fn from_samples(samples: &[AllocationStats], iterations: u64) -> Self {
+        let n = samples.len().max(1) as f64;
+        Self {
+            count_per_iter: samples
+                .iter()
+                .map(|s| s.count_per_iter(iterations))
+                .sum::<f64>()
+                / n,
+            bytes_per_iter: samples
+                .iter()
+                .map(|s| s.bytes_per_iter(iterations))
+                .sum::<f64>()
+                / n,
+            peak_live_bytes: samples.iter().map(|s| s.peak_live_bytes).max().unwrap_or(0),
+        }
+    }
+}
+
+/// A comparison of a per-iteration count, such as allocations or bytes.
+///
+/// The figures of a [`TestResult`] on the counts, with means in the counted
+/// unit rather than nanoseconds.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CountTest {
+    pub p_value: f64,
+    pub statistically_significant: bool,
+    /// Percent difference (positive = candidate needs less).
+    pub effect_size: f64,
+    pub change_ci_low: f64,
+    pub change_ci_high: f64,
+    pub winner: Option<Side>,
+    /// Mean count per iteration on the baseline.
+    pub baseline_mean: f64,
+    /// Mean count per iteration on the candidate.
+    pub candidate_mean: f64,
+}
+
+impl From<TestResult> for CountTest {
+    fn from(result: TestResult) -> Self {
+        Self {
+            p_value: result.p_value,
+            statistically_significant: result.statistically_significant,
+            effect_size: result.effect_size,
+            change_ci_low: result.change_ci_low,
+            change_ci_high: result.change_ci_high,
+            winner: result.winner,
+            baseline_mean: result.baseline_mean_ns,
+            candidate_mean: result.candidate_mean_ns,
+        }
+    }
+}
+
+impl CountTest {
+    /// Withdraw significance unless the p-value is below `alpha`, as
+    /// [`apply_bonferroni`] does for a [`TestResult`].
+    pub fn require_alpha(&mut self, alpha: f64) {
+        if self.p_value >= alpha {
+            self.statistically_significant = false;
+            self.winner = None;
+        }
+    }
+}
+
+/// Heap allocations compared between baseline and candidate.
+///
+/// The tests run on per-iteration figures, with the same sign convention as
+/// the time comparison: a positive effect size means the candidate allocates
+/// less.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AllocationComparison {
+    pub baseline: AllocationSummary,
+    pub candidate: AllocationSummary,
+    /// Comparison of allocations per iteration.
+    pub count_test: CountTest,
+    /// Comparison of bytes allocated per iteration.
+    pub bytes_test: CountTest,
+}
+
+impl AllocationComparison {
+    /// Compare per-sample allocation stats of samples of `iterations` iterations each.
+    pub fn analyze(
+        test: &dyn StatisticalTest,
+        baseline: &[AllocationStats],
+        candidate: &[AllocationStats],
+        iterations: u64,
+    ) -> Self {
+        let counts = |samples: &[AllocationStats]| -> Vec<f64> {
+            samples
+                .iter()
+                .map(|s| s.count_per_iter(iterations))
+                .collect()
+        };
+        let bytes = |samples: &[AllocationStats]| -> Vec<f64> {
+            samples
+                .iter()
+                .map(|s| s.bytes_per_iter(iterations))
+                .collect()
+        };
+        Self {
+            baseline: AllocationSummary::from_samples(baseline, iterations),
+            candidate: AllocationSummary::from_samples(candidate, iterations),
+            count_test: test
+                .analyze_values(&counts(baseline), &counts(candidate))
+                .into(),
+            bytes_test: test
+                .analyze_values(&bytes(baseline), &bytes(candidate))
+                .into(),
+        }
+    }
+
+    /// Whether either allocation count or volume changed significantly.
+    pub fn changed(&self) -> bool {
+        self.count_test.statistically_significant || self.bytes_test.statistically_significant
+    }
+}
+
+/// A secondary metric compared between baseline and candidate, such as
+/// instructions per iteration.
+///
+/// The test runs on per-iteration values with the same sign convention as the
+/// time comparison: a positive effect size means the candidate needs less.
+/// Its `*_mean_ns` fields hold the metric's mean per iteration.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MetricComparison {
+    /// Name of the metric, e.g. `instructions`.
+    pub metric: String,
+    pub test_result: TestResult,
+}
+
+impl MetricComparison {
+    /// Compare per-iteration values of `metric`, one per sample.
+    pub fn analyze(
+        test: &dyn StatisticalTest,
+        metric: impl Into<String>,
+        baseline: &[f64],
+        candidate: &[f64],
+    ) -> Self {
+        Self {
+            metric: metric.into(),
+            test_result: test.analyze_values(baseline, candidate),
+        }
+    }
+
+    /// Change of the candidate relative to the baseline, in percent
+    /// (negative means the candidate needs less).
+    pub fn change_percent(&self) -> f64 {
+        -self.test_result.effect_size
+    }
+}
+
+/// Bonferroni-correct the secondary tests (perf counters and allocations)
+/// of `comparisons` at a family-wise `family_alpha`, like the primary test.
+///
+/// Each counter, allocation count and allocated bytes is a family of its
+/// own, across the benchmarks that report it.
+pub fn correct_secondary_tests(comparisons: &mut [BenchmarkComparison], family_alpha: f64) {
+    let mut names: Vec<String> = Vec::new();
+    for metric in comparisons.iter().flat_map(|c| &c.metrics) {
+        if !names.contains(&metric.metric) {
+            names.push(metric.metric.clone());
+        }
+    }
+    for name in &names {
+        let mut family: Vec<&mut MetricComparison> = comparisons
+            .iter_mut()
+            .flat_map(|c| c.metrics.iter_mut())
+            .filter(|m| &m.metric == name)
+            .collect();
+        let mut results: Vec<TestResult> = family.iter().map(|m| m.test_result.clone()).collect();
+        apply_bonferroni(&mut results, family_alpha);
+        for (metric, result) in family.iter_mut().zip(results) {
+            metric.test_result = result;
+        }
+    }
+
+    let mut allocations: Vec<&mut AllocationComparison> = comparisons
+        .iter_mut()
+        .filter_map(|c| c.allocations.as_mut())
+        .collect();
+    if allocations.len() > 1 {
+        let per_test_alpha = family_alpha / allocations.len() as f64;
+        for allocation in allocations.iter_mut() {
+            allocation.count_test.require_alpha(per_test_alpha);
+            allocation.bytes_test.require_alpha(per_test_alpha);
+        }
+    }
+}
+
+/// Suite-level aggregate: the (optionally weighted) geometric mean of
+/// per-benchmark mean-time ratios, candidate over baseline.
+///
+/// Same sign convention as [`TestResult::effect_size`]: a positive `change`
+/// means the candidate is faster overall.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SuiteSummary {
+    /// Number of benchmarks aggregated.
+    pub benchmarks: usize,
+    /// Geometric-mean change across the suite, in percent.
+    pub change: f64,
+    /// Lower bound of the bootstrap confidence interval on `change`.
+    pub change_ci_low: f64,
+    /// Upper bound of the bootstrap confidence interval on `change`.
+    pub change_ci_high: f64,
+    /// Confidence level of the interval.
+    pub confidence_level: f64,
+    /// Whether per-benchmark weights were applied.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub weighted: bool,
+}
+
+impl SuiteSummary {
+    /// Aggregate `benchmarks`, each given as its baseline and candidate
+    /// measurements and its weight. The `bootstrap` confidence interval
+    /// resamples within each benchmark's samples.
+    ///
+    /// Benchmarks with a non-positive weight or mean are left out. Returns
+    /// `None` if fewer than two remain, since a one-benchmark "suite" just
+    /// repeats that benchmark's row.
+    pub fn compute(
+        benchmarks: &[(&[f64], &[f64], f64)],
+        confidence_level: f64,
+        weighted: bool,
+        bootstrap: &Bootstrap,
+    ) -> Option<Self> {
+        let positive_mean = |values: &[f64]| !values.is_empty() && values.iter().sum::<f64>() > 0.0;
+        let included: Vec<(&[f64], &[f64], f64)> = benchmarks
+            .iter()
+            .copied()
+            .filter(|(baseline, candidate, weight)| {
+                *weight > 0.0 && positive_mean(baseline) && positive_mean(candidate)
+            })
+            .collect();
+        if included.len() < 2 {
+            return None;
+        }
+        let (change_ci_low, change_ci_high) =
+            bootstrap.geomean_change_ci(&included, confidence_level);
+        Some(Self {
+            benchmarks: included.len(),
+            change: geomean_change(&included),
+            change_ci_low,
+            change_ci_high,
+            confidence_level,
+            weighted,
+        })
+    }
+
+    /// Whether the confidence interval excludes zero.
+    pub fn changed(&self) -> bool {
+        self.change_ci_low > 0.0 || self.change_ci_high < 0.0
+    }
 }
 
 mod schema;
-pub use schema::{ConfigSnapshot, Report, ReportMetadata};
+pub use schema::{ConfigSnapshot, HarnessPlacement, Report, ReportMetadata, SystemCheck};
 
 #[cfg(test)]
 mod serde_tests {
@@ -34,6 +606,10 @@ mod serde_tests {
                 min_ns: 900,
                 max_ns: 1100,
                 sample_count: 50,
+                median_ns: 1000.0,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             candidate_stats: SampleStats {
                 mean_ns: 800.0,
@@ -41,6 +617,10 @@ mod serde_tests {
                 min_ns: 720,
                 max_ns: 880,
                 sample_count: 50,
+                median_ns: 800.0,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             test_result: TestResult {
                 p_value: 0.001,
@@ -52,7 +632,17 @@ mod serde_tests {
                 winner: Some(Side::Candidate),
                 baseline_mean_ns: 1000.0,
                 candidate_mean_ns: 800.0,
+                equivalence: None,
+                power: None,
+                posterior: None,
+                change_interval: None,
             },
+            allocations: None,
+            metrics: Vec::new(),
+            contamination: None,
+            output_check: None,
+            quantiles: Vec::new(),
+            stationarity: None,
         }
     }
 
@@ -86,7 +676,7 @@ mod serde_tests {
 #[cfg(test)]
 mod report_tests {
     use super::*;
-    use crate::stats::{Side, TestResult};
+    use crate::stats::{Side, TestResult, WelchTTest};
 
     #[test]
     fn report_roundtrips_through_json_with_metadata() {
@@ -102,7 +692,18 @@ mod report_tests {
                     minimum_effect_size: 2.0,
                     sample_size: 50,
                     correct_multiple_comparisons: true,
+                    equivalence_margin: None,
+                    power: None,
+                    method: None,
+                    bootstrap: None,
+                    sampling: None,
+                    change_interval: None,
                 },
+                baseline_harnesses: Vec::new(),
+                candidate_harnesses: Vec::new(),
+                baseline_placement: None,
+                candidate_placement: None,
+                system_checks: Vec::new(),
             },
             comparisons: vec![BenchmarkComparison {
                 name: "bench_foo".to_string(),
@@ -112,6 +713,10 @@ mod report_tests {
                     min_ns: 900,
                     max_ns: 1100,
                     sample_count: 50,
+                    median_ns: 1000.0,
+                    quantiles: Vec::new(),
+                    fit: None,
+                    modality: None,
                 },
                 candidate_stats: SampleStats {
                     mean_ns: 800.0,
@@ -119,6 +724,10 @@ mod report_tests {
                     min_ns: 720,
                     max_ns: 880,
                     sample_count: 50,
+                    median_ns: 800.0,
+                    quantiles: Vec::new(),
+                    fit: None,
+                    modality: None,
                 },
                 test_result: TestResult {
                     p_value: 0.001,
@@ -130,8 +739,19 @@ mod report_tests {
                     winner: Some(Side::Candidate),
                     baseline_mean_ns: 1000.0,
                     candidate_mean_ns: 800.0,
+                    equivalence: None,
+                    power: None,
+                    posterior: None,
+                    change_interval: None,
                 },
+                allocations: None,
+                metrics: Vec::new(),
+                contamination: None,
+                output_check: None,
+                quantiles: Vec::new(),
+                stationarity: None,
             }],
+            suite: None,
         };
 
         let json = serde_json::to_string_pretty(&report).expect("serialize");
@@ -146,4 +766,288 @@ mod report_tests {
         assert_eq!(parsed.comparisons.len(), 1);
         assert_eq!(parsed.comparisons[0].name, "bench_foo");
     }
+
+    #[test]
+    fn report_metadata_without_harness_info_parses() {
+        let json = r#"{
+            "hypobench_version": "0.5.0",
+            "generated_at": "2026-04-18T10:00:00Z",
+            "baseline_ref": "abc123",
+            "candidate_ref": "def456",
+            "config": {
+                "confidence_level": 0.99,
+                "minimum_effect_size": 2.0,
+                "sample_size": 50,
+                "correct_multiple_comparisons": true
+            }
+        }"#;
+        let metadata: ReportMetadata = serde_json::from_str(json).expect("deserialize");
+        assert!(metadata.baseline_harnesses.is_empty());
+        assert!(metadata.candidate_harnesses.is_empty());
+
+        assert!(metadata.baseline_placement.is_none());
+
+        let out = serde_json::to_string(&metadata).expect("serialize");
+        assert!(!out.contains("harnesses"));
+        assert!(!out.contains("placement"));
+    }
+
+    #[test]
+    fn harness_placement_summary() {
+        let placement = HarnessPlacement {
+            launcher: vec!["taskset".to_string(), "-c".to_string(), "2".to_string()],
+            env: [("RAYON_NUM_THREADS".to_string(), "1".to_string())].into(),
+            cpus: vec![2, 3],
+            nice: Some(-5),
+            fifo_priority: Some(10),
+        };
+        assert_eq!(
+            placement.summary(),
+            "via taskset -c 2 · env RAYON_NUM_THREADS=1 · CPUs 2,3 · nice -5 · SCHED_FIFO 10"
+        );
+        assert!(!placement.is_default());
+
+        let single = HarnessPlacement {
+            cpus: vec![1],
+            ..Default::default()
+        };
+        assert_eq!(single.summary(), "CPU 1");
+        assert!(HarnessPlacement::default().is_default());
+        assert_eq!(HarnessPlacement::default().summary(), "default");
+    }
+
+    #[test]
+    fn allocation_comparison_flags_extra_allocations() {
+        // 4 iterations per sample: baseline 12 allocs/iter, candidate 15.
+        let stats = |count, bytes| AllocationStats {
+            count,
+            bytes,
+            peak_live_bytes: 1024,
+        };
+        let baseline = vec![stats(48, 1920); 10];
+        let candidate = vec![stats(60, 2304); 10];
+        let comparison =
+            AllocationComparison::analyze(&WelchTTest::new(0.95), &baseline, &candidate, 4);
+
+        assert_eq!(comparison.baseline.count_per_iter, 12.0);
+        assert_eq!(comparison.candidate.count_per_iter, 15.0);
+        assert_eq!(comparison.candidate.bytes_per_iter, 576.0);
+        assert_eq!(comparison.candidate.peak_live_bytes, 1024);
+        assert!(comparison.changed());
+        assert_eq!(comparison.count_test.winner, Some(Side::Baseline));
+        assert_eq!(comparison.count_test.baseline_mean, 12.0);
+        assert_eq!(comparison.bytes_test.candidate_mean, 576.0);
+
+        let same = AllocationComparison::analyze(&WelchTTest::new(0.95), &baseline, &baseline, 4);
+        assert!(!same.changed());
+    }
+
+    #[test]
+    fn output_check_serializes_with_status_tag() {
+        assert_eq!(OutputCheck::compare("ab", "ab"), OutputCheck::Match);
+        let mismatch = OutputCheck::compare("ab", "cd");
+        assert!(mismatch.is_mismatch());
+        let json = serde_json::to_string(&mismatch).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"status":"mismatch","baseline":"ab","candidate":"cd"}"#
+        );
+        let parsed: OutputCheck = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, mismatch);
+    }
+
+    #[test]
+    fn metric_comparison_reports_relative_change() {
+        let baseline: Vec<f64> = (0..10).map(|i| 1000.0 + i as f64).collect();
+        let candidate: Vec<f64> = baseline.iter().map(|v| v * 0.959).collect();
+        let comparison = MetricComparison::analyze(
+            &WelchTTest::new(0.95),
+            "instructions",
+            &baseline,
+            &candidate,
+        );
+
+        assert_eq!(comparison.metric, "instructions");
+        assert!((comparison.change_percent() + 4.1).abs() < 1e-9);
+        assert_eq!(comparison.test_result.winner, Some(Side::Candidate));
+    }
+
+    #[test]
+    fn secondary_tests_are_bonferroni_corrected() {
+        let significant = |p_value| TestResult {
+            p_value,
+            statistically_significant: true,
+            effect_size: -10.0,
+            change_ci_low: -12.0,
+            change_ci_high: -8.0,
+            confidence_level: 0.95,
+            winner: Some(Side::Baseline),
+            baseline_mean_ns: 100.0,
+            candidate_mean_ns: 110.0,
+            equivalence: None,
+            power: None,
+            posterior: None,
+            change_interval: None,
+        };
+        let stats = SampleStats::from_samples(&[100.0, 110.0], &[]);
+        let comparison = |name: &str, p_value| BenchmarkComparison {
+            name: name.to_string(),
+            baseline_stats: stats.clone(),
+            candidate_stats: stats.clone(),
+            test_result: significant(p_value),
+            allocations: Some(AllocationComparison {
+                baseline: AllocationSummary::from_samples(&[], 1),
+                candidate: AllocationSummary::from_samples(&[], 1),
+                count_test: significant(p_value).into(),
+                bytes_test: significant(p_value).into(),
+            }),
+            metrics: vec![MetricComparison {
+                metric: "instructions".to_string(),
+                test_result: significant(p_value),
+            }],
+            contamination: None,
+            output_check: None,
+            quantiles: Vec::new(),
+            stationarity: None,
+        };
+        // Both pass at 0.05; only the second passes at 0.05 / 2.
+        let mut comparisons = vec![comparison("a", 0.04), comparison("b", 0.001)];
+        correct_secondary_tests(&mut comparisons, 0.05);
+
+        let a = &comparisons[0];
+        assert!(!a.metrics[0].test_result.statistically_significant);
+        assert_eq!(a.metrics[0].test_result.winner, None);
+        let allocations = a.allocations.as_ref().unwrap();
+        assert!(!allocations.count_test.statistically_significant);
+        assert!(!allocations.bytes_test.statistically_significant);
+        // The primary test is corrected separately.
+        assert!(a.test_result.statistically_significant);
+
+        let b = &comparisons[1];
+        assert!(b.metrics[0].test_result.statistically_significant);
+        assert!(b.allocations.as_ref().unwrap().changed());
+    }
+
+    #[test]
+    fn sample_stats_include_median_and_quantiles() {
+        let samples: Vec<f64> = (1..=101).map(f64::from).collect();
+        let stats = SampleStats::from_samples(&samples, &[0.99, 0.9]);
+        assert_eq!(stats.mean_ns, 51.0);
+        assert_eq!(stats.median_ns, 51.0);
+        assert_eq!((stats.min_ns, stats.max_ns), (1, 101));
+        assert_eq!(stats.quantiles[0].quantile, 0.9);
+        assert_eq!(stats.quantile(0.9), Some(91.0));
+        assert_eq!(stats.quantile(0.99), Some(100.0));
+        assert_eq!(stats.quantile(0.5), None);
+        assert!(!stats.multimodal());
+    }
+
+    #[test]
+    fn sample_stats_flag_a_second_mode() {
+        // Every third run hits a slow path.
+        let samples: Vec<f64> = (0..90)
+            .map(|i| if i % 3 == 2 { 2000.0 } else { 1000.0 } + (i % 10) as f64)
+            .collect();
+        let stats = SampleStats::from_samples(&samples, &[]);
+        assert!(stats.multimodal());
+        let modes = &stats.modality.as_ref().unwrap().modes;
+        assert_eq!(modes.len(), 2);
+        assert!((modes[0].share - 2.0 / 3.0).abs() < 1e-9);
+
+        let few = SampleStats::from_samples(&samples[..10], &[]);
+        assert!(few.modality.is_none() && !few.multimodal());
+    }
+
+    #[test]
+    fn quantile_comparison_flags_tail_regression() {
+        let baseline: Vec<f64> = (0..100).map(|i| 1000.0 + (i % 10) as f64).collect();
+        let candidate: Vec<f64> = baseline
+            .iter()
+            .enumerate()
+            .map(|(i, v)| if i % 10 == 9 { v * 2.0 } else { *v })
+            .collect();
+        let bootstrap = Bootstrap::default();
+
+        let median = QuantileComparison::analyze(&baseline, &candidate, 0.5, 0.95, 1.0, &bootstrap);
+        assert_eq!(median.change, 0.0);
+        assert_eq!(median.verdict(), Verdict::Inconclusive);
+
+        let p99 = QuantileComparison::analyze(&baseline, &candidate, 0.99, 0.95, 1.0, &bootstrap);
+        assert_eq!(p99.label(), "p99");
+        assert!((p99.change + 100.0).abs() < 1e-9, "{}", p99.change);
+        assert_eq!(p99.verdict(), Verdict::Slower);
+
+        // Below the minimum effect size, even a clear change isn't significant.
+        let gated =
+            QuantileComparison::analyze(&baseline, &candidate, 0.99, 0.95, 150.0, &bootstrap);
+        assert_eq!(gated.verdict(), Verdict::Inconclusive);
+    }
+
+    #[test]
+    fn stationarity_flags_a_shift_on_one_side() {
+        let noise = |i: usize| ((i * 7919) % 101) as f64 / 10.0;
+        let baseline: Vec<f64> = (0..100).map(|i| 1000.0 + noise(i)).collect();
+        let steady: Vec<f64> = (0..100).map(|i| 1000.0 + noise(i + 17)).collect();
+        let stationarity = Stationarity::analyze(&baseline, &steady, 0.05).unwrap();
+        assert!(stationarity.stationary(), "{:?}", stationarity.findings());
+        assert!((stationarity.alpha - 0.05 / 6.0).abs() < 1e-12);
+
+        // A background job slows the candidate down from sample 60 on.
+        let mut shifted = steady.clone();
+        for x in &mut shifted[60..] {
+            *x += 50.0;
+        }
+        let stationarity = Stationarity::analyze(&baseline, &shifted, 0.05).unwrap();
+        assert!(!stationarity.stationary());
+        let findings = stationarity.findings();
+        assert!(
+            findings.iter().any(|f| f.starts_with("candidate shift +")),
+            "{findings:?}"
+        );
+        let shift = stationarity.candidate.changepoint;
+        assert!(shift.index.abs_diff(60) <= 1, "{}", shift.index);
+        assert!(
+            (shift.shift_percent - 5.0).abs() < 0.5,
+            "{}",
+            shift.shift_percent
+        );
+        assert!(
+            findings.iter().any(|f| f.starts_with("difference shift")),
+            "{findings:?}"
+        );
+        assert!(!findings.iter().any(|f| f.starts_with("baseline")));
+
+        assert!(Stationarity::analyze(&baseline[..5], &steady[..5], 0.05).is_none());
+    }
+
+    #[test]
+    fn quantile_labels() {
+        assert_eq!(quantile_label(0.5), "p50");
+        assert_eq!(quantile_label(0.9), "p90");
+        assert_eq!(quantile_label(0.999), "p99.9");
+        assert_eq!(quantile_label(0.9995), "p99.95");
+    }
+
+    #[test]
+    fn suite_summary_aggregates_benchmarks() {
+        let base: Vec<f64> = (0..20).map(|i| 1000.0 + (i % 5) as f64).collect();
+        let faster: Vec<f64> = base.iter().map(|v| v * 0.9).collect();
+        let same = base.clone();
+        let benchmarks = [
+            (&base[..], &faster[..], 1.0),
+            (&base[..], &same[..], 1.0),
+            (&base[..], &[][..], 1.0),
+        ];
+
+        // The empty benchmark is dropped; sqrt(0.9) → ~5.13% faster.
+        let suite = SuiteSummary::compute(&benchmarks, 0.95, false, &Bootstrap::default()).unwrap();
+        assert_eq!(suite.benchmarks, 2);
+        assert!((suite.change - (1.0 - 0.9f64.sqrt()) * 100.0).abs() < 1e-9);
+        assert!(suite.changed());
+        assert!(suite.change_ci_low <= suite.change && suite.change <= suite.change_ci_high);
+
+        // Weighting out the unchanged benchmark leaves just one.
+        let weighted = [(&base[..], &faster[..], 1.0), (&base[..], &same[..], 0.0)];
+        assert!(SuiteSummary::compute(&weighted, 0.95, true, &Bootstrap::default()).is_none());
+    }
 }
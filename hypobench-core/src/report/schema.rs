@@ -4,7 +4,11 @@
 //! consumers (web UIs, dashboards) read. Bump `schema_version` on any breaking
 //! shape change.
 
-use super::BenchmarkComparison;
+use std::collections::BTreeMap;
+
+use super::{BenchmarkComparison, SuiteSummary};
+use crate::protocol::HarnessInfo;
+use crate::stats::Bootstrap;
 
 /// A complete benchmark comparison report, ready to serialize to JSON.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -15,6 +19,10 @@ pub struct Report {
     pub metadata: ReportMetadata,
     /// The per-benchmark comparisons.
     pub comparisons: Vec<BenchmarkComparison>,
+    /// Suite-wide geometric-mean change, when there are at least two
+    /// benchmarks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suite: Option<SuiteSummary>,
 }
 
 /// Metadata describing the context in which a report was produced.
@@ -30,6 +38,101 @@ pub struct ReportMetadata {
     pub candidate_ref: String,
     /// Snapshot of the statistical configuration used.
     pub config: ConfigSnapshot,
+    /// Build provenance of each baseline harness that was run, as reported by
+    /// `GET /info`. Empty if the harnesses predate the endpoint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub baseline_harnesses: Vec<HarnessInfo>,
+    /// Build provenance of each candidate harness that was run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidate_harnesses: Vec<HarnessInfo>,
+    /// Launcher, environment, CPU placement and priority of the spawned
+    /// baseline harnesses, if any was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_placement: Option<HarnessPlacement>,
+    /// Launcher, environment, CPU placement and priority of the spawned
+    /// candidate harnesses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_placement: Option<HarnessPlacement>,
+    /// Preflight checks of the machine the orchestrator ran on. Empty if none
+    /// could be made (e.g. not on Linux).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub system_checks: Vec<SystemCheck>,
+}
+
+/// One preflight check for a source of system noise, e.g. the CPU governor.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SystemCheck {
+    /// What was checked, e.g. `cpu_governor`.
+    pub name: String,
+    /// Whether the system looked quiet in this respect.
+    pub ok: bool,
+    /// What was found, e.g. `8 of 8 CPUs use the powersave governor`.
+    pub detail: String,
+    /// How to fix a failed check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub advice: Option<String>,
+}
+
+/// How a spawned harness was launched, where it was allowed to run and at
+/// what priority.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HarnessPlacement {
+    /// Command the harness binary was run under (e.g. `numactl --cpunodebind=0 --`),
+    /// with the binary path appended. Empty if it was executed directly.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub launcher: Vec<String>,
+    /// Extra environment variables the harness was started with.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// CPUs the harness was pinned to. Empty if the kernel placed it freely.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpus: Vec<usize>,
+    /// Nice value the harness ran at, if one was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice: Option<i32>,
+    /// Real-time `SCHED_FIFO` priority the harness ran at, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fifo_priority: Option<u32>,
+}
+
+impl HarnessPlacement {
+    /// Whether nothing was requested, i.e. the harness ran as a plain child.
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Short human-readable summary, e.g.
+    /// `via taskset -c 2 · env RAYON_NUM_THREADS=1 · CPUs 2,3 · nice -5 · SCHED_FIFO 10`.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.launcher.is_empty() {
+            parts.push(format!("via {}", self.launcher.join(" ")));
+        }
+        if !self.env.is_empty() {
+            let env: Vec<String> = self
+                .env
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            parts.push(format!("env {}", env.join(" ")));
+        }
+        if !self.cpus.is_empty() {
+            let cpus: Vec<String> = self.cpus.iter().map(usize::to_string).collect();
+            let noun = if self.cpus.len() == 1 { "CPU" } else { "CPUs" };
+            parts.push(format!("{} {}", noun, cpus.join(",")));
+        }
+        if let Some(nice) = self.nice {
+            parts.push(format!("nice {}", nice));
+        }
+        if let Some(priority) = self.fifo_priority {
+            parts.push(format!("SCHED_FIFO {}", priority));
+        }
+        if parts.is_empty() {
+            "default".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
 }
 
 /// Statistical configuration captured at report time.
@@ -46,6 +149,26 @@ pub struct ConfigSnapshot {
     pub sample_size: u32,
     /// Whether Bonferroni correction was applied.
     pub correct_multiple_comparisons: bool,
+    /// Equivalence margin (percent), if equivalence was tested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equivalence_margin: Option<f64>,
+    /// Power at which minimum detectable effects were computed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<f64>,
+    /// Statistical test used (`"welch"` or `"bayesian"`); Welch if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Bootstrap settings behind the confidence intervals; the percentile
+    /// defaults if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bootstrap: Option<Bootstrap>,
+    /// Iteration counts per sample (`"flat"` or `"linear"`); flat if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<String>,
+    /// How the mean change CI was computed (`"bootstrap"`, `"fieller"` or
+    /// `"delta"`); bootstrapped if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_interval: Option<String>,
 }
 
 impl Report {
@@ -0,0 +1,389 @@
+//! Bayesian comparison of two sample sets.
+//!
+//! Answers "how likely is it that the candidate is faster?" directly, where a
+//! p-value only says how surprising the data would be if nothing changed.
+//!
+//! Each side's log-times get a normal likelihood with the noninformative prior
+//! `p(μ, σ) ∝ 1/σ`. The marginal posterior of each log-mean is then a scaled
+//! Student-t with `n - 1` degrees of freedom centred on the sample mean of
+//! the logs. The difference of two such posteriors has no closed form, so
+//! [`BayesianTest`] draws from both (with the bootstrap's seed and draw
+//! count) and reads probabilities and the credible interval off the draws. Working
+//! on logs makes the change a ratio of geometric means, which suits
+//! right-skewed timings. Samples with zero or negative values (allocation
+//! counts, say) are compared on the raw scale instead.
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use super::bootstrap::{percentile, Bootstrap};
+use super::{Equivalence, Side, StatisticalTest, TestResult};
+
+/// Posterior summary of a comparison, in the same percent-change convention
+/// as [`TestResult::effect_size`] (positive = candidate faster).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Posterior {
+    /// Posterior probability that the candidate is faster.
+    pub prob_faster: f64,
+    /// Margin (percent) for `prob_within_margin`: the equivalence margin if
+    /// one is configured, else the minimum effect size. Zero if neither is.
+    pub margin: f64,
+    /// Posterior probability that the change is within ±`margin`.
+    pub prob_within_margin: f64,
+}
+
+impl Posterior {
+    /// Posterior probability that the candidate is slower.
+    pub fn prob_slower(&self) -> f64 {
+        1.0 - self.prob_faster
+    }
+
+    /// One-line summary for reports, e.g. "97% probability of regression,
+    /// 2% within ±1%".
+    pub fn summary(&self) -> String {
+        let mut summary = if self.prob_slower() >= 0.5 {
+            format!(
+                "{:.0}% probability of regression",
+                self.prob_slower() * 100.0
+            )
+        } else {
+            format!(
+                "{:.0}% probability of improvement",
+                self.prob_faster * 100.0
+            )
+        };
+        if self.margin > 0.0 {
+            summary.push_str(&format!(
+                ", {:.0}% within ±{}%",
+                self.prob_within_margin * 100.0,
+                self.margin
+            ));
+        }
+        summary
+    }
+}
+
+/// Bayesian estimator of the change between baseline and candidate.
+///
+/// `TestResult::change_ci_low`/`change_ci_high` hold the equal-tailed
+/// credible interval at `confidence_level`, and `TestResult::posterior` the
+/// probabilities. `p_value` is `2 · min(P(faster), P(slower))`, the posterior
+/// counterpart of a two-sided p-value (under these priors it is close to
+/// Welch's), so significance, Bonferroni correction and the verdict work the
+/// same as for [`super::WelchTTest`]: a result is significant when one
+/// direction has posterior probability above `1 - α/2` and the change clears
+/// `minimum_effect_size`.
+#[derive(Debug, Clone)]
+pub struct BayesianTest {
+    /// Credible-interval mass and decision threshold (default: 0.95).
+    pub confidence_level: f64,
+    /// Minimum absolute change (percent) for a result to count as significant.
+    pub minimum_effect_size: f64,
+    /// When set, a result whose posterior probability of being within
+    /// ±margin percent exceeds `confidence_level` is reported as equivalent.
+    pub equivalence_margin: Option<f64>,
+    /// Number of posterior draws (`resamples`) and their seed, fixed so
+    /// identical samples give identical results. At the default 10_000 draws
+    /// tail probabilities are accurate to about ±0.5 percentage points. The
+    /// interval method doesn't apply.
+    pub bootstrap: Bootstrap,
+}
+
+impl Default for BayesianTest {
+    fn default() -> Self {
+        Self {
+            confidence_level: 0.95,
+            minimum_effect_size: 0.0,
+            equivalence_margin: None,
+            bootstrap: Bootstrap::default(),
+        }
+    }
+}
+
+impl BayesianTest {
+    /// Create a Bayesian test with the given credible level.
+    ///
+    /// # Panics
+    /// Panics if confidence_level is not in the range (0, 1).
+    pub fn new(confidence_level: f64) -> Self {
+        assert!(
+            confidence_level > 0.0 && confidence_level < 1.0,
+            "confidence_level must be between 0 and 1 (exclusive)"
+        );
+        Self {
+            confidence_level,
+            ..Default::default()
+        }
+    }
+
+    /// Set the minimum absolute change (percent) for significance.
+    pub fn with_minimum_effect_size(mut self, threshold: f64) -> Self {
+        assert!(threshold >= 0.0, "minimum_effect_size must be non-negative");
+        self.minimum_effect_size = threshold;
+        self
+    }
+
+    /// Set the equivalence margin (percent). See [`Self::equivalence_margin`].
+    pub fn with_equivalence_margin(mut self, margin: f64) -> Self {
+        assert!(margin > 0.0, "equivalence_margin must be positive");
+        self.equivalence_margin = Some(margin);
+        self
+    }
+
+    /// Set the draw count and seed. See [`Self::bootstrap`].
+    pub fn with_bootstrap(mut self, bootstrap: Bootstrap) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+}
+
+/// Sample mean and standard error of the mean.
+fn mean_and_se(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, (variance / n).sqrt())
+}
+
+/// A standard normal draw (Box–Muller).
+pub(super) fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = 1.0 - rng.random::<f64>();
+    let u2: f64 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// A Gamma(`shape`, 1) draw (Marsaglia–Tsang).
+fn gamma(shape: f64, rng: &mut impl Rng) -> f64 {
+    if shape < 1.0 {
+        let u: f64 = 1.0 - rng.random::<f64>();
+        return gamma(shape + 1.0, rng) * u.powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u: f64 = 1.0 - rng.random::<f64>();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// A Student-t draw with `df` degrees of freedom.
+fn student_t(df: f64, rng: &mut impl Rng) -> f64 {
+    // χ²(df) = 2 · Gamma(df/2).
+    standard_normal(rng) / (2.0 * gamma(df / 2.0, rng) / df).sqrt()
+}
+
+impl StatisticalTest for BayesianTest {
+    fn analyze_values(&self, baseline: &[f64], candidate: &[f64]) -> TestResult {
+        let mean = |values: &[f64]| {
+            if values.is_empty() {
+                0.0
+            } else {
+                values.iter().sum::<f64>() / values.len() as f64
+            }
+        };
+        let baseline_mean_ns = mean(baseline);
+        let candidate_mean_ns = mean(candidate);
+        let margin = self.equivalence_margin.unwrap_or(self.minimum_effect_size);
+
+        if baseline.len() < 2 || candidate.len() < 2 || baseline_mean_ns == 0.0 {
+            return TestResult {
+                p_value: 1.0,
+                statistically_significant: false,
+                effect_size: 0.0,
+                change_ci_low: 0.0,
+                change_ci_high: 0.0,
+                confidence_level: self.confidence_level,
+                winner: None,
+                baseline_mean_ns,
+                candidate_mean_ns,
+                equivalence: self.equivalence_margin.map(|margin| Equivalence {
+                    margin,
+                    p_value: 1.0,
+                    equivalent: false,
+                }),
+                power: None,
+                posterior: None,
+                change_interval: None,
+            };
+        }
+
+        // Percent change (positive = candidate faster) from a pair of
+        // baseline/candidate location parameters. On the raw scale it is
+        // relative to the observed baseline mean, since a drawn one can be
+        // near zero.
+        let log_scale = baseline.iter().chain(candidate).all(|&x| x > 0.0);
+        let change = |b: f64, c: f64| {
+            if log_scale {
+                (1.0 - (c - b).exp()) * 100.0
+            } else {
+                (b - c) / baseline_mean_ns.abs() * 100.0
+            }
+        };
+        let logs = |values: &[f64]| -> Vec<f64> {
+            if log_scale {
+                values.iter().map(|x| x.ln()).collect()
+            } else {
+                values.to_vec()
+            }
+        };
+        let (b_mean, b_se) = mean_and_se(&logs(baseline));
+        let (c_mean, c_se) = mean_and_se(&logs(candidate));
+        let (b_df, c_df) = ((baseline.len() - 1) as f64, (candidate.len() - 1) as f64);
+
+        let mut rng = SmallRng::seed_from_u64(self.bootstrap.seed);
+        let mut draws: Vec<f64> = (0..self.bootstrap.resamples.max(1))
+            .map(|_| {
+                let b = b_mean + b_se * student_t(b_df, &mut rng);
+                let c = c_mean + c_se * student_t(c_df, &mut rng);
+                change(b, c)
+            })
+            .collect();
+        draws.sort_by(f64::total_cmp);
+
+        let share = |pred: &dyn Fn(f64) -> bool| {
+            draws.iter().filter(|&&d| pred(d)).count() as f64 / draws.len() as f64
+        };
+        // Ties (zero-variance samples) count half each way.
+        let prob_faster = share(&|d| d > 0.0) + share(&|d| d == 0.0) / 2.0;
+        let prob_within_margin = share(&|d| d.abs() < margin);
+        let effect_size = change(b_mean, c_mean);
+
+        let alpha = 1.0 - self.confidence_level;
+        let p_value = (2.0 * prob_faster.min(1.0 - prob_faster)).min(1.0);
+        let statistically_significant =
+            p_value < alpha && effect_size.abs() >= self.minimum_effect_size;
+        let winner = statistically_significant.then_some(if prob_faster > 0.5 {
+            Side::Candidate
+        } else {
+            Side::Baseline
+        });
+
+        TestResult {
+            p_value,
+            statistically_significant,
+            effect_size,
+            change_ci_low: percentile(&draws, alpha / 2.0),
+            change_ci_high: percentile(&draws, 1.0 - alpha / 2.0),
+            confidence_level: self.confidence_level,
+            winner,
+            baseline_mean_ns,
+            candidate_mean_ns,
+            equivalence: self.equivalence_margin.map(|margin| Equivalence {
+                margin,
+                p_value: 1.0 - prob_within_margin,
+                equivalent: 1.0 - prob_within_margin < alpha,
+            }),
+            power: None,
+            posterior: Some(Posterior {
+                prob_faster,
+                margin,
+                prob_within_margin,
+            }),
+            change_interval: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{Verdict, WelchTTest};
+    use std::time::Duration;
+
+    fn durations_from_nanos(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn test_clear_regression_is_near_certain() {
+        let baseline = durations_from_nanos(&[100, 101, 102, 99, 100, 98, 101, 100]);
+        let candidate = durations_from_nanos(&[110, 111, 109, 112, 110, 108, 111, 110]);
+
+        let result = BayesianTest::new(0.95)
+            .with_minimum_effect_size(1.0)
+            .analyze(&baseline, &candidate);
+        let posterior = result.posterior.unwrap();
+        assert!(posterior.prob_slower() > 0.99, "{posterior:?}");
+        assert_eq!(posterior.prob_within_margin, 0.0);
+        assert_eq!(result.verdict(), Verdict::Slower);
+        assert!(result.effect_size < -9.0 && result.effect_size > -11.0);
+        assert!(result.change_ci_low < result.effect_size);
+        assert!(result.change_ci_high > result.effect_size);
+        assert!(posterior
+            .summary()
+            .starts_with("100% probability of regression, 0% within ±1%"));
+    }
+
+    #[test]
+    fn test_tail_probability_tracks_welch_p_value() {
+        let baseline = durations_from_nanos(&[1000, 1040, 980, 1010, 995, 1030, 970, 1005]);
+        let candidate = durations_from_nanos(&[1010, 1050, 1000, 1020, 985, 1045, 990, 1015]);
+
+        let bayes = BayesianTest::default().analyze(&baseline, &candidate);
+        let welch = WelchTTest::default().analyze(&baseline, &candidate);
+        assert!(
+            (bayes.p_value - welch.p_value).abs() < 0.1,
+            "bayes p={} welch p={}",
+            bayes.p_value,
+            welch.p_value
+        );
+        assert_eq!(
+            bayes.statistically_significant,
+            welch.statistically_significant
+        );
+    }
+
+    #[test]
+    fn test_equivalence_and_raw_scale_fallback() {
+        let baseline = durations_from_nanos(&[1000, 1002, 998, 1001, 999, 1000, 1003, 997]);
+        let candidate = durations_from_nanos(&[1001, 1003, 999, 1002, 1000, 1001, 1004, 998]);
+        let result = BayesianTest::new(0.95)
+            .with_equivalence_margin(2.0)
+            .analyze(&baseline, &candidate);
+        let posterior = result.posterior.unwrap();
+        assert_eq!(posterior.margin, 2.0);
+        assert!(posterior.prob_within_margin > 0.99);
+        assert_eq!(result.verdict(), Verdict::Equivalent);
+
+        // Zero allocation counts can't be logged; still compared.
+        let result =
+            BayesianTest::default().analyze_values(&[0.0, 0.0, 1.0, 0.0], &[3.0, 4.0, 3.0, 4.0]);
+        assert!(result.posterior.unwrap().prob_slower() > 0.99);
+
+        let result = BayesianTest::default().analyze_values(&[1.0], &[2.0]);
+        assert!(result.posterior.is_none());
+        assert_eq!(result.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_draws_follow_bootstrap_settings() {
+        let baseline = durations_from_nanos(&[1000, 1040, 980, 1010, 995, 1030, 970, 1005]);
+        let candidate = durations_from_nanos(&[1010, 1050, 1000, 1020, 985, 1045, 990, 1015]);
+        let with = |resamples, seed| {
+            BayesianTest::default()
+                .with_bootstrap(Bootstrap {
+                    resamples,
+                    seed,
+                    ..Bootstrap::default()
+                })
+                .analyze(&baseline, &candidate)
+        };
+
+        let default = BayesianTest::default().analyze(&baseline, &candidate);
+        let same = with(10_000, Bootstrap::default().seed);
+        assert_eq!(default.change_ci_low, same.change_ci_low);
+        assert_eq!(default.posterior, same.posterior);
+        assert_ne!(with(10_000, 1).change_ci_low, default.change_ci_low);
+        assert_ne!(
+            with(500, Bootstrap::default().seed).posterior,
+            default.posterior
+        );
+    }
+}
@@ -1,5 +1,5 @@
 //! Non-parametric bootstrap CI on the relative mean difference between two
-//! sample sets.
+//! sample sets, and on the relative difference in a quantile.
 //!
 //! Given baseline and candidate sample vectors, resample each with replacement
 //! `n_resamples` times (independently), compute the per-resample
@@ -9,14 +9,139 @@
 //! and return the `(confidence_level / 2)` and `1 - (confidence_level / 2)`
 //! percentiles.
 //!
+//! Percentile intervals under-cover when the bootstrap distribution is skewed
+//! or biased, as it often is for small samples of timings. The bias-corrected
+//! and accelerated (BCa) interval shifts the two percentiles to correct for
+//! both; see [`BootstrapInterval::Bca`].
+//!
 //! This is a direct complement to the p-value produced by Welch's t-test: a p
 //! = 0.0001 effect with a change CI of `[-3.47%, -2.94%, -2.44%]` tells a very
 //! different story from the same p-value with `[-0.5%, -0.1%, +0.3%]`.
 
+use rand::rngs::SmallRng;
 use rand::seq::IndexedRandom;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use statrs::distribution::{ContinuousCDF, Normal};
 use std::time::Duration;
 
+use super::regression::LinearFit;
+
+/// How a bootstrap confidence interval is read off the resampled changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BootstrapInterval {
+    /// Equal-tailed percentiles of the bootstrap distribution.
+    #[default]
+    Percentile,
+    /// Bias-corrected and accelerated percentiles. The bias correction comes
+    /// from how many resamples fall below the point estimate, the
+    /// acceleration from a jackknife over both samples. Costs one extra pass
+    /// over the samples per side.
+    Bca,
+}
+
+impl BootstrapInterval {
+    /// The name used in config files and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BootstrapInterval::Percentile => "percentile",
+            BootstrapInterval::Bca => "bca",
+        }
+    }
+}
+
+/// Resample count, seed and interval method for bootstrap confidence
+/// intervals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Bootstrap {
+    /// Number of resamples. Below ~1000 the tail percentiles get noisy.
+    pub resamples: usize,
+    /// Seed for the resampling RNG, fixed so identical samples give identical
+    /// intervals.
+    pub seed: u64,
+    /// How the interval is read off the resampled changes.
+    pub interval: BootstrapInterval,
+}
+
+impl Default for Bootstrap {
+    fn default() -> Self {
+        Self {
+            resamples: 10_000,
+            seed: 0xC0FFEE,
+            interval: BootstrapInterval::Percentile,
+        }
+    }
+}
+
+impl Bootstrap {
+    /// CI on the relative mean change, as [`bootstrap_change_ci_values`].
+    pub fn change_ci(&self, baseline: &[f64], candidate: &[f64], confidence: f64) -> (f64, f64) {
+        two_sample_ci(
+            Statistic::Mean,
+            baseline,
+            candidate,
+            self.resamples,
+            confidence,
+            self.interval,
+            &mut self.rng(),
+        )
+    }
+
+    /// CI on the relative change in quantile `q`, as
+    /// [`bootstrap_quantile_change_ci`].
+    pub fn quantile_change_ci(
+        &self,
+        baseline: &[f64],
+        candidate: &[f64],
+        q: f64,
+        confidence: f64,
+    ) -> (f64, f64) {
+        two_sample_ci(
+            Statistic::Quantile(q),
+            baseline,
+            candidate,
+            self.resamples,
+            confidence,
+            self.interval,
+            &mut self.rng(),
+        )
+    }
+
+    /// CI on a suite's geometric-mean change, as
+    /// [`bootstrap_geomean_change_ci`]. Always a percentile interval: a
+    /// jackknife over every sample of every benchmark would cost more than
+    /// the resampling itself.
+    pub fn geomean_change_ci(
+        &self,
+        benchmarks: &[(&[f64], &[f64], f64)],
+        confidence: f64,
+    ) -> (f64, f64) {
+        bootstrap_geomean_change_ci(benchmarks, self.resamples, confidence, &mut self.rng())
+    }
+
+    /// CI on the relative change in per-iteration slope, as
+    /// [`bootstrap_slope_change_ci`]. Always a percentile interval.
+    pub fn slope_change_ci(
+        &self,
+        baseline: &[(f64, f64)],
+        candidate: &[(f64, f64)],
+        confidence: f64,
+    ) -> (f64, f64) {
+        bootstrap_slope_change_ci(
+            baseline,
+            candidate,
+            self.resamples,
+            confidence,
+            &mut self.rng(),
+        )
+    }
+
+    fn rng(&self) -> SmallRng {
+        SmallRng::seed_from_u64(self.seed)
+    }
+}
+
 /// Compute a non-parametric bootstrap confidence interval on the relative
 /// mean change between baseline and candidate samples.
 ///
@@ -48,50 +173,392 @@ pub fn bootstrap_change_ci<R: Rng + ?Sized>(
     n_resamples: usize,
     confidence: f64,
     rng: &mut R,
+) -> (f64, f64) {
+    bootstrap_change_ci_values(
+        &to_nanos(baseline),
+        &to_nanos(candidate),
+        n_resamples,
+        confidence,
+        rng,
+    )
+}
+
+/// [`bootstrap_change_ci`] over raw measurements in any unit where lower is
+/// better (e.g. allocations per iteration).
+///
+/// # Panics
+/// Panics if `confidence` is not in `(0, 1)`.
+pub fn bootstrap_change_ci_values<R: Rng + ?Sized>(
+    baseline: &[f64],
+    candidate: &[f64],
+    n_resamples: usize,
+    confidence: f64,
+    rng: &mut R,
+) -> (f64, f64) {
+    two_sample_ci(
+        Statistic::Mean,
+        baseline,
+        candidate,
+        n_resamples,
+        confidence,
+        BootstrapInterval::Percentile,
+        rng,
+    )
+}
+
+/// [`bootstrap_change_ci_values`] with a bias-corrected and accelerated
+/// interval instead of plain percentiles. Falls back to percentiles when
+/// every resample lands on the same side of the point estimate, where the
+/// bias correction is infinite.
+///
+/// # Panics
+/// Panics if `confidence` is not in `(0, 1)`.
+pub fn bca_change_ci_values<R: Rng + ?Sized>(
+    baseline: &[f64],
+    candidate: &[f64],
+    n_resamples: usize,
+    confidence: f64,
+    rng: &mut R,
+) -> (f64, f64) {
+    two_sample_ci(
+        Statistic::Mean,
+        baseline,
+        candidate,
+        n_resamples,
+        confidence,
+        BootstrapInterval::Bca,
+        rng,
+    )
+}
+
+/// Bootstrap confidence interval on the relative change in the slope of
+/// elapsed time against iteration count, with samples given as `(iterations,
+/// elapsed_ns)`. Each resample draws whole samples with replacement and
+/// refits both sides; resamples where a side can't be fitted (every draw at
+/// one iteration count) are skipped.
+///
+/// Returns `(ci_low, ci_high)` in percent, with the same sign convention as
+/// [`bootstrap_change_ci`]. Collapses to the point estimate if either side
+/// can't be fitted at all.
+pub fn bootstrap_slope_change_ci<R: Rng + ?Sized>(
+    baseline: &[(f64, f64)],
+    candidate: &[(f64, f64)],
+    n_resamples: usize,
+    confidence: f64,
+    rng: &mut R,
+) -> (f64, f64) {
+    assert!(
+        confidence > 0.0 && confidence < 1.0,
+        "confidence must be between 0 and 1 (exclusive)"
+    );
+    let slope = |runs: &[(f64, f64)]| LinearFit::fit(runs).map(|fit| fit.slope_ns);
+    let (Some(base_slope), Some(cand_slope)) = (slope(baseline), slope(candidate)) else {
+        return (0.0, 0.0);
+    };
+    let point_estimate = relative_change(base_slope, cand_slope);
+
+    let resample = |runs: &[(f64, f64)], buf: &mut Vec<(f64, f64)>, rng: &mut R| {
+        buf.clear();
+        buf.extend((0..runs.len()).map(|_| *runs.choose(rng).expect("runs is non-empty")));
+    };
+    let mut base_buf = Vec::with_capacity(baseline.len());
+    let mut cand_buf = Vec::with_capacity(candidate.len());
+    let mut diffs: Vec<f64> = Vec::with_capacity(n_resamples);
+    for _ in 0..n_resamples {
+        resample(baseline, &mut base_buf, rng);
+        resample(candidate, &mut cand_buf, rng);
+        if let (Some(b), Some(c)) = (slope(&base_buf), slope(&cand_buf)) {
+            diffs.push(relative_change(b, c));
+        }
+    }
+    if diffs.is_empty() {
+        return (point_estimate, point_estimate);
+    }
+    diffs.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - confidence) / 2.0;
+    (percentile(&diffs, tail), percentile(&diffs, 1.0 - tail))
+}
+
+/// The `q` quantile (`0..=1`) of `samples`, interpolating linearly between
+/// adjacent order statistics. Returns 0.0 for an empty slice.
+pub fn quantile(samples: &[f64], q: f64) -> f64 {
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    percentile(&sorted, q)
+}
+
+/// Bootstrap confidence interval on the relative change in the `q` quantile
+/// between baseline and candidate, e.g. the change in p99 latency for
+/// `q = 0.99`.
+///
+/// Returns `(ci_low, ci_high)` in percent, with the same sign convention and
+/// edge cases as [`bootstrap_change_ci`]. Extreme quantiles of small samples
+/// can only take a few distinct values under resampling, so expect a coarse
+/// interval for, say, a p99 of 50 samples.
+///
+/// # Panics
+/// Panics if `confidence` is not in `(0, 1)`.
+pub fn bootstrap_quantile_change_ci<R: Rng + ?Sized>(
+    baseline: &[f64],
+    candidate: &[f64],
+    q: f64,
+    n_resamples: usize,
+    confidence: f64,
+    rng: &mut R,
+) -> (f64, f64) {
+    two_sample_ci(
+        Statistic::Quantile(q),
+        baseline,
+        candidate,
+        n_resamples,
+        confidence,
+        BootstrapInterval::Percentile,
+        rng,
+    )
+}
+
+/// Weighted geometric mean of per-benchmark mean ratios (candidate over
+/// baseline), as a percent change with the same sign convention as
+/// [`crate::stats::TestResult::effect_size`]. `benchmarks` holds each
+/// benchmark's baseline and candidate measurements and its weight.
+///
+/// Benchmarks with a non-positive mean on either side or a non-positive
+/// weight are left out. Returns 0.0 if nothing is left.
+pub fn geomean_change(benchmarks: &[(&[f64], &[f64], f64)]) -> f64 {
+    weighted_geomean_change(benchmarks.iter().map(|(b, c, w)| (mean(b), mean(c), *w)))
+}
+
+/// Bootstrap confidence interval on [`geomean_change`]. Each resample draws
+/// every benchmark's baseline and candidate samples with replacement, within
+/// that benchmark, and recomputes the weighted geometric mean.
+///
+/// Returns `(ci_low, ci_high)` in percent. Benchmarks with fewer than 2
+/// samples on a side contribute their point estimate to every resample.
+///
+/// # Panics
+/// Panics if `confidence` is not in `(0, 1)`.
+pub fn bootstrap_geomean_change_ci<R: Rng + ?Sized>(
+    benchmarks: &[(&[f64], &[f64], f64)],
+    n_resamples: usize,
+    confidence: f64,
+    rng: &mut R,
 ) -> (f64, f64) {
     assert!(
         confidence > 0.0 && confidence < 1.0,
         "confidence must be between 0 and 1 (exclusive)"
     );
+    let point_estimate = geomean_change(benchmarks);
+    if n_resamples == 0 {
+        return (point_estimate, point_estimate);
+    }
 
-    let base_mean = mean_ns(baseline);
-    let cand_mean = mean_ns(candidate);
-    let point_estimate = relative_change(base_mean, cand_mean);
+    let resample = |data: &[f64], rng: &mut R| {
+        if data.len() < 2 {
+            mean(data)
+        } else {
+            resample_mean(data, rng)
+        }
+    };
+    let mut changes: Vec<f64> = Vec::with_capacity(n_resamples);
+    let mut means = Vec::with_capacity(benchmarks.len());
+    for _ in 0..n_resamples {
+        means.clear();
+        for (baseline, candidate, weight) in benchmarks {
+            means.push((resample(baseline, rng), resample(candidate, rng), *weight));
+        }
+        changes.push(weighted_geomean_change(means.iter().copied()));
+    }
+    changes.sort_by(f64::total_cmp);
+
+    let tail = (1.0 - confidence) / 2.0;
+    (percentile(&changes, tail), percentile(&changes, 1.0 - tail))
+}
+
+/// [`geomean_change`] from `(baseline mean, candidate mean, weight)` triples.
+fn weighted_geomean_change(means: impl Iterator<Item = (f64, f64, f64)>) -> f64 {
+    let (mut log_sum, mut weight_sum) = (0.0, 0.0);
+    for (base_mean, cand_mean, weight) in means {
+        if base_mean > 0.0 && cand_mean > 0.0 && weight > 0.0 {
+            log_sum += weight * (cand_mean / base_mean).ln();
+            weight_sum += weight;
+        }
+    }
+    if weight_sum == 0.0 {
+        0.0
+    } else {
+        (1.0 - (log_sum / weight_sum).exp()) * 100.0
+    }
+}
+
+/// A per-sample summary whose relative change is bootstrapped.
+#[derive(Debug, Clone, Copy)]
+enum Statistic {
+    Mean,
+    Quantile(f64),
+}
+
+impl Statistic {
+    /// The statistic of `data`, which may be reordered.
+    fn eval(self, data: &mut [f64]) -> f64 {
+        match self {
+            Statistic::Mean => mean(data),
+            Statistic::Quantile(q) => {
+                data.sort_by(f64::total_cmp);
+                percentile(data, q)
+            }
+        }
+    }
+
+    /// The statistic of `data` with each sample left out in turn (in no
+    /// particular order), in O(n log n).
+    fn jackknife(self, data: &[f64]) -> Vec<f64> {
+        let n = data.len();
+        match self {
+            Statistic::Mean => {
+                let sum: f64 = data.iter().sum();
+                data.iter().map(|x| (sum - x) / (n - 1) as f64).collect()
+            }
+            Statistic::Quantile(q) => {
+                let mut sorted = data.to_vec();
+                sorted.sort_by(f64::total_cmp);
+                (0..n)
+                    .map(|left_out| percentile_without(&sorted, left_out, q))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Bootstrap CI on the relative change in `statistic` from baseline to
+/// candidate. See [`bootstrap_change_ci`] for the edge cases.
+fn two_sample_ci<R: Rng + ?Sized>(
+    statistic: Statistic,
+    baseline: &[f64],
+    candidate: &[f64],
+    n_resamples: usize,
+    confidence: f64,
+    interval: BootstrapInterval,
+    rng: &mut R,
+) -> (f64, f64) {
+    assert!(
+        confidence > 0.0 && confidence < 1.0,
+        "confidence must be between 0 and 1 (exclusive)"
+    );
+
+    let base_stat = statistic.eval(&mut baseline.to_vec());
+    let cand_stat = statistic.eval(&mut candidate.to_vec());
+    let point_estimate = relative_change(base_stat, cand_stat);
 
     if baseline.len() < 2 || candidate.len() < 2 || n_resamples == 0 {
         return (point_estimate, point_estimate);
     }
-    if base_mean == 0.0 {
+    if base_stat == 0.0 {
         return (0.0, 0.0);
     }
 
-    // Convert samples to ns-f64 up front so resampling just copies floats.
-    let baseline_ns: Vec<f64> = baseline.iter().map(|d| d.as_nanos() as f64).collect();
-    let candidate_ns: Vec<f64> = candidate.iter().map(|d| d.as_nanos() as f64).collect();
-
+    let mut base_buf = Vec::with_capacity(baseline.len());
+    let mut cand_buf = Vec::with_capacity(candidate.len());
     let mut diffs: Vec<f64> = Vec::with_capacity(n_resamples);
     for _ in 0..n_resamples {
-        let b_mean = resample_mean(&baseline_ns, rng);
-        let c_mean = resample_mean(&candidate_ns, rng);
-        diffs.push(relative_change(b_mean, c_mean));
+        resample_into(baseline, &mut base_buf, rng);
+        resample_into(candidate, &mut cand_buf, rng);
+        diffs.push(relative_change(
+            statistic.eval(&mut base_buf),
+            statistic.eval(&mut cand_buf),
+        ));
     }
+    diffs.sort_by(f64::total_cmp);
 
-    diffs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Equal-tailed percentile CI.
     let tail = (1.0 - confidence) / 2.0;
-    let lo = percentile(&diffs, tail);
-    let hi = percentile(&diffs, 1.0 - tail);
-    (lo, hi)
+    let (lo_fraction, hi_fraction) = match interval {
+        BootstrapInterval::Percentile => None,
+        BootstrapInterval::Bca => {
+            // Leave-one-out changes, one group per side.
+            let base_jack: Vec<f64> = statistic
+                .jackknife(baseline)
+                .into_iter()
+                .map(|b| relative_change(b, cand_stat))
+                .collect();
+            let cand_jack: Vec<f64> = statistic
+                .jackknife(candidate)
+                .into_iter()
+                .map(|c| relative_change(base_stat, c))
+                .collect();
+            bca_fractions(&diffs, point_estimate, &[&base_jack, &cand_jack], tail)
+        }
+    }
+    .unwrap_or((tail, 1.0 - tail));
+    (
+        percentile(&diffs, lo_fraction),
+        percentile(&diffs, hi_fraction),
+    )
+}
+
+/// The BCa-adjusted percentile fractions for an equal-tailed interval with
+/// `tail` in each tail, given the sorted bootstrap changes, the point
+/// estimate and each sample's jackknife changes. `None` if the bias
+/// correction is infinite, i.e. no resample fell on one side of the point
+/// estimate.
+fn bca_fractions(
+    sorted: &[f64],
+    point: f64,
+    jackknife: &[&[f64]],
+    tail: f64,
+) -> Option<(f64, f64)> {
+    let normal = Normal::new(0.0, 1.0).expect("standard normal");
+
+    // Bias correction: how far the bootstrap median sits from the estimate.
+    let below = sorted.partition_point(|&d| d < point);
+    let ties = sorted[below..].partition_point(|&d| d <= point);
+    let p0 = (below as f64 + ties as f64 / 2.0) / sorted.len() as f64;
+    if p0 <= 0.0 || p0 >= 1.0 {
+        return None;
+    }
+    let z0 = normal.inverse_cdf(p0);
+
+    let acceleration = acceleration(jackknife);
+    let adjust = |z: f64| {
+        let shifted = z0 + z;
+        normal.cdf(z0 + shifted / (1.0 - acceleration * shifted))
+    };
+    let lo = adjust(normal.inverse_cdf(tail));
+    let hi = adjust(normal.inverse_cdf(1.0 - tail));
+    (lo.is_finite() && hi.is_finite()).then_some((lo, hi))
 }
 
-/// Sample mean in nanoseconds. Returns 0.0 for an empty slice.
-fn mean_ns(samples: &[Duration]) -> f64 {
+/// BCa acceleration: the skewness of the jackknife influence values, summed
+/// over every sample. Each sample's influence values are scaled by `n - 1`
+/// so that samples of different sizes are weighed correctly.
+fn acceleration(jackknife: &[&[f64]]) -> f64 {
+    let (mut num, mut den) = (0.0, 0.0);
+    for values in jackknife {
+        let n = values.len() as f64;
+        let jack_mean = mean(values);
+        for value in values.iter() {
+            let u = (n - 1.0) * (jack_mean - value);
+            num += u.powi(3) / n.powi(3);
+            den += u.powi(2) / n.powi(2);
+        }
+    }
+    if den > 0.0 {
+        num / (6.0 * den.powf(1.5))
+    } else {
+        0.0
+    }
+}
+
+/// Durations as nanosecond floats.
+fn to_nanos(samples: &[Duration]) -> Vec<f64> {
+    samples.iter().map(|d| d.as_nanos() as f64).collect()
+}
+
+/// Sample mean. Returns 0.0 for an empty slice.
+fn mean(samples: &[f64]) -> f64 {
     if samples.is_empty() {
         return 0.0;
     }
-    let sum: f64 = samples.iter().map(|d| d.as_nanos() as f64).sum();
-    sum / samples.len() as f64
+    samples.iter().sum::<f64>() / samples.len() as f64
 }
 
 /// Relative change from baseline to candidate, in percent.
@@ -116,9 +583,15 @@ fn resample_mean<R: Rng + ?Sized>(data: &[f64], rng: &mut R) -> f64 {
     sum / n as f64
 }
 
+/// Resample `data` with replacement into `buf`, to the original length.
+fn resample_into<R: Rng + ?Sized>(data: &[f64], buf: &mut Vec<f64>, rng: &mut R) {
+    buf.clear();
+    buf.extend((0..data.len()).map(|_| *data.choose(rng).expect("data is non-empty")));
+}
+
 /// Given a pre-sorted vector, return the value at `fraction` (0..=1) using
 /// linear interpolation between adjacent elements.
-fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+pub(super) fn percentile(sorted: &[f64], fraction: f64) -> f64 {
     if sorted.is_empty() {
         return 0.0;
     }
@@ -136,6 +609,17 @@ fn percentile(sorted: &[f64], fraction: f64) -> f64 {
     sorted[lo_idx] * (1.0 - frac) + sorted[hi_idx] * frac
 }
 
+/// [`percentile`] of `sorted` with the element at index `left_out` removed.
+/// `sorted` must have at least 2 elements.
+fn percentile_without(sorted: &[f64], left_out: usize, fraction: f64) -> f64 {
+    let at = |i: usize| sorted[if i < left_out { i } else { i + 1 }];
+    let pos = fraction.clamp(0.0, 1.0) * (sorted.len() - 2) as f64;
+    let lo_idx = pos.floor() as usize;
+    let hi_idx = pos.ceil() as usize;
+    let frac = pos - lo_idx as f64;
+    at(lo_idx) * (1.0 - frac) + at(hi_idx) * frac
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,4 +702,197 @@ mod tests {
         let candidate = durations(&[90, 100]);
         let _ = bootstrap_change_ci(&baseline, &candidate, 100, 1.5, &mut seeded());
     }
+
+    #[test]
+    fn quantile_interpolates_between_order_statistics() {
+        let samples = [5.0, 1.0, 4.0, 2.0, 3.0];
+        assert_eq!(quantile(&samples, 0.5), 3.0);
+        assert_eq!(quantile(&samples, 0.0), 1.0);
+        assert_eq!(quantile(&samples, 1.0), 5.0);
+        assert!((quantile(&samples, 0.9) - 4.6).abs() < 1e-9);
+        assert_eq!(quantile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn quantile_ci_sees_tail_regression_the_mean_misses() {
+        // Same bulk, but the candidate's slowest tenth got 3x slower.
+        let baseline: Vec<f64> = (0..100).map(|i| 1000.0 + (i % 10) as f64).collect();
+        let candidate: Vec<f64> = baseline
+            .iter()
+            .enumerate()
+            .map(|(i, v)| if i % 10 == 9 { v * 3.0 } else { *v })
+            .collect();
+
+        let (lo, hi) =
+            bootstrap_quantile_change_ci(&baseline, &candidate, 0.5, 2_000, 0.95, &mut seeded());
+        assert!(
+            lo <= 0.0 && hi >= 0.0,
+            "median CI [{lo}, {hi}] should contain 0"
+        );
+
+        let (lo, hi) =
+            bootstrap_quantile_change_ci(&baseline, &candidate, 0.99, 2_000, 0.95, &mut seeded());
+        assert!(
+            hi < -100.0,
+            "p99 CI [{lo}, {hi}] should show a big slowdown"
+        );
+    }
+
+    #[test]
+    fn quantile_ci_too_few_samples_returns_point_estimate() {
+        let (lo, hi) =
+            bootstrap_quantile_change_ci(&[100.0], &[50.0, 60.0], 0.5, 1_000, 0.95, &mut seeded());
+        assert_eq!(lo, hi);
+        assert!((lo - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn geomean_change_weights_benchmarks() {
+        // One benchmark 2x faster, one 2x slower: they cancel unweighted.
+        let (fast_b, fast_c) = ([200.0, 200.0], [100.0, 100.0]);
+        let (slow_b, slow_c) = ([100.0, 100.0], [200.0, 200.0]);
+        let even = [
+            (&fast_b[..], &fast_c[..], 1.0),
+            (&slow_b[..], &slow_c[..], 1.0),
+        ];
+        assert!(geomean_change(&even).abs() < 1e-9);
+
+        // Weighting the speedup 3:1 gives 2^(-1/2) → ~29.3% faster.
+        let weighted = [
+            (&fast_b[..], &fast_c[..], 3.0),
+            (&slow_b[..], &slow_c[..], 1.0),
+        ];
+        assert!((geomean_change(&weighted) - (1.0 - 0.5f64.sqrt()) * 100.0).abs() < 1e-9);
+
+        // Zero weight drops a benchmark entirely.
+        let dropped = [
+            (&fast_b[..], &fast_c[..], 1.0),
+            (&slow_b[..], &slow_c[..], 0.0),
+        ];
+        assert!((geomean_change(&dropped) - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn geomean_ci_brackets_point_estimate() {
+        let a_base = [100.0, 102.0, 98.0, 101.0, 99.0, 100.0];
+        let a_cand = [95.0, 96.0, 94.0, 95.0, 97.0, 93.0];
+        let b_base = [1000.0, 1010.0, 990.0, 1005.0, 995.0, 1000.0];
+        let b_cand = [960.0, 970.0, 950.0, 955.0, 965.0, 960.0];
+        let suite = [
+            (&a_base[..], &a_cand[..], 1.0),
+            (&b_base[..], &b_cand[..], 1.0),
+        ];
+
+        let point = geomean_change(&suite);
+        let (lo, hi) = bootstrap_geomean_change_ci(&suite, 2000, 0.95, &mut seeded());
+        assert!(lo < point && point < hi, "{lo} < {point} < {hi}");
+        assert!(lo > 0.0, "clear speedup should exclude zero: [{lo}, {hi}]");
+    }
+
+    #[test]
+    fn percentile_without_skips_the_left_out_sample() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        for left_out in 0..sorted.len() {
+            let mut rest = sorted.to_vec();
+            rest.remove(left_out);
+            for q in [0.0, 0.3, 0.5, 0.9, 1.0] {
+                assert_eq!(
+                    percentile_without(&sorted, left_out, q),
+                    percentile(&rest, q)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn jackknife_leaves_out_each_sample() {
+        let data = [4.0, 1.0, 3.0, 2.0];
+        let means = Statistic::Mean.jackknife(&data);
+        assert_eq!(means, vec![2.0, 3.0, 7.0 / 3.0, 8.0 / 3.0]);
+        let mut medians = Statistic::Quantile(0.5).jackknife(&data);
+        medians.sort_by(f64::total_cmp);
+        assert_eq!(medians, vec![2.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn bca_shifts_interval_on_skewed_samples() {
+        // Mostly fast runs with a long slow tail on the candidate side: the
+        // mean's sampling distribution is right-skewed, which BCa corrects.
+        let baseline: Vec<f64> = (0..60).map(|i| 1000.0 + (i % 7) as f64).collect();
+        let candidate: Vec<f64> = (0..60)
+            .map(|i| {
+                if i % 10 == 0 {
+                    3000.0 + i as f64 * 10.0
+                } else {
+                    1000.0 + (i % 5) as f64
+                }
+            })
+            .collect();
+        let point = relative_change(mean(&baseline), mean(&candidate));
+
+        let percentile = Bootstrap::default().change_ci(&baseline, &candidate, 0.95);
+        let bca = Bootstrap {
+            interval: BootstrapInterval::Bca,
+            ..Bootstrap::default()
+        }
+        .change_ci(&baseline, &candidate, 0.95);
+        assert!(bca.0 < point && point < bca.1, "{bca:?} around {point}");
+        assert_ne!(bca, percentile);
+        assert_eq!(
+            bca,
+            bca_change_ci_values(&baseline, &candidate, 10_000, 0.95, &mut seeded())
+        );
+    }
+
+    #[test]
+    fn acceleration_weighs_unequal_samples() {
+        // For a sum of two means the influence of each sample is its
+        // deviation from its side's mean, whatever the side's size.
+        let small = [1.0, 2.0, 9.0, 3.0];
+        let large: Vec<f64> = (0..25).map(|i| ((i * i) % 11) as f64).collect();
+        let deviations = |data: &[f64]| -> Vec<f64> {
+            let m = mean(data);
+            data.iter().map(|x| x - m).collect()
+        };
+        let (mut num, mut den) = (0.0, 0.0);
+        for data in [&small[..], &large[..]] {
+            let n = data.len() as f64;
+            for d in deviations(data) {
+                num += d.powi(3) / n.powi(3);
+                den += d.powi(2) / n.powi(2);
+            }
+        }
+        let expected = num / (6.0 * den.powf(1.5));
+
+        let small_jack = Statistic::Mean.jackknife(&small);
+        let large_jack = Statistic::Mean.jackknife(&large);
+        let a = acceleration(&[&small_jack, &large_jack]);
+        assert!((a - expected).abs() < 1e-12, "{a} vs {expected}");
+    }
+
+    #[test]
+    fn bootstrap_seed_and_resamples_are_honoured() {
+        let baseline: Vec<f64> = (0..30).map(|i| 1000.0 + (i % 9) as f64).collect();
+        let candidate: Vec<f64> = baseline.iter().map(|v| v * 1.02).collect();
+        let default = Bootstrap::default();
+        assert_eq!(
+            default.change_ci(&baseline, &candidate, 0.95),
+            bootstrap_change_ci_values(&baseline, &candidate, 10_000, 0.95, &mut seeded())
+        );
+        let reseeded = Bootstrap {
+            seed: 7,
+            resamples: 500,
+            ..default
+        };
+        assert_eq!(
+            reseeded.change_ci(&baseline, &candidate, 0.95),
+            bootstrap_change_ci_values(
+                &baseline,
+                &candidate,
+                500,
+                0.95,
+                &mut SmallRng::seed_from_u64(7)
+            )
+        );
+    }
 }
@@ -0,0 +1,225 @@
+//! Stationarity checks on a sequence of samples in the order they were taken.
+//!
+//! The comparison tests treat samples as exchangeable, which they aren't if
+//! the machine warmed up, throttled or picked up a background job partway
+//! through the run. [`Drift`] looks for the two common shapes of that: a
+//! monotonic trend (Mann–Kendall) and a single level shift (Pettitt). Both
+//! are rank-based, so a few outliers don't trigger them.
+
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Fewest samples the checks run on. Below this the normal approximations
+/// behind both p-values are poor.
+pub const MIN_DRIFT_SAMPLES: usize = 10;
+
+/// Mann–Kendall test for a monotonic trend.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Trend {
+    /// Kendall's tau between sample value and position, from -1 (steadily
+    /// falling) to +1 (steadily rising).
+    pub tau: f64,
+    /// Two-sided p-value for "no trend", corrected for ties.
+    pub p_value: f64,
+}
+
+impl Trend {
+    /// Test `sequence` for a monotonic trend.
+    pub fn mann_kendall(sequence: &[f64]) -> Self {
+        let n = sequence.len();
+        if n < 3 {
+            return Self {
+                tau: 0.0,
+                p_value: 1.0,
+            };
+        }
+        let s: f64 = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| sign(sequence[j] - sequence[i]))
+            .sum();
+
+        // Var(S) = [n(n-1)(2n+5) - Σ t(t-1)(2t+5)] / 18 over tied groups.
+        let mut sorted = sequence.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let ties: f64 = sorted
+            .chunk_by(|a, b| a == b)
+            .map(|group| {
+                let t = group.len() as f64;
+                t * (t - 1.0) * (2.0 * t + 5.0)
+            })
+            .sum();
+        let nf = n as f64;
+        let variance = (nf * (nf - 1.0) * (2.0 * nf + 5.0) - ties) / 18.0;
+
+        let tau = s / (nf * (nf - 1.0) / 2.0);
+        if variance <= 0.0 {
+            return Self { tau, p_value: 1.0 };
+        }
+        // Continuity correction towards zero.
+        let z = (s - s.signum()) / variance.sqrt();
+        Self {
+            tau,
+            p_value: two_sided_p(z),
+        }
+    }
+}
+
+/// Pettitt's test for a single shift in level.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Changepoint {
+    /// Index of the first sample after the most likely shift.
+    pub index: usize,
+    /// Approximate p-value for "no shift".
+    pub p_value: f64,
+    /// Mean after the shift minus mean before it, in percent of the
+    /// reference the check was given.
+    pub shift_percent: f64,
+}
+
+impl Changepoint {
+    /// Find the most likely level shift in `sequence`, reporting its size
+    /// in percent of `reference`.
+    pub fn pettitt(sequence: &[f64], reference: f64) -> Self {
+        let n = sequence.len();
+        if n < 2 {
+            return Self {
+                index: 0,
+                p_value: 1.0,
+                shift_percent: 0.0,
+            };
+        }
+        // U_t = Σ_{i<=t<j} sgn(x_i - x_j), built up one sample at a time:
+        // moving sample t to the first segment adds Σ_{j≠t} sgn(x_t - x_j).
+        let mut u = 0.0_f64;
+        let (mut k, mut index) = (0.0_f64, 1);
+        for t in 0..n - 1 {
+            u += sequence
+                .iter()
+                .map(|&other| sign(sequence[t] - other))
+                .sum::<f64>();
+            if u.abs() > k {
+                k = u.abs();
+                index = t + 1;
+            }
+        }
+        let nf = n as f64;
+        let p_value = (2.0 * (-6.0 * k * k / (nf.powi(3) + nf.powi(2))).exp()).min(1.0);
+
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+        let shift = mean(&sequence[index..]) - mean(&sequence[..index]);
+        Self {
+            index,
+            p_value,
+            shift_percent: if reference != 0.0 {
+                shift / reference.abs() * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Trend and changepoint checks on one sequence.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Drift {
+    pub trend: Trend,
+    pub changepoint: Changepoint,
+}
+
+impl Drift {
+    /// Check `sequence`, in the order it was sampled, sizing any shift in
+    /// percent of `reference`. `None` with fewer than
+    /// [`MIN_DRIFT_SAMPLES`] samples.
+    pub fn analyze(sequence: &[f64], reference: f64) -> Option<Self> {
+        (sequence.len() >= MIN_DRIFT_SAMPLES).then(|| Self {
+            trend: Trend::mann_kendall(sequence),
+            changepoint: Changepoint::pettitt(sequence, reference),
+        })
+    }
+
+    /// Whether a trend is significant at `alpha`.
+    pub fn trending(&self, alpha: f64) -> bool {
+        self.trend.p_value < alpha
+    }
+
+    /// Whether a level shift is significant at `alpha`.
+    pub fn shifted(&self, alpha: f64) -> bool {
+        self.changepoint.p_value < alpha
+    }
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn two_sided_p(z: f64) -> f64 {
+    let normal = Normal::new(0.0, 1.0).expect("standard normal");
+    2.0 * (1.0 - normal.cdf(z.abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic noise in `-1..=1` with no trend or shift.
+    fn noise(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| ((i * 7919) % 101) as f64 / 50.0 - 1.0)
+            .collect()
+    }
+
+    #[test]
+    fn mann_kendall_sees_a_trend() {
+        let rising: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        let trend = Trend::mann_kendall(&rising);
+        assert_eq!(trend.tau, 1.0);
+        assert!(trend.p_value < 1e-6, "{}", trend.p_value);
+
+        let falling: Vec<f64> = noise(60)
+            .iter()
+            .enumerate()
+            .map(|(i, x)| 100.0 + x - 0.05 * i as f64)
+            .collect();
+        let trend = Trend::mann_kendall(&falling);
+        assert!(trend.tau < 0.0);
+        assert!(trend.p_value < 0.01, "{}", trend.p_value);
+    }
+
+    #[test]
+    fn mann_kendall_ignores_noise_and_ties() {
+        let trend = Trend::mann_kendall(&noise(60));
+        assert!(trend.p_value > 0.1, "{}", trend.p_value);
+
+        let flat = Trend::mann_kendall(&[5.0; 20]);
+        assert_eq!(flat.tau, 0.0);
+        assert_eq!(flat.p_value, 1.0);
+    }
+
+    #[test]
+    fn pettitt_locates_a_shift() {
+        let mut sequence: Vec<f64> = noise(60).iter().map(|x| 100.0 + x).collect();
+        for x in &mut sequence[40..] {
+            *x += 10.0;
+        }
+        let changepoint = Changepoint::pettitt(&sequence, 100.0);
+        // Within a sample: a noise extreme next to the step can pull it over.
+        assert!(changepoint.index.abs_diff(40) <= 1, "{}", changepoint.index);
+        assert!(changepoint.p_value < 1e-3, "{}", changepoint.p_value);
+        assert!((changepoint.shift_percent - 10.0).abs() < 0.5);
+
+        let steady = Changepoint::pettitt(&noise(60), 100.0);
+        assert!(steady.p_value > 0.1, "{}", steady.p_value);
+    }
+
+    #[test]
+    fn drift_needs_enough_samples() {
+        assert!(Drift::analyze(&noise(MIN_DRIFT_SAMPLES - 1), 1.0).is_none());
+        let drift = Drift::analyze(&noise(MIN_DRIFT_SAMPLES), 1.0).unwrap();
+        assert!(!drift.trending(0.01) && !drift.shifted(0.01));
+    }
+}
@@ -16,11 +16,13 @@ pub struct TestResult {
     pub statistically_significant: bool,
     /// Effect size as percent difference (positive = candidate is faster than baseline).
     pub effect_size: f64,
-    /// Lower bound of the bootstrap confidence interval on the relative mean change,
-    /// in percent. Same sign convention as `effect_size`.
+    /// Lower bound of the confidence interval on the relative mean change
+    /// (the credible interval, for a Bayesian test), in percent. Same sign
+    /// convention as `effect_size`.
     pub change_ci_low: f64,
-    /// Upper bound of the bootstrap confidence interval on the relative mean change,
-    /// in percent. Same sign convention as `effect_size`.
+    /// Upper bound of the confidence interval on the relative mean change
+    /// (the credible interval, for a Bayesian test), in percent. Same sign
+    /// convention as `effect_size`.
     pub change_ci_high: f64,
     /// The confidence level used for the test (e.g., 0.95 for 95% confidence).
     pub confidence_level: f64,
@@ -30,16 +32,98 @@ pub struct TestResult {
     pub baseline_mean_ns: f64,
     /// Mean of candidate measurements in nanoseconds.
     pub candidate_mean_ns: f64,
+    /// Equivalence test, when the test was given an equivalence margin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equivalence: Option<Equivalence>,
+    /// Minimum detectable effect and required sample size, when the test
+    /// computes them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerAnalysis>,
+    /// Posterior probabilities, when the test is Bayesian.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posterior: Option<Posterior>,
+    /// How the change CI was computed: the configured [`ChangeInterval`], or
+    /// the bootstrap when the analytic interval was unbounded. `None` for a
+    /// credible interval, or when there was too little data or noise to
+    /// resample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_interval: Option<ChangeInterval>,
+}
+
+/// Outcome of a two-one-sided-tests (TOST) equivalence check.
+///
+/// Where a significance test asks "did it change?", TOST asks "can we rule
+/// out a change of `margin` percent or more in either direction?".
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Equivalence {
+    /// Equivalence margin, in percent of the baseline mean.
+    pub margin: f64,
+    /// The larger of the two one-sided p-values.
+    pub p_value: f64,
+    /// Whether the change is confidently within ±`margin`.
+    pub equivalent: bool,
+}
+
+/// Overall reading of a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The candidate is significantly faster.
+    Faster,
+    /// The candidate is significantly slower.
+    Slower,
+    /// No significant change, and any change is confidently within the
+    /// equivalence margin.
+    Equivalent,
+    /// We couldn't tell.
+    Inconclusive,
+}
+
+impl TestResult {
+    /// Classify this result. A significant change wins over equivalence.
+    pub fn verdict(&self) -> Verdict {
+        match (self.statistically_significant, self.winner) {
+            (true, Some(Side::Candidate)) => Verdict::Faster,
+            (true, Some(Side::Baseline)) => Verdict::Slower,
+            _ if self.equivalence.is_some_and(|e| e.equivalent) => Verdict::Equivalent,
+            _ => Verdict::Inconclusive,
+        }
+    }
 }
 
 /// Trait for statistical tests that compare two sets of measurements.
+///
+/// Tests run on plain numbers so the same test can compare any metric where
+/// lower is better: wall time, allocations, instruction counts.
 pub trait StatisticalTest: Send + Sync {
-    /// Analyze baseline and candidate measurements and return a statistical test result.
-    fn analyze(&self, baseline: &[Duration], candidate: &[Duration]) -> TestResult;
+    /// Analyze baseline and candidate measurements in any unit where lower is
+    /// better. The `*_mean_ns` fields of the result carry the means in the
+    /// measurements' own unit.
+    fn analyze_values(&self, baseline: &[f64], candidate: &[f64]) -> TestResult;
+
+    /// Analyze baseline and candidate durations, compared in nanoseconds.
+    fn analyze(&self, baseline: &[Duration], candidate: &[Duration]) -> TestResult {
+        let to_nanos = |samples: &[Duration]| -> Vec<f64> {
+            samples.iter().map(|d| d.as_nanos() as f64).collect()
+        };
+        self.analyze_values(&to_nanos(baseline), &to_nanos(candidate))
+    }
 }
 
+mod bayes;
 pub mod bootstrap;
+mod drift;
+mod modality;
+mod power;
+mod ratio;
+mod regression;
 mod ttest;
+pub use bayes::{BayesianTest, Posterior};
+pub use bootstrap::{Bootstrap, BootstrapInterval};
+pub use drift::{Changepoint, Drift, Trend, MIN_DRIFT_SAMPLES};
+pub use modality::{Modality, Mode, MIN_MODALITY_SAMPLES, MULTIMODAL_ALPHA};
+pub use power::PowerAnalysis;
+pub use ratio::{fieller_change_ci, log_delta_change_ci, ChangeInterval};
+pub use regression::{LinearFit, SlopeTest};
 pub use ttest::WelchTTest;
 
 /// Apply a Bonferroni multiple-comparisons correction to a batch of test results.
@@ -58,6 +142,7 @@ pub use ttest::WelchTTest;
 /// per-PR bench output. Mutates each `TestResult` in place: re-evaluates
 /// `statistically_significant` with the corrected threshold, and clears
 /// `winner` to `None` for any result that flips from significant to not.
+/// Equivalence claims and power analyses get the same corrected threshold.
 ///
 /// # Arguments
 /// * `results` — the results to adjust, one per benchmark comparison.
@@ -76,6 +161,12 @@ pub fn apply_bonferroni(results: &mut [TestResult], family_alpha: f64) {
     }
     let per_test_alpha = family_alpha / n as f64;
     for result in results.iter_mut() {
+        if let Some(equivalence) = &mut result.equivalence {
+            equivalence.equivalent = equivalence.p_value < per_test_alpha;
+        }
+        if let Some(power) = &mut result.power {
+            *power = power.rescaled(1.0 - result.confidence_level, per_test_alpha);
+        }
         if !result.statistically_significant {
             continue;
         }
@@ -105,6 +196,10 @@ mod tests {
             },
             baseline_mean_ns: 100.0,
             candidate_mean_ns: 95.0,
+            equivalence: None,
+            power: None,
+            posterior: None,
+            change_interval: None,
         }
     }
 
@@ -153,6 +248,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verdict_prefers_significance_over_equivalence() {
+        let equivalence = |equivalent| {
+            Some(Equivalence {
+                margin: 2.0,
+                p_value: if equivalent { 0.01 } else { 0.3 },
+                equivalent,
+            })
+        };
+        let mut faster = result(0.001, true);
+        faster.equivalence = equivalence(true);
+        assert_eq!(faster.verdict(), Verdict::Faster);
+
+        let mut unchanged = result(0.5, false);
+        assert_eq!(unchanged.verdict(), Verdict::Inconclusive);
+        unchanged.equivalence = equivalence(true);
+        assert_eq!(unchanged.verdict(), Verdict::Equivalent);
+        unchanged.equivalence = equivalence(false);
+        assert_eq!(unchanged.verdict(), Verdict::Inconclusive);
+
+        // 10 tests → α' = 0.005, so p=0.01 no longer establishes equivalence.
+        let mut results = vec![result(0.5, false); 10];
+        results[0].equivalence = equivalence(true);
+        apply_bonferroni(&mut results, 0.05);
+        assert_eq!(results[0].verdict(), Verdict::Inconclusive);
+    }
+
     #[test]
     fn test_bonferroni_does_not_touch_p_or_effect_size() {
         let mut results = vec![result(0.04, true); 10];
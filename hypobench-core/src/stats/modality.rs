@@ -0,0 +1,348 @@
+//! Mode count of a sample distribution.
+//!
+//! A benchmark that flips between two performance modes (an allocator size
+//! class boundary, an occasional hash collision chain) has a mean that sits
+//! between the modes and describes no actual run, and a variance that makes
+//! the t-test blind. [`Modality`] runs Silverman's test on the samples: the
+//! critical bandwidth is the narrowest Gaussian kernel whose density
+//! estimate has a single mode, and a smoothed bootstrap from that estimate
+//! gives how often unimodal data needs a kernel that wide. Skewed or
+//! heavy-tailed timings need a wide kernel only as often as their own
+//! resamples do, so they aren't flagged the way a fixed-bandwidth bump
+//! count would flag them. Modes are only counted between the 5th and 95th
+//! percentiles, so a few outliers never make one.
+
+use super::bayes::standard_normal;
+use super::bootstrap::quantile;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+/// Fewest samples the test runs on.
+pub const MIN_MODALITY_SAMPLES: usize = 20;
+
+/// p-value below which the samples count as multimodal.
+pub const MULTIMODAL_ALPHA: f64 = 0.05;
+
+/// Smoothed bootstrap resamples behind the p-value.
+const RESAMPLES: usize = 200;
+
+/// Seed for the resamples, so a report is reproducible.
+const SEED: u64 = 0x00D1_7E57;
+
+/// Halvings of the search for the critical bandwidth.
+const BISECTIONS: usize = 30;
+
+/// Bounds on the points the density is evaluated at. Between them, the grid
+/// step is a quarter of the bandwidth.
+const MIN_GRID_POINTS: usize = 64;
+const MAX_GRID_POINTS: usize = 8192;
+
+/// Bandwidths beyond which the kernel is cut off.
+const KERNEL_REACH: f64 = 6.0;
+
+/// Share of the samples in each tail whose bumps aren't counted as modes.
+/// Sparse tails wiggle at any bandwidth that resolves the bulk, and counting
+/// them flags skewed unimodal timings (after Hall and York, 2001).
+const TAIL: f64 = 0.05;
+
+/// One mode of a sample distribution.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Mode {
+    /// Median of the samples in this mode, in nanoseconds.
+    pub at_ns: f64,
+    /// Share of the samples closer to this mode than to its neighbours, from
+    /// 0 to 1.
+    pub share: f64,
+}
+
+/// Silverman's test for more than one mode.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Modality {
+    /// The modes, in ascending order of time: those of the density estimate
+    /// just narrower than the critical bandwidth if the test rejects a
+    /// single mode, else one holding every sample.
+    pub modes: Vec<Mode>,
+    /// Narrowest kernel bandwidth with a single mode, in nanoseconds.
+    pub critical_bandwidth_ns: f64,
+    /// Share of smoothed resamples that have more than one mode at the
+    /// critical bandwidth.
+    pub p_value: f64,
+}
+
+impl Modality {
+    /// Test `samples` for more than one mode. `None` with fewer than
+    /// [`MIN_MODALITY_SAMPLES`] samples.
+    pub fn estimate(samples: &[f64]) -> Option<Self> {
+        let n = samples.len();
+        if n < MIN_MODALITY_SAMPLES {
+            return None;
+        }
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        if var <= 0.0 {
+            return Some(Self {
+                modes: vec![Mode {
+                    at_ns: samples[0],
+                    share: 1.0,
+                }],
+                critical_bandwidth_ns: 0.0,
+                p_value: 1.0,
+            });
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let bulk = (quantile(&sorted, TAIL), quantile(&sorted, 1.0 - TAIL));
+
+        // Wider kernels never add modes, so bisect for the narrowest that
+        // leaves one.
+        let mut wide = var.sqrt();
+        while Density::new(samples, wide).peaks(bulk).len() > 1 {
+            wide *= 2.0;
+        }
+        let mut narrow = 0.0;
+        for _ in 0..BISECTIONS {
+            let mid = (narrow + wide) / 2.0;
+            if Density::new(samples, mid).peaks(bulk).len() > 1 {
+                narrow = mid;
+            } else {
+                wide = mid;
+            }
+        }
+        let critical = wide;
+
+        // Resample from the critical estimate, shrunk back to the sample
+        // variance so the kernel doesn't widen the resamples.
+        let shrink = (1.0 + critical * critical / var).sqrt();
+        let mut rng = SmallRng::seed_from_u64(SEED);
+        let mut resample = vec![0.0; n];
+        let mut multimodal = 0;
+        for _ in 0..RESAMPLES {
+            for y in &mut resample {
+                let x = samples[rng.random_range(0..n)];
+                *y = mean + (x - mean + critical * standard_normal(&mut rng)) / shrink;
+            }
+            if Density::new(&resample, critical).peaks(bulk).len() > 1 {
+                multimodal += 1;
+            }
+        }
+        let p_value = multimodal as f64 / RESAMPLES as f64;
+
+        let bandwidth = if p_value < MULTIMODAL_ALPHA && narrow > 0.0 {
+            narrow
+        } else {
+            critical
+        };
+        let density = Density::new(samples, bandwidth);
+        let peaks = density.peaks(bulk);
+        // The lowest point between each pair of neighbouring peaks.
+        let valleys: Vec<f64> = peaks
+            .windows(2)
+            .map(|pair| {
+                let lowest = (pair[0]..=pair[1])
+                    .min_by(|&a, &b| density.values[a].total_cmp(&density.values[b]))
+                    .expect("non-empty range");
+                density.at(lowest)
+            })
+            .collect();
+        // `sorted` splits into consecutive runs, one per mode.
+        let mut modes = Vec::with_capacity(peaks.len());
+        let mut rest = &sorted[..];
+        for i in 0..peaks.len().max(1) {
+            let len = match valleys.get(i) {
+                Some(&valley) => rest.partition_point(|&x| x <= valley),
+                None => rest.len(),
+            };
+            let (members, tail) = rest.split_at(len);
+            rest = tail;
+            if members.is_empty() {
+                continue;
+            }
+            modes.push(Mode {
+                at_ns: quantile(members, 0.5),
+                share: members.len() as f64 / n as f64,
+            });
+        }
+        Some(Self {
+            modes,
+            critical_bandwidth_ns: critical,
+            p_value,
+        })
+    }
+
+    /// Whether the test rejects a single mode.
+    pub fn multimodal(&self) -> bool {
+        self.modes.len() > 1
+    }
+}
+
+/// A Gaussian kernel density estimate on an even grid, from linearly binned
+/// samples. Unnormalized: only the shape matters.
+struct Density {
+    low: f64,
+    step: f64,
+    values: Vec<f64>,
+}
+
+impl Density {
+    fn new(samples: &[f64], bandwidth: f64) -> Self {
+        let (min, max) = samples
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        let (low, high) = (min - 3.0 * bandwidth, max + 3.0 * bandwidth);
+        let points = (((high - low) / (bandwidth / 4.0)).ceil() as usize + 1)
+            .clamp(MIN_GRID_POINTS, MAX_GRID_POINTS);
+        let step = (high - low) / (points - 1) as f64;
+
+        let mut weights = vec![0.0; points];
+        for &x in samples {
+            let at = (x - low) / step;
+            let i = (at.floor() as usize).min(points - 2);
+            let frac = at - i as f64;
+            weights[i] += 1.0 - frac;
+            weights[i + 1] += frac;
+        }
+        let reach = ((KERNEL_REACH * bandwidth / step).ceil() as usize).min(points - 1);
+        let kernel: Vec<f64> = (0..=reach)
+            .map(|k| (-0.5 * (k as f64 * step / bandwidth).powi(2)).exp())
+            .collect();
+        let values = (0..points)
+            .map(|i| {
+                let (from, to) = (i.saturating_sub(reach), (i + reach).min(points - 1));
+                (from..=to)
+                    .map(|j| weights[j] * kernel[i.abs_diff(j)])
+                    .sum()
+            })
+            .collect();
+        Self { low, step, values }
+    }
+
+    /// Grid indices of the local maxima.
+    fn peaks(&self, (from, to): (f64, f64)) -> Vec<usize> {
+        let d = &self.values;
+        (1..d.len() - 1)
+            .filter(|&i| d[i] > d[i - 1] && d[i] >= d[i + 1])
+            .filter(|&i| (from..=to).contains(&self.at(i)))
+            .collect()
+    }
+
+    /// The time at grid index `i`.
+    fn at(&self, i: usize) -> f64 {
+        self.low + i as f64 * self.step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+    use statrs::distribution::{ContinuousCDF, Normal};
+
+    /// `n` evenly spaced quantiles of a normal distribution.
+    fn normal(mean: f64, sd: f64, n: usize) -> Vec<f64> {
+        let dist = Normal::new(mean, sd).unwrap();
+        (0..n)
+            .map(|i| dist.inverse_cdf((i as f64 + 0.5) / n as f64))
+            .collect()
+    }
+
+    #[test]
+    fn finds_two_separated_modes() {
+        let mut samples = normal(1000.0, 20.0, 60);
+        samples.extend(normal(1500.0, 20.0, 40));
+        let modality = Modality::estimate(&samples).unwrap();
+        assert!(modality.multimodal());
+        assert_eq!(modality.modes.len(), 2, "{modality:?}");
+        let (fast, slow) = (modality.modes[0], modality.modes[1]);
+        assert!((fast.at_ns - 1000.0).abs() < 10.0, "{fast:?}");
+        assert!((slow.at_ns - 1500.0).abs() < 10.0, "{slow:?}");
+        assert!((fast.share - 0.6).abs() < 1e-9);
+        assert!((slow.share - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn normal_samples_have_one_mode() {
+        let modality = Modality::estimate(&normal(1000.0, 30.0, 200)).unwrap();
+        assert!(!modality.multimodal(), "{modality:?}");
+        assert!((modality.modes[0].at_ns - 1000.0).abs() < 5.0);
+        assert_eq!(modality.modes[0].share, 1.0);
+    }
+
+    #[test]
+    fn outliers_and_overlap_are_not_modes() {
+        // Two slow outliers in a hundred.
+        let mut samples = normal(1000.0, 20.0, 98);
+        samples.extend([5000.0, 5100.0]);
+        assert!(!Modality::estimate(&samples).unwrap().multimodal());
+
+        // Two components a standard deviation apart blur into one hump.
+        let mut samples = normal(1000.0, 20.0, 50);
+        samples.extend(normal(1020.0, 20.0, 50));
+        assert!(!Modality::estimate(&samples).unwrap().multimodal());
+    }
+
+    #[test]
+    fn needs_enough_samples() {
+        assert!(Modality::estimate(&normal(1.0, 1.0, MIN_MODALITY_SAMPLES - 1)).is_none());
+        let constant = Modality::estimate(&[7.0; MIN_MODALITY_SAMPLES]).unwrap();
+        assert_eq!(
+            constant.modes,
+            vec![Mode {
+                at_ns: 7.0,
+                share: 1.0
+            }]
+        );
+    }
+
+    /// `n` seeded draws of `draw`, from uniform and standard normal variates.
+    fn draws(n: usize, rng: &mut SmallRng, draw: impl Fn(f64, f64) -> f64) -> Vec<f64> {
+        (0..n)
+            .map(|_| {
+                let u = rng.random::<f64>();
+                draw(u, crate::stats::bayes::standard_normal(rng))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skewed_unimodal_samples_are_rarely_flagged() {
+        // Timings sit on a floor with a long right tail. A fixed-bandwidth
+        // bump count flags 15-20% of these; the test should stay near its
+        // 5% level.
+        let mut rng = SmallRng::seed_from_u64(11);
+        let trials = 60;
+        let shifted_exponential = (0..trials)
+            .filter(|_| {
+                let samples = draws(50, &mut rng, |u, _| 1000.0 - 100.0 * (1.0 - u).ln());
+                Modality::estimate(&samples).unwrap().multimodal()
+            })
+            .count();
+        let lognormal = (0..trials)
+            .filter(|_| {
+                let samples = draws(50, &mut rng, |_, z| 1000.0 * (0.5 * z).exp());
+                Modality::estimate(&samples).unwrap().multimodal()
+            })
+            .count();
+        assert!(shifted_exponential <= 4, "{shifted_exponential}/{trials}");
+        assert!(lognormal <= 4, "{lognormal}/{trials}");
+    }
+
+    #[test]
+    fn random_bimodal_samples_are_flagged() {
+        // A quarter of the runs take a slow path, both paths skewed.
+        let mut rng = SmallRng::seed_from_u64(12);
+        let trials = 20;
+        let flagged = (0..trials)
+            .filter(|_| {
+                let mut samples = draws(45, &mut rng, |u, _| 1000.0 - 100.0 * (1.0 - u).ln());
+                samples.extend(draws(15, &mut rng, |u, _| 2000.0 - 100.0 * (1.0 - u).ln()));
+                let modality = Modality::estimate(&samples).unwrap();
+                modality.p_value < MULTIMODAL_ALPHA && modality.modes.len() == 2
+            })
+            .count();
+        assert!(flagged >= 18, "{flagged}/{trials}");
+    }
+}
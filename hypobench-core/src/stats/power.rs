@@ -0,0 +1,119 @@
+//! Power analysis: could this comparison have seen a change at all?
+//!
+//! An inconclusive result means one of two things: the change is smaller than
+//! the benchmark can resolve, or there was no change. The minimum detectable
+//! effect (MDE) tells them apart. It is the smallest true change the test
+//! would flag with the requested power, given the observed variances and
+//! sample counts. If the MDE is ±8% and you care about 1%, the run was too
+//! short, and [`PowerAnalysis::required_sample_size`] says how long it would
+//! need to be.
+//!
+//! Both figures use the normal approximation to the two-sided Welch test:
+//! `MDE = (z(1 - α/2) + z(power)) · SE`.
+
+use statrs::distribution::{ContinuousCDF, Normal};
+
+/// Minimum detectable effect and required sample size for one comparison.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PowerAnalysis {
+    /// Probability of detecting a true change of `minimum_detectable_effect`
+    /// (e.g. 0.8).
+    pub power: f64,
+    /// Smallest change, in percent of the baseline mean, that the test would
+    /// detect with probability `power`. Applies in either direction.
+    pub minimum_detectable_effect: f64,
+    /// Samples per side needed to detect a change of `minimum_effect_size`
+    /// with probability `power`. `None` without a minimum effect size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_sample_size: Option<u64>,
+}
+
+impl PowerAnalysis {
+    /// Power analysis for a two-sided test at level `alpha` on samples with
+    /// the given baseline mean, variances and counts.
+    ///
+    /// Returns `None` if the baseline mean is zero, since percentages of it
+    /// are meaningless.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        alpha: f64,
+        power: f64,
+        minimum_effect_size: f64,
+        baseline_mean: f64,
+        baseline_variance: f64,
+        baseline_count: usize,
+        candidate_variance: f64,
+        candidate_count: usize,
+    ) -> Option<Self> {
+        if baseline_mean == 0.0 {
+            return None;
+        }
+        let multiplier = multiplier(alpha, power);
+        let se = (baseline_variance / baseline_count as f64
+            + candidate_variance / candidate_count as f64)
+            .sqrt();
+        let minimum_detectable_effect = multiplier * se / baseline_mean.abs() * 100.0;
+
+        // Equal sample counts per side: SE² = (var1 + var2) / n.
+        let required_sample_size = (minimum_effect_size > 0.0).then(|| {
+            let delta = minimum_effect_size / 100.0 * baseline_mean.abs();
+            let n = (multiplier / delta).powi(2) * (baseline_variance + candidate_variance);
+            (n.ceil() as u64).max(2)
+        });
+
+        Some(Self {
+            power,
+            minimum_detectable_effect,
+            required_sample_size,
+        })
+    }
+
+    /// The same analysis at significance level `new_alpha` instead of
+    /// `alpha`, e.g. after a multiple-comparisons correction.
+    pub(crate) fn rescaled(&self, alpha: f64, new_alpha: f64) -> Self {
+        let ratio = multiplier(new_alpha, self.power) / multiplier(alpha, self.power);
+        Self {
+            power: self.power,
+            minimum_detectable_effect: self.minimum_detectable_effect * ratio,
+            required_sample_size: self
+                .required_sample_size
+                .map(|n| ((n as f64 * ratio * ratio).ceil() as u64).max(2)),
+        }
+    }
+}
+
+/// `z(1 - α/2) + z(power)`: how many standard errors a true change must span
+/// to be detected with probability `power`.
+fn multiplier(alpha: f64, power: f64) -> f64 {
+    let normal = Normal::new(0.0, 1.0).expect("standard normal parameters are valid");
+    normal.inverse_cdf(1.0 - alpha / 2.0) + normal.inverse_cdf(power)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mde_and_required_sample_size() {
+        // SE = sqrt(2 · 100 / 50) = 2ns on a 1000ns mean; at α = 0.05 and 80%
+        // power the multiplier is 1.96 + 0.8416 = 2.8016.
+        let analysis = PowerAnalysis::new(0.05, 0.8, 1.0, 1000.0, 100.0, 50, 100.0, 50).unwrap();
+        assert!((analysis.minimum_detectable_effect - 0.56032).abs() < 1e-3);
+        // n = (2.8016 / 10)² · 200 = 15.7 → 16.
+        assert_eq!(analysis.required_sample_size, Some(16));
+
+        let without_target =
+            PowerAnalysis::new(0.05, 0.8, 0.0, 1000.0, 100.0, 50, 100.0, 50).unwrap();
+        assert_eq!(without_target.required_sample_size, None);
+        assert!(PowerAnalysis::new(0.05, 0.8, 1.0, 0.0, 1.0, 5, 1.0, 5).is_none());
+    }
+
+    #[test]
+    fn test_rescaled_to_stricter_alpha_needs_more_samples() {
+        let analysis = PowerAnalysis::new(0.05, 0.8, 1.0, 1000.0, 100.0, 50, 100.0, 50).unwrap();
+        let strict = analysis.rescaled(0.05, 0.005);
+        assert!(strict.minimum_detectable_effect > analysis.minimum_detectable_effect);
+        assert!(strict.required_sample_size.unwrap() > analysis.required_sample_size.unwrap());
+        assert_eq!(analysis.rescaled(0.05, 0.05), analysis);
+    }
+}
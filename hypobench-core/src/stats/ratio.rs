@@ -0,0 +1,208 @@
+//! Analytic confidence intervals on the relative change between two means.
+//!
+//! The relative change `(mean1 - mean2) / mean1` is one minus the ratio of
+//! means, so a CI on the ratio gives one on the change. Both intervals here
+//! need only each mean and its standard error, where the bootstrap resamples
+//! every sample thousands of times:
+//!
+//! - Fieller's theorem inverts the t-test of `mean2 - ρ·mean1 = 0` for the
+//!   ratio ρ. It is exact for normal means and stays valid, if asymmetric,
+//!   when the baseline mean is noisy.
+//! - The delta method approximates the standard error of `ln(mean2/mean1)`
+//!   and exponentiates a symmetric t interval on it.
+//!
+//! Both assume the two means are independent, which interleaved samples from
+//! separate processes are.
+
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+/// How the CI on a mean (or slope) change is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeInterval {
+    /// Resample both sides; see [`super::Bootstrap`].
+    #[default]
+    Bootstrap,
+    /// Fieller's interval on the ratio of means.
+    Fieller,
+    /// Delta-method interval on the log of the ratio of means.
+    Delta,
+}
+
+impl ChangeInterval {
+    /// The name used in config files and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeInterval::Bootstrap => "bootstrap",
+            ChangeInterval::Fieller => "fieller",
+            ChangeInterval::Delta => "delta",
+        }
+    }
+
+    /// The analytic CI, in percent, on the change from `mean1` to `mean2`,
+    /// given their standard errors and the degrees of freedom of their
+    /// difference. `None` for [`ChangeInterval::Bootstrap`], or when the
+    /// method can't bound the ratio; callers fall back to the bootstrap.
+    pub fn analytic_ci(
+        &self,
+        mean1: f64,
+        se1: f64,
+        mean2: f64,
+        se2: f64,
+        df: f64,
+        confidence: f64,
+    ) -> Option<(f64, f64)> {
+        let t = StudentsT::new(0.0, 1.0, df)
+            .ok()?
+            .inverse_cdf(1.0 - (1.0 - confidence) / 2.0);
+        match self {
+            ChangeInterval::Bootstrap => None,
+            ChangeInterval::Fieller => fieller_change_ci(mean1, se1, mean2, se2, t),
+            ChangeInterval::Delta => log_delta_change_ci(mean1, se1, mean2, se2, t),
+        }
+    }
+}
+
+/// Fieller's CI on the relative change `(mean1 - mean2) / mean1 * 100`, with
+/// critical value `t`.
+///
+/// The ratio ρ = mean2/mean1 is in the interval where
+/// `(mean2 - ρ·mean1)² <= t²·(se2² + ρ²·se1²)`, a quadratic in ρ. `None` if
+/// `mean1` isn't significantly non-zero, as the set is then unbounded.
+pub fn fieller_change_ci(mean1: f64, se1: f64, mean2: f64, se2: f64, t: f64) -> Option<(f64, f64)> {
+    let t2 = t * t;
+    let a = mean1 * mean1 - t2 * se1 * se1;
+    if a <= 0.0 {
+        return None;
+    }
+    let b = mean1 * mean2;
+    let c = mean2 * mean2 - t2 * se2 * se2;
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let (ratio_low, ratio_high) = ((b - root) / a, (b + root) / a);
+    Some(((1.0 - ratio_high) * 100.0, (1.0 - ratio_low) * 100.0))
+}
+
+/// Delta-method CI on the relative change `(mean1 - mean2) / mean1 * 100`,
+/// with critical value `t`: `ln(mean2/mean1) ± t·sqrt(se1²/mean1² +
+/// se2²/mean2²)`, exponentiated. `None` unless both means are positive.
+pub fn log_delta_change_ci(
+    mean1: f64,
+    se1: f64,
+    mean2: f64,
+    se2: f64,
+    t: f64,
+) -> Option<(f64, f64)> {
+    if mean1 <= 0.0 || mean2 <= 0.0 {
+        return None;
+    }
+    let log_ratio = (mean2 / mean1).ln();
+    let se = ((se1 / mean1).powi(2) + (se2 / mean2).powi(2)).sqrt();
+    Some((
+        (1.0 - (log_ratio + t * se).exp()) * 100.0,
+        (1.0 - (log_ratio - t * se).exp()) * 100.0,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::{Bootstrap, StatisticalTest, WelchTTest};
+    use rand::rngs::SmallRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn intervals_bracket_the_point_estimate() {
+        // 10% faster, each mean known to about 1%.
+        let (low, high) = fieller_change_ci(1000.0, 10.0, 900.0, 9.0, 2.0).unwrap();
+        assert!(low < 10.0 && 10.0 < high, "{low} {high}");
+        assert!((high - low - 5.1).abs() < 0.2, "{low} {high}");
+
+        let (low, high) = log_delta_change_ci(1000.0, 10.0, 900.0, 9.0, 2.0).unwrap();
+        assert!(low < 10.0 && 10.0 < high, "{low} {high}");
+        // Symmetric on the log scale, so slightly skewed as a change.
+        assert!(10.0 - low > high - 10.0);
+    }
+
+    #[test]
+    fn exact_means_give_a_point() {
+        let (low, high) = fieller_change_ci(1000.0, 0.0, 1100.0, 0.0, 2.0).unwrap();
+        assert!((low + 10.0).abs() < 1e-9 && (high + 10.0).abs() < 1e-9);
+        let (low, high) = log_delta_change_ci(1000.0, 0.0, 1100.0, 0.0, 2.0).unwrap();
+        assert!((low + 10.0).abs() < 1e-9 && (high + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unbounded_ratios_are_none() {
+        // The baseline mean is within noise of zero.
+        assert!(fieller_change_ci(10.0, 8.0, 100.0, 1.0, 2.0).is_none());
+        assert!(log_delta_change_ci(-1.0, 1.0, 100.0, 1.0, 2.0).is_none());
+        assert!(ChangeInterval::Bootstrap
+            .analytic_ci(1000.0, 10.0, 900.0, 9.0, 50.0, 0.95)
+            .is_none());
+    }
+
+    /// A log-normal draw with median `median` and log-scale spread `sigma`.
+    fn log_normal(median: f64, sigma: f64, rng: &mut impl Rng) -> f64 {
+        let u1: f64 = 1.0 - rng.random::<f64>();
+        let u2: f64 = rng.random();
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+        median * (sigma * z).exp()
+    }
+
+    #[test]
+    fn coverage_matches_the_bootstrap() {
+        // Right-skewed timings, candidate 10% faster at the median but with
+        // a longer tail.
+        let (base_sigma, cand_sigma) = (0.1_f64, 0.25_f64);
+        let base_mean = 1000.0 * (base_sigma.powi(2) / 2.0).exp();
+        let cand_mean = 900.0 * (cand_sigma.powi(2) / 2.0).exp();
+        let true_change = (base_mean - cand_mean) / base_mean * 100.0;
+
+        let confidence = 0.9;
+        let bootstrap = Bootstrap {
+            resamples: 1000,
+            ..Bootstrap::default()
+        };
+        let tests = [
+            ChangeInterval::Bootstrap,
+            ChangeInterval::Fieller,
+            ChangeInterval::Delta,
+        ]
+        .map(|interval| {
+            WelchTTest::new(confidence)
+                .with_bootstrap(bootstrap)
+                .with_change_interval(interval)
+        });
+
+        let trials = 400;
+        let mut covered = [0usize; 3];
+        let mut rng = SmallRng::seed_from_u64(7);
+        for _ in 0..trials {
+            let baseline: Vec<f64> = (0..30)
+                .map(|_| log_normal(1000.0, base_sigma, &mut rng))
+                .collect();
+            let candidate: Vec<f64> = (0..30)
+                .map(|_| log_normal(900.0, cand_sigma, &mut rng))
+                .collect();
+            for (test, hits) in tests.iter().zip(&mut covered) {
+                let result = test.analyze_values(&baseline, &candidate);
+                if result.change_ci_low <= true_change && true_change <= result.change_ci_high {
+                    *hits += 1;
+                }
+            }
+        }
+
+        let coverage = covered.map(|hits| hits as f64 / trials as f64);
+        for c in coverage {
+            // Nominal 90%, within a few standard errors of 400 trials.
+            assert!((0.84..=0.96).contains(&c), "{coverage:?}");
+        }
+        for c in &coverage[1..] {
+            assert!((c - coverage[0]).abs() < 0.04, "{coverage:?}");
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Linear-regression estimate of per-iteration time.
+//!
+//! Dividing a sample's elapsed time by its iteration count bakes any fixed
+//! per-sample overhead (request handling, timer reads, a cold first
+//! iteration) into the per-iteration figure. Taking samples at several
+//! iteration counts and fitting `elapsed = slope · iterations + intercept`
+//! separates the two: the slope is the marginal cost of one iteration and
+//! the intercept soaks up the overhead. [`SlopeTest`] compares the slopes of
+//! two such fits.
+
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+use super::bootstrap::Bootstrap;
+use super::ratio::ChangeInterval;
+use super::ttest::tost;
+use super::{Side, TestResult};
+
+/// Ordinary least-squares fit of elapsed time against iteration count.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinearFit {
+    /// Marginal time per iteration, in nanoseconds.
+    pub slope_ns: f64,
+    /// Fixed time per sample, in nanoseconds.
+    pub intercept_ns: f64,
+    /// Standard error of the slope.
+    pub slope_std_err: f64,
+    /// Share of the variance in elapsed time explained by the fit.
+    pub r_squared: f64,
+    /// Number of samples fitted.
+    pub samples: usize,
+}
+
+impl LinearFit {
+    /// Fit `elapsed_ns = slope · iterations + intercept` to `(iterations,
+    /// elapsed_ns)` samples. `None` with fewer than three samples or if every
+    /// sample ran the same number of iterations.
+    pub fn fit(runs: &[(f64, f64)]) -> Option<Self> {
+        let n = runs.len();
+        if n < 3 {
+            return None;
+        }
+        let mean_x = runs.iter().map(|&(x, _)| x).sum::<f64>() / n as f64;
+        let mean_y = runs.iter().map(|&(_, y)| y).sum::<f64>() / n as f64;
+        let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+        for &(x, y) in runs {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            sxx += dx * dx;
+            sxy += dx * dy;
+            syy += dy * dy;
+        }
+        if sxx == 0.0 {
+            return None;
+        }
+
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let sse: f64 = runs
+            .iter()
+            .map(|&(x, y)| (y - intercept - slope * x).powi(2))
+            .sum();
+        Some(Self {
+            slope_ns: slope,
+            intercept_ns: intercept,
+            slope_std_err: (sse / (n - 2) as f64 / sxx).sqrt(),
+            r_squared: if syy > 0.0 { 1.0 - sse / syy } else { 1.0 },
+            samples: n,
+        })
+    }
+}
+
+/// Compares per-iteration time as the slopes of two [`LinearFit`]s.
+///
+/// The p-value comes from a Welch-style t-test on the slope difference, with
+/// each slope's standard error and `n - 2` degrees of freedom. The change CI
+/// bootstraps whole samples, refitting both sides each time. Results use the
+/// same conventions as [`super::WelchTTest`], with the slopes in the
+/// `*_mean_ns` fields. With an analytic [`ChangeInterval`], the change CI
+/// comes from the slopes and their standard errors instead.
+#[derive(Debug, Clone)]
+pub struct SlopeTest {
+    /// The confidence level for determining statistical significance (default: 0.95).
+    pub confidence_level: f64,
+    /// Minimum absolute change (percent) for a result to count as significant.
+    /// See [`super::WelchTTest::minimum_effect_size`].
+    pub minimum_effect_size: f64,
+    /// Equivalence margin (percent of the baseline slope) for a TOST check.
+    pub equivalence_margin: Option<f64>,
+    /// Resample count and seed for the change CI. The interval is always a
+    /// percentile one.
+    pub bootstrap: Bootstrap,
+    /// How the change CI is computed.
+    pub change_interval: ChangeInterval,
+}
+
+impl Default for SlopeTest {
+    fn default() -> Self {
+        Self::new(0.95)
+    }
+}
+
+impl SlopeTest {
+    /// Create a slope test with the specified confidence level.
+    ///
+    /// # Panics
+    /// Panics if confidence_level is not in the range (0, 1).
+    pub fn new(confidence_level: f64) -> Self {
+        assert!(
+            confidence_level > 0.0 && confidence_level < 1.0,
+            "confidence_level must be between 0 and 1 (exclusive)"
+        );
+        Self {
+            confidence_level,
+            minimum_effect_size: 0.0,
+            equivalence_margin: None,
+            bootstrap: Bootstrap::default(),
+            change_interval: ChangeInterval::Bootstrap,
+        }
+    }
+
+    /// Set the minimum absolute change (percent) required for significance.
+    pub fn with_minimum_effect_size(mut self, threshold: f64) -> Self {
+        assert!(threshold >= 0.0, "minimum_effect_size must be non-negative");
+        self.minimum_effect_size = threshold;
+        self
+    }
+
+    /// Also test whether the slopes are within ±`margin` percent.
+    pub fn with_equivalence_margin(mut self, margin: f64) -> Self {
+        assert!(margin > 0.0, "equivalence_margin must be positive");
+        self.equivalence_margin = Some(margin);
+        self
+    }
+
+    /// Set the resample count and seed for the change CI.
+    pub fn with_bootstrap(mut self, bootstrap: Bootstrap) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    /// Set how the change CI is computed.
+    pub fn with_change_interval(mut self, interval: ChangeInterval) -> Self {
+        self.change_interval = interval;
+        self
+    }
+
+    /// Compare the per-iteration slopes of baseline and candidate samples,
+    /// each given as `(iterations, elapsed_ns)`.
+    ///
+    /// If either side can't be fitted, the result is inconclusive with
+    /// `p_value = 1.0` and zero slopes.
+    pub fn analyze_runs(&self, baseline: &[(f64, f64)], candidate: &[(f64, f64)]) -> TestResult {
+        let fits = LinearFit::fit(baseline).zip(LinearFit::fit(candidate));
+        let Some((base, cand)) = fits else {
+            return TestResult {
+                p_value: 1.0,
+                statistically_significant: false,
+                effect_size: 0.0,
+                change_ci_low: 0.0,
+                change_ci_high: 0.0,
+                confidence_level: self.confidence_level,
+                winner: None,
+                baseline_mean_ns: 0.0,
+                candidate_mean_ns: 0.0,
+                equivalence: self.equivalence_margin.map(|margin| super::Equivalence {
+                    margin,
+                    p_value: 1.0,
+                    equivalent: false,
+                }),
+                power: None,
+                posterior: None,
+                change_interval: None,
+            };
+        };
+
+        let (slope1, slope2) = (base.slope_ns, cand.slope_ns);
+        let (v1, v2) = (base.slope_std_err.powi(2), cand.slope_std_err.powi(2));
+        let se = (v1 + v2).sqrt();
+        // Welch-Satterthwaite, with n - 2 degrees of freedom per fit.
+        let df = if se > 0.0 {
+            (v1 + v2).powi(2)
+                / (v1.powi(2) / (base.samples - 2) as f64 + v2.powi(2) / (cand.samples - 2) as f64)
+        } else {
+            (base.samples.min(cand.samples) - 2) as f64
+        };
+        let p_value = if se == 0.0 {
+            if slope1 == slope2 {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            match StudentsT::new(0.0, 1.0, df) {
+                Ok(t_dist) => 2.0 * (1.0 - t_dist.cdf(((slope1 - slope2) / se).abs())),
+                Err(_) => 1.0,
+            }
+        };
+
+        let effect_size = if slope1 != 0.0 {
+            (slope1 - slope2) / slope1 * 100.0
+        } else {
+            0.0
+        };
+        let alpha = 1.0 - self.confidence_level;
+        let statistically_significant =
+            p_value < alpha && effect_size.abs() >= self.minimum_effect_size;
+        let winner = statistically_significant.then_some(if slope1 > slope2 {
+            Side::Candidate
+        } else {
+            Side::Baseline
+        });
+        let analytic = self.change_interval.analytic_ci(
+            slope1,
+            base.slope_std_err,
+            slope2,
+            cand.slope_std_err,
+            df,
+            self.confidence_level,
+        );
+        let (change_ci_low, change_ci_high, change_interval) = match analytic {
+            Some((low, high)) => (low, high, self.change_interval),
+            None => {
+                let (low, high) =
+                    self.bootstrap
+                        .slope_change_ci(baseline, candidate, self.confidence_level);
+                (low, high, ChangeInterval::Bootstrap)
+            }
+        };
+
+        TestResult {
+            p_value,
+            statistically_significant,
+            effect_size,
+            change_ci_low,
+            change_ci_high,
+            confidence_level: self.confidence_level,
+            winner,
+            baseline_mean_ns: slope1,
+            candidate_mean_ns: slope2,
+            equivalence: self
+                .equivalence_margin
+                .map(|margin| tost(margin, self.confidence_level, slope1, slope2, se, df)),
+            power: None,
+            posterior: None,
+            change_interval: Some(change_interval),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples at 1..=n iterations with a fixed overhead and a little
+    /// alternating noise.
+    fn runs(per_iter: f64, overhead: f64, n: usize) -> Vec<(f64, f64)> {
+        (1..=n)
+            .map(|i| {
+                let noise = if i % 2 == 0 { 1.0 } else { -1.0 } * (i % 7) as f64;
+                (i as f64, per_iter * i as f64 + overhead + noise)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fit_separates_overhead_from_slope() {
+        let fit = LinearFit::fit(&[(1.0, 150.0), (2.0, 250.0), (4.0, 450.0)]).unwrap();
+        assert!((fit.slope_ns - 100.0).abs() < 1e-9);
+        assert!((fit.intercept_ns - 50.0).abs() < 1e-9);
+        assert!(fit.slope_std_err < 1e-9, "{}", fit.slope_std_err);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+        assert_eq!(fit.samples, 3);
+    }
+
+    #[test]
+    fn fit_needs_varying_iterations() {
+        assert!(LinearFit::fit(&[(1.0, 10.0), (2.0, 20.0)]).is_none());
+        assert!(LinearFit::fit(&[(5.0, 10.0), (5.0, 11.0), (5.0, 12.0)]).is_none());
+    }
+
+    #[test]
+    fn overhead_does_not_mask_a_slope_change() {
+        // Candidate's overhead doubled but each iteration got 10% faster; the
+        // per-iteration means would call that a regression at low counts.
+        let baseline = runs(100.0, 1_000.0, 40);
+        let candidate = runs(90.0, 2_000.0, 40);
+        let result = SlopeTest::new(0.95)
+            .with_minimum_effect_size(1.0)
+            .analyze_runs(&baseline, &candidate);
+        assert!(result.statistically_significant);
+        assert_eq!(result.winner, Some(Side::Candidate));
+        assert!(
+            (result.effect_size - 10.0).abs() < 0.5,
+            "{}",
+            result.effect_size
+        );
+        assert!(result.change_ci_low < result.effect_size);
+        assert!(result.effect_size < result.change_ci_high);
+        assert!((result.baseline_mean_ns - 100.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn same_slope_is_not_significant_and_can_be_equivalent() {
+        let baseline = runs(100.0, 500.0, 40);
+        let candidate = runs(100.0, 900.0, 40);
+        let result = SlopeTest::new(0.95)
+            .with_equivalence_margin(2.0)
+            .analyze_runs(&baseline, &candidate);
+        assert!(!result.statistically_significant);
+        assert!(result.equivalence.unwrap().equivalent);
+    }
+
+    #[test]
+    fn analytic_interval_agrees_with_bootstrap() {
+        let baseline = runs(100.0, 1_000.0, 40);
+        let candidate = runs(90.0, 2_000.0, 40);
+        let bootstrapped = SlopeTest::new(0.95).analyze_runs(&baseline, &candidate);
+        let fieller = SlopeTest::new(0.95)
+            .with_change_interval(ChangeInterval::Fieller)
+            .analyze_runs(&baseline, &candidate);
+        assert_eq!(fieller.p_value, bootstrapped.p_value);
+        assert!(fieller.change_ci_low < fieller.effect_size);
+        assert!(fieller.effect_size < fieller.change_ci_high);
+        assert!(
+            (fieller.change_ci_low - bootstrapped.change_ci_low).abs() < 0.5,
+            "{} vs {}",
+            fieller.change_ci_low,
+            bootstrapped.change_ci_low
+        );
+        assert!(
+            (fieller.change_ci_high - bootstrapped.change_ci_high).abs() < 0.5,
+            "{} vs {}",
+            fieller.change_ci_high,
+            bootstrapped.change_ci_high
+        );
+    }
+
+    #[test]
+    fn unfittable_side_is_inconclusive() {
+        let result = SlopeTest::default().analyze_runs(&[(1.0, 10.0)], &runs(1.0, 0.0, 10));
+        assert_eq!(result.p_value, 1.0);
+        assert!(!result.statistically_significant);
+    }
+}
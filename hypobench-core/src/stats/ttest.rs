@@ -1,20 +1,8 @@
-use std::time::Duration;
-
-use rand::rngs::SmallRng;
-use rand::SeedableRng;
 use statrs::distribution::{ContinuousCDF, StudentsT};
 
-use super::bootstrap::bootstrap_change_ci;
-use super::{Side, StatisticalTest, TestResult};
-
-/// Seed used for the bootstrap RNG inside `WelchTTest::analyze`. Fixed so that
-/// results are reproducible on identical sample inputs across runs.
-const BOOTSTRAP_SEED: u64 = 0xC0FFEE;
-
-/// Number of bootstrap resamples to compute the change CI with. 10_000 is the
-/// conventional default for percentile bootstrap; enough to stabilise the tail
-/// percentile estimates without blowing up wall time (~ms per benchmark).
-const BOOTSTRAP_N_RESAMPLES: usize = 10_000;
+use super::bootstrap::Bootstrap;
+use super::ratio::ChangeInterval;
+use super::{Equivalence, PowerAnalysis, Side, StatisticalTest, TestResult};
 
 /// Welch's t-test for comparing two independent samples with potentially unequal variances.
 ///
@@ -34,6 +22,20 @@ pub struct WelchTTest {
     /// (branch-predictor drift, cache-line alignment, etc.) on large benchmark suites
     /// where small p-values are easy to achieve with moderate sample sizes.
     pub minimum_effect_size: f64,
+    /// Equivalence margin (percent of the baseline mean) for a two-one-sided-tests
+    /// (TOST) equivalence check. `None` (the default) skips the check and leaves
+    /// `TestResult::equivalence` empty.
+    pub equivalence_margin: Option<f64>,
+    /// Power (default: 0.8) at which to report the minimum detectable effect
+    /// and the sample size needed to detect `minimum_effect_size`.
+    pub power: f64,
+    /// How the change CI is bootstrapped. The default, 10_000 resamples with
+    /// a fixed seed and percentile intervals, stabilises the tail percentiles
+    /// in ~ms per benchmark and is reproducible on identical inputs.
+    pub bootstrap: Bootstrap,
+    /// How the change CI is computed: bootstrapped (the default), or
+    /// analytically from the means and their standard errors.
+    pub change_interval: ChangeInterval,
 }
 
 impl Default for WelchTTest {
@@ -41,6 +43,10 @@ impl Default for WelchTTest {
         Self {
             confidence_level: 0.95,
             minimum_effect_size: 0.0,
+            equivalence_margin: None,
+            power: 0.8,
+            bootstrap: Bootstrap::default(),
+            change_interval: ChangeInterval::Bootstrap,
         }
     }
 }
@@ -61,6 +67,10 @@ impl WelchTTest {
         Self {
             confidence_level,
             minimum_effect_size: 0.0,
+            equivalence_margin: None,
+            power: 0.8,
+            bootstrap: Bootstrap::default(),
+            change_interval: ChangeInterval::Bootstrap,
         }
     }
 
@@ -72,25 +82,60 @@ impl WelchTTest {
         self
     }
 
-    /// Calculate the sample mean of durations in nanoseconds.
-    fn mean_ns(samples: &[Duration]) -> f64 {
+    /// Also test whether the change is confidently within ±`margin` percent.
+    /// See [`Self::equivalence_margin`].
+    pub fn with_equivalence_margin(mut self, margin: f64) -> Self {
+        assert!(margin > 0.0, "equivalence_margin must be positive");
+        self.equivalence_margin = Some(margin);
+        self
+    }
+
+    /// Set the power for the minimum detectable effect. See [`Self::power`].
+    pub fn with_power(mut self, power: f64) -> Self {
+        assert!(
+            power > 0.0 && power < 1.0,
+            "power must be between 0 and 1 (exclusive)"
+        );
+        self.power = power;
+        self
+    }
+
+    /// Set how the change CI is bootstrapped. See [`Self::bootstrap`].
+    pub fn with_bootstrap(mut self, bootstrap: Bootstrap) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    /// Set how the change CI is computed. See [`Self::change_interval`].
+    pub fn with_change_interval(mut self, interval: ChangeInterval) -> Self {
+        self.change_interval = interval;
+        self
+    }
+
+    /// TOST against ±`margin` percent of the baseline mean, if a margin is set.
+    fn tost(&self, mean1: f64, mean2: f64, se: f64, df: f64) -> Option<Equivalence> {
+        let margin = self.equivalence_margin?;
+        Some(tost(margin, self.confidence_level, mean1, mean2, se, df))
+    }
+
+    /// Calculate the sample mean.
+    fn mean(samples: &[f64]) -> f64 {
         if samples.is_empty() {
             return 0.0;
         }
-        let sum: f64 = samples.iter().map(|d| d.as_nanos() as f64).sum();
-        sum / samples.len() as f64
+        samples.iter().sum::<f64>() / samples.len() as f64
     }
 
-    /// Calculate the sample variance of durations in nanoseconds.
+    /// Calculate the sample variance.
     /// Uses Bessel's correction (n-1 denominator) for unbiased estimation.
-    fn variance_ns(samples: &[Duration], mean: f64) -> f64 {
+    fn variance(samples: &[f64], mean: f64) -> f64 {
         if samples.len() < 2 {
             return 0.0;
         }
         let sum_sq_diff: f64 = samples
             .iter()
-            .map(|d| {
-                let diff = d.as_nanos() as f64 - mean;
+            .map(|x| {
+                let diff = x - mean;
                 diff * diff
             })
             .sum();
@@ -116,13 +161,13 @@ impl WelchTTest {
 }
 
 impl StatisticalTest for WelchTTest {
-    fn analyze(&self, baseline: &[Duration], candidate: &[Duration]) -> TestResult {
+    fn analyze_values(&self, baseline: &[f64], candidate: &[f64]) -> TestResult {
         let n1 = baseline.len();
         let n2 = candidate.len();
 
         // Calculate means
-        let mean1 = Self::mean_ns(baseline);
-        let mean2 = Self::mean_ns(candidate);
+        let mean1 = Self::mean(baseline);
+        let mean2 = Self::mean(candidate);
 
         // Handle edge cases with insufficient data
         if n1 < 2 || n2 < 2 {
@@ -136,16 +181,35 @@ impl StatisticalTest for WelchTTest {
                 winner: None,
                 baseline_mean_ns: mean1,
                 candidate_mean_ns: mean2,
+                equivalence: self.equivalence_margin.map(|margin| Equivalence {
+                    margin,
+                    p_value: 1.0,
+                    equivalent: false,
+                }),
+                power: None,
+                posterior: None,
+                change_interval: None,
             };
         }
 
         // Calculate variances
-        let var1 = Self::variance_ns(baseline, mean1);
-        let var2 = Self::variance_ns(candidate, mean2);
+        let var1 = Self::variance(baseline, mean1);
+        let var2 = Self::variance(candidate, mean2);
 
         // Calculate standard error of the difference
         let se = (var1 / n1 as f64 + var2 / n2 as f64).sqrt();
 
+        let power = PowerAnalysis::new(
+            1.0 - self.confidence_level,
+            self.power,
+            self.minimum_effect_size,
+            mean1,
+            var1,
+            n1,
+            var2,
+            n2,
+        );
+
         // Handle case where both samples have zero variance
         if se == 0.0 {
             let effect_size = if mean1 != 0.0 {
@@ -179,6 +243,18 @@ impl StatisticalTest for WelchTTest {
                 winner,
                 baseline_mean_ns: mean1,
                 candidate_mean_ns: mean2,
+                // No noise: the change is exactly `effect_size`.
+                equivalence: self.equivalence_margin.map(|margin| {
+                    let equivalent = effect_size.abs() < margin;
+                    Equivalence {
+                        margin,
+                        p_value: if equivalent { 0.0 } else { 1.0 },
+                        equivalent,
+                    }
+                }),
+                power,
+                posterior: None,
+                change_interval: None,
             };
         }
 
@@ -228,16 +304,26 @@ impl StatisticalTest for WelchTTest {
             None
         };
 
-        // Non-parametric bootstrap CI on the relative mean change. Uses a fixed
-        // seed for reproducibility across runs on identical inputs.
-        let mut rng = SmallRng::seed_from_u64(BOOTSTRAP_SEED);
-        let (change_ci_low, change_ci_high) = bootstrap_change_ci(
-            baseline,
-            candidate,
-            BOOTSTRAP_N_RESAMPLES,
+        // CI on the relative mean change: analytic if configured and the
+        // ratio is bounded, else a bootstrap seeded for reproducibility
+        // across runs on identical inputs.
+        let analytic = self.change_interval.analytic_ci(
+            mean1,
+            (var1 / n1 as f64).sqrt(),
+            mean2,
+            (var2 / n2 as f64).sqrt(),
+            df,
             self.confidence_level,
-            &mut rng,
         );
+        let (change_ci_low, change_ci_high, change_interval) = match analytic {
+            Some((low, high)) => (low, high, self.change_interval),
+            None => {
+                let (low, high) =
+                    self.bootstrap
+                        .change_ci(baseline, candidate, self.confidence_level);
+                (low, high, ChangeInterval::Bootstrap)
+            }
+        };
 
         TestResult {
             p_value,
@@ -249,13 +335,48 @@ impl StatisticalTest for WelchTTest {
             winner,
             baseline_mean_ns: mean1,
             candidate_mean_ns: mean2,
+            equivalence: self.tost(mean1, mean2, se, df),
+            power,
+            posterior: None,
+            change_interval: Some(change_interval),
         }
     }
 }
 
+/// Two one-sided t-tests of the difference between `mean1` and `mean2`, with
+/// standard error `se` and `df` degrees of freedom, against the bounds
+/// ±`margin` percent of `mean1`. Equivalence is claimed only if both "slower
+/// by at least the margin" and "faster by at least the margin" are rejected
+/// at `1 - confidence_level`.
+pub(super) fn tost(
+    margin: f64,
+    confidence_level: f64,
+    mean1: f64,
+    mean2: f64,
+    se: f64,
+    df: f64,
+) -> Equivalence {
+    let bound = margin / 100.0 * mean1.abs();
+    let diff = mean2 - mean1;
+    let p_value = match StudentsT::new(0.0, 1.0, df) {
+        Ok(t_dist) => {
+            let p_lower = 1.0 - t_dist.cdf((diff + bound) / se);
+            let p_upper = t_dist.cdf((diff - bound) / se);
+            p_lower.max(p_upper)
+        }
+        Err(_) => 1.0,
+    };
+    Equivalence {
+        margin,
+        p_value,
+        equivalent: p_value < 1.0 - confidence_level,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     fn durations_from_nanos(nanos: &[u64]) -> Vec<Duration> {
         nanos.iter().map(|&n| Duration::from_nanos(n)).collect()
@@ -387,6 +508,107 @@ mod tests {
         assert_eq!(result.winner, Some(Side::Candidate));
     }
 
+    #[test]
+    fn test_equivalence_within_margin() {
+        // ~0.1% apart with low noise: not significant at a 1% gate, but
+        // confidently within ±2%.
+        let baseline = durations_from_nanos(&[1000, 1002, 998, 1001, 999, 1000, 1003, 997]);
+        let candidate = durations_from_nanos(&[1001, 1003, 999, 1002, 1000, 1001, 1004, 998]);
+        let test = WelchTTest::new(0.95)
+            .with_minimum_effect_size(1.0)
+            .with_equivalence_margin(2.0);
+
+        let result = test.analyze(&baseline, &candidate);
+        assert!(!result.statistically_significant);
+        let equivalence = result.equivalence.unwrap();
+        assert_eq!(equivalence.margin, 2.0);
+        assert!(equivalence.equivalent, "p={}", equivalence.p_value);
+        assert_eq!(result.verdict(), crate::stats::Verdict::Equivalent);
+
+        // The same data can't rule out a 0.05% change.
+        let tight = WelchTTest::new(0.95).with_equivalence_margin(0.05);
+        assert!(
+            !tight
+                .analyze(&baseline, &candidate)
+                .equivalence
+                .unwrap()
+                .equivalent
+        );
+
+        // Without a margin there's no equivalence check at all.
+        assert!(WelchTTest::default()
+            .analyze(&baseline, &candidate)
+            .equivalence
+            .is_none());
+    }
+
+    #[test]
+    fn test_equivalence_needs_enough_data() {
+        // Noisy and few samples: neither significant nor equivalent.
+        let baseline = durations_from_nanos(&[900, 1100, 950, 1050]);
+        let candidate = durations_from_nanos(&[1000, 1150, 880, 1020]);
+        let test = WelchTTest::new(0.95).with_equivalence_margin(2.0);
+
+        let result = test.analyze(&baseline, &candidate);
+        assert!(!result.statistically_significant);
+        assert!(!result.equivalence.unwrap().equivalent);
+        assert_eq!(result.verdict(), crate::stats::Verdict::Inconclusive);
+
+        let result = test.analyze(&durations_from_nanos(&[100]), &durations_from_nanos(&[100]));
+        assert!(!result.equivalence.unwrap().equivalent);
+
+        let result = test.analyze(
+            &durations_from_nanos(&[100, 100, 100]),
+            &durations_from_nanos(&[101, 101, 101]),
+        );
+        assert!(result.equivalence.unwrap().equivalent);
+    }
+
+    #[test]
+    fn test_power_analysis_explains_inconclusive_results() {
+        // ~5% noise and 8 samples: a 1% change is far below what this can see.
+        let baseline = durations_from_nanos(&[950, 1050, 1000, 980, 1020, 960, 1040, 1000]);
+        let candidate = durations_from_nanos(&[1040, 960, 1010, 990, 1000, 1050, 950, 1000]);
+        let result = WelchTTest::new(0.95)
+            .with_minimum_effect_size(1.0)
+            .analyze(&baseline, &candidate);
+
+        assert!(!result.statistically_significant);
+        let power = result.power.unwrap();
+        assert_eq!(power.power, 0.8);
+        assert!(
+            power.minimum_detectable_effect > 3.0,
+            "MDE {}%",
+            power.minimum_detectable_effect
+        );
+        assert!(power.required_sample_size.unwrap() > 8);
+
+        let higher = WelchTTest::new(0.95)
+            .with_minimum_effect_size(1.0)
+            .with_power(0.95)
+            .analyze(&baseline, &candidate)
+            .power
+            .unwrap();
+        assert!(higher.minimum_detectable_effect > power.minimum_detectable_effect);
+
+        let single = WelchTTest::default()
+            .analyze(&durations_from_nanos(&[100]), &durations_from_nanos(&[100]));
+        assert!(single.power.is_none());
+    }
+
+    #[test]
+    fn test_records_change_interval_fallback() {
+        let test = WelchTTest::default().with_change_interval(ChangeInterval::Fieller);
+        let candidate = [10.0, 11.0, 12.0, 13.0, 14.0];
+
+        let result = test.analyze_values(&[100.0, 102.0, 98.0, 101.0, 99.0], &candidate);
+        assert_eq!(result.change_interval, Some(ChangeInterval::Fieller));
+
+        // The baseline mean is within noise of zero, so the ratio is unbounded.
+        let result = test.analyze_values(&[1.0, 30.0, 2.0, 25.0, 3.0], &candidate);
+        assert_eq!(result.change_interval, Some(ChangeInterval::Bootstrap));
+    }
+
     #[test]
     #[should_panic(expected = "minimum_effect_size must be non-negative")]
     fn test_invalid_minimum_effect_size() {
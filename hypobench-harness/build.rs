@@ -0,0 +1,29 @@
+//! Records how the harness was compiled so `GET /info` can report it.
+//!
+//! The harness is built in the same cargo invocation as the benchmark binary
+//! that links it, so its toolchain, target and profile are the binary's too.
+
+use std::process::Command;
+
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("-V")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=HYPOBENCH_BUILD_RUSTC={}", rustc_version);
+    for (var, key) in [
+        ("TARGET", "HYPOBENCH_BUILD_TARGET"),
+        ("PROFILE", "HYPOBENCH_BUILD_PROFILE"),
+        ("OPT_LEVEL", "HYPOBENCH_BUILD_OPT_LEVEL"),
+    ] {
+        let value = std::env::var(var).unwrap_or_else(|_| "unknown".to_string());
+        println!("cargo:rustc-env={}={}", key, value);
+    }
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
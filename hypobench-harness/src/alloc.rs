@@ -0,0 +1,188 @@
+//! Opt-in heap allocation counting.
+//!
+//! A benchmark binary opts in by installing [`CountingAllocator`] as its
+//! global allocator. The harness then reports the allocations each `/run`
+//! makes alongside its duration. Counters are per thread, so only
+//! allocations made on the thread running the benchmark are counted; work a
+//! benchmark hands to other threads is not.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use hypobench_core::protocol::AllocationStats;
+
+/// Set by the first allocation through a [`CountingAllocator`], so the harness
+/// can tell "no allocations" from "not counting".
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Running totals for one thread. Only `Cell`s, so the thread-local needs no
+/// destructor and is safe to touch from inside the allocator.
+struct Counters {
+    count: Cell<u64>,
+    bytes: Cell<u64>,
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            count: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// A global allocator that counts allocations for the harness.
+///
+/// Wraps another allocator ([`System`] by default) and adds a few thread-local
+/// increments per call.
+///
+/// # Example
+///
+/// ```ignore
+/// use hypobench_harness::CountingAllocator;
+///
+/// #[global_allocator]
+/// static ALLOC: CountingAllocator = CountingAllocator::system();
+/// ```
+pub struct CountingAllocator<A = System> {
+    inner: A,
+    /// Flag the first allocation sets: [`INSTALLED`], except in tests, which
+    /// mustn't turn on counting for the rest of the test binary.
+    installed: &'static AtomicBool,
+}
+
+impl CountingAllocator<System> {
+    /// Count allocations made through the system allocator.
+    pub const fn system() -> Self {
+        Self::new(System)
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Count allocations made through `inner`.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            installed: &INSTALLED,
+        }
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to `inner`; the bookkeeping
+// doesn't allocate and doesn't touch the returned memory.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        let _ = COUNTERS.try_with(|c| c.live.set(c.live.get() - layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Count an allocation of `size` bytes that replaces `freed` live bytes.
+    fn record(&self, size: usize, freed: usize) {
+        if !self.installed.load(Ordering::Relaxed) {
+            self.installed.store(true, Ordering::Relaxed);
+        }
+        // `try_with` fails only while the thread is being torn down.
+        let _ = COUNTERS.try_with(|c| {
+            c.count.set(c.count.get() + 1);
+            c.bytes.set(c.bytes.get() + size as u64);
+            let live = c.live.get() + size as i64 - freed as i64;
+            c.live.set(live);
+            if live > c.peak.get() {
+                c.peak.set(live);
+            }
+        });
+    }
+}
+
+/// Run `f` and count the allocations it makes on this thread.
+///
+/// Returns `None` for the stats if the binary doesn't use a
+/// [`CountingAllocator`].
+pub(crate) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocationStats>) {
+    measure_with(&INSTALLED, f)
+}
+
+/// [`measure`], counting only if `installed` is set.
+fn measure_with<T>(installed: &AtomicBool, f: impl FnOnce() -> T) -> (T, Option<AllocationStats>) {
+    if !installed.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+
+    let (count, bytes, live) = COUNTERS.with(|c| {
+        c.peak.set(c.live.get());
+        (c.count.get(), c.bytes.get(), c.live.get())
+    });
+    let result = f();
+    let stats = COUNTERS.with(|c| AllocationStats {
+        count: c.count.get() - count,
+        bytes: c.bytes.get() - bytes,
+        peak_live_bytes: (c.peak.get() - live).max(0) as u64,
+    });
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The test binary doesn't install the allocator, so drive it directly,
+    // with its own flag so other tests' runs still see no allocator.
+    static TEST_INSTALLED: AtomicBool = AtomicBool::new(false);
+    static ALLOC: CountingAllocator = CountingAllocator {
+        inner: System,
+        installed: &TEST_INSTALLED,
+    };
+
+    #[test]
+    fn test_measure_counts_allocations_on_this_thread() {
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        assert!(measure_with(&TEST_INSTALLED, || ()).1.is_none());
+        // Mark the allocator as installed before measuring.
+        unsafe { ALLOC.dealloc(ALLOC.alloc(layout), layout) };
+
+        let (_, stats) = measure_with(&TEST_INSTALLED, || unsafe {
+            let a = ALLOC.alloc(layout);
+            let b = ALLOC.alloc_zeroed(layout);
+            let b = ALLOC.realloc(b, layout, 128);
+            ALLOC.dealloc(a, layout);
+            ALLOC.dealloc(b, Layout::from_size_align(128, 8).unwrap());
+        });
+
+        let stats = stats.expect("allocator is installed");
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.bytes, 64 + 64 + 128);
+        assert_eq!(stats.peak_live_bytes, 64 + 128);
+        assert!(!INSTALLED.load(Ordering::Relaxed));
+    }
+}
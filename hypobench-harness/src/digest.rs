@@ -0,0 +1,80 @@
+//! Digests of benchmark outputs, for comparing baseline and candidate.
+
+use std::hash::{Hash, Hasher};
+
+/// 64-bit FNV-1a, with integers written little-endian and `usize` widened
+/// to 64 bits.
+///
+/// Unlike `DefaultHasher`, its output is fixed by definition rather than by
+/// the standard library version, and it doesn't depend on the target's
+/// endianness or pointer width. What it can't pin down is the bytes a type's
+/// `Hash` impl writes: std doesn't promise those are stable (`str` appends a
+/// separator byte, for example), so harnesses built with different Rust
+/// versions could in principle disagree on equal values.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Digest `value` as 16 lowercase hex digits.
+pub(crate) fn digest<T: Hash + ?Sized>(value: &T) -> String {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    value.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_is_stable() {
+        // The empty byte string hashes to the FNV-1a offset basis.
+        let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+        hasher.write(b"");
+        assert_eq!(hasher.finish(), 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        // Lengths hash as 8 little-endian bytes on every target.
+        let mut lengths = Fnv1a(0xcbf2_9ce4_8422_2325);
+        lengths.write_usize(3);
+        let mut bytes = Fnv1a(0xcbf2_9ce4_8422_2325);
+        bytes.write(&[3, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(lengths.finish(), bytes.finish());
+
+        assert_eq!(digest(&42u64), digest(&42u64));
+        assert_ne!(digest(&42u64), digest(&43u64));
+        assert_eq!(digest("abc").len(), 16);
+    }
+}
@@ -2,13 +2,26 @@
 //!
 //! This replaces criterion's default harness with an HTTP-controlled one.
 //! The harness exposes benchmark functions via HTTP endpoints, allowing
-//! external orchestration of benchmark execution.
+//! external orchestration of benchmark execution. Harnesses spawned by the
+//! orchestrator can instead speak the same protocol over stdin/stdout.
 
+mod alloc;
+mod digest;
+mod perf;
+mod pipe;
+mod rusage;
 mod server;
 
-pub use server::{run_harness, run_harness_async};
+pub use alloc::CountingAllocator;
+pub use hypobench_core::protocol::HarnessInfo;
+pub use server::{
+    run_harness, run_harness_async, run_harness_with_config, run_harness_with_config_async,
+    HarnessConfig,
+};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// A benchmark function that runs `n` inner iterations and returns total elapsed.
@@ -22,12 +35,65 @@ use std::time::Duration;
 /// ~20–50 ns) and gives meaningful variance estimates for fast functions.
 pub type BenchmarkFn = Box<dyn Fn(u64) -> Duration + Send + Sync>;
 
+/// Runs a benchmark once and digests its output, for `/verify`.
+type DigestFn = Box<dyn Fn() -> String + Send + Sync>;
+
+/// Build provenance of the running harness binary, without a crate identity.
+///
+/// The toolchain, target and profile come from this crate's build script. The
+/// git SHA and feature list are read from `HYPOBENCH_GIT_SHA` and
+/// `HYPOBENCH_FEATURES` at compile time, which hypobench sets when it builds a
+/// benchmark. Use [`harness_info!`] to also record the benchmark crate.
+pub fn build_info() -> HarnessInfo {
+    HarnessInfo {
+        crate_name: None,
+        crate_version: None,
+        git_sha: option_env!("HYPOBENCH_GIT_SHA")
+            .filter(|sha| !sha.is_empty())
+            .map(str::to_string),
+        rustc_version: env!("HYPOBENCH_BUILD_RUSTC").to_string(),
+        target: env!("HYPOBENCH_BUILD_TARGET").to_string(),
+        profile: env!("HYPOBENCH_BUILD_PROFILE").to_string(),
+        opt_level: env!("HYPOBENCH_BUILD_OPT_LEVEL").to_string(),
+        features: option_env!("HYPOBENCH_FEATURES")
+            .unwrap_or_default()
+            .split(',')
+            .filter(|feature| !feature.is_empty())
+            .map(str::to_string)
+            .collect(),
+        harness_version: env!("CARGO_PKG_VERSION").to_string(),
+        pid: std::process::id(),
+    }
+}
+
+/// Build provenance of the running harness, naming the crate the macro is
+/// expanded in.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut registry = BenchmarkRegistry::new();
+/// registry.set_info(hypobench_harness::harness_info!());
+/// ```
+#[macro_export]
+macro_rules! harness_info {
+    () => {
+        $crate::HarnessInfo {
+            crate_name: Some(env!("CARGO_PKG_NAME").to_string()),
+            crate_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            ..$crate::build_info()
+        }
+    };
+}
+
 /// Registry of discovered benchmarks.
 ///
 /// This stores all benchmark functions that have been registered with the harness.
 /// Each benchmark is identified by a unique string name.
 pub struct BenchmarkRegistry {
     benchmarks: HashMap<String, BenchmarkFn>,
+    digests: HashMap<String, DigestFn>,
+    info: HarnessInfo,
 }
 
 impl BenchmarkRegistry {
@@ -35,9 +101,21 @@ impl BenchmarkRegistry {
     pub fn new() -> Self {
         Self {
             benchmarks: HashMap::new(),
+            digests: HashMap::new(),
+            info: build_info(),
         }
     }
 
+    /// Set the provenance served by `GET /info`, usually [`harness_info!`].
+    pub fn set_info(&mut self, info: HarnessInfo) {
+        self.info = info;
+    }
+
+    /// The provenance served by `GET /info`.
+    pub fn info(&self) -> &HarnessInfo {
+        &self.info
+    }
+
     /// Register a benchmark function with the given name.
     ///
     /// The closure receives an iteration count `n` and should execute the work
@@ -59,7 +137,51 @@ impl BenchmarkRegistry {
     where
         F: Fn(u64) -> Duration + Send + Sync + 'static,
     {
-        self.benchmarks.insert(name.into(), Box::new(f));
+        let name = name.into();
+        self.digests.remove(&name);
+        self.benchmarks.insert(name, Box::new(f));
+    }
+
+    /// Register a benchmark whose closure also returns its output.
+    ///
+    /// Timed runs discard the output; `/verify` runs the closure once with
+    /// `n = 1` and digests it, so the orchestrator can check that baseline
+    /// and candidate compute the same thing before comparing their speed.
+    /// The output should be what one iteration produces.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// registry.register_with_output("count_chars", |n| {
+    ///     let start = std::time::Instant::now();
+    ///     let mut count = 0;
+    ///     for _ in 0..n {
+    ///         count = std::hint::black_box(count_chars(INPUT));
+    ///     }
+    ///     (start.elapsed(), count)
+    /// });
+    /// ```
+    pub fn register_with_output<F, O>(&mut self, name: impl Into<String>, f: F)
+    where
+        F: Fn(u64) -> (Duration, O) + Send + Sync + 'static,
+        O: Hash,
+    {
+        let name = name.into();
+        let f = Arc::new(f);
+        let timed = Arc::clone(&f);
+        self.benchmarks
+            .insert(name.clone(), Box::new(move |n| timed(n).0));
+        self.digests
+            .insert(name, Box::new(move || digest::digest(&f(1).1)));
+    }
+
+    /// Run every benchmark registered with an output once, and digest the
+    /// outputs.
+    pub fn digests(&self) -> BTreeMap<String, String> {
+        self.digests
+            .iter()
+            .map(|(name, digest)| (name.clone(), digest()))
+            .collect()
     }
 
     /// List all registered benchmark names.
@@ -151,6 +273,41 @@ mod tests {
         assert!(registry.run("missing", 1).is_none());
     }
 
+    #[test]
+    fn test_registry_digests_outputs() {
+        let mut registry = BenchmarkRegistry::new();
+        registry.register("plain", |_n| Duration::from_millis(1));
+        registry.register_with_output("sum", |n| {
+            (Duration::from_nanos(n), (1..=3u64).sum::<u64>())
+        });
+        registry.register_with_output("text", |_n| (Duration::ZERO, "abc".to_string()));
+
+        assert_eq!(registry.run("sum", 5), Some(Duration::from_nanos(5)));
+        let digests = registry.digests();
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests["sum"], digest::digest(&6u64));
+        assert_ne!(digests["sum"], digests["text"]);
+
+        // Re-registering without an output drops the digest.
+        registry.register("sum", |_n| Duration::ZERO);
+        assert!(!registry.digests().contains_key("sum"));
+    }
+
+    #[test]
+    fn test_registry_info() {
+        let mut registry = BenchmarkRegistry::new();
+        assert_eq!(registry.info().crate_name, None);
+        assert_eq!(registry.info().pid, std::process::id());
+        assert!(registry.info().rustc_version.starts_with("rustc "));
+
+        registry.set_info(harness_info!());
+        assert_eq!(
+            registry.info().crate_name.as_deref(),
+            Some("hypobench-harness")
+        );
+        assert_eq!(registry.info().harness_version, env!("CARGO_PKG_VERSION"));
+    }
+
     #[test]
     fn test_registry_default() {
         let registry = BenchmarkRegistry::default();
@@ -0,0 +1,214 @@
+//! Opt-in perf event counters (Linux only).
+//!
+//! When a run request asks for counters, the harness opens `perf_event_open`
+//! counters on the thread running the benchmark for the duration of that run.
+//! Hardware counters (instructions, cycles, branch and cache misses) are
+//! preferred. Where the CPU or hypervisor doesn't expose them, the software
+//! counters are used instead, and where perf events aren't permitted at all
+//! (`perf_event_paranoid`, seccomp) the run reports no counters. Only
+//! user-space activity is counted, which the default paranoia level allows.
+//!
+//! Like allocation counts, counters are per thread: work a benchmark hands to
+//! other threads is not counted.
+
+use std::collections::BTreeMap;
+
+/// Run `f`, counting perf events on this thread if `enabled`.
+///
+/// Returns the totals keyed by counter name, or an empty map when counting
+/// is disabled or unavailable.
+pub(crate) fn measure<T>(enabled: bool, f: impl FnOnce() -> T) -> (T, BTreeMap<String, u64>) {
+    if !enabled {
+        return (f(), BTreeMap::new());
+    }
+    sys::measure(f)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::collections::BTreeMap;
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::FromRawFd;
+    use std::sync::OnceLock;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_TYPE_SOFTWARE: u32 = 1;
+    /// `PERF_FORMAT_TOTAL_TIME_ENABLED | PERF_FORMAT_TOTAL_TIME_RUNNING`, so
+    /// multiplexed counters can be scaled up.
+    const READ_FORMAT: u64 = 1 | 2;
+    /// `exclude_kernel | exclude_hv` in the attribute bitfield.
+    const EXCLUDE_KERNEL_AND_HV: u64 = (1 << 5) | (1 << 6);
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 8;
+
+    struct Event {
+        name: &'static str,
+        kind: u32,
+        config: u64,
+    }
+
+    const HARDWARE: &[Event] = &[
+        Event {
+            name: "instructions",
+            kind: PERF_TYPE_HARDWARE,
+            config: 1,
+        },
+        Event {
+            name: "cycles",
+            kind: PERF_TYPE_HARDWARE,
+            config: 0,
+        },
+        Event {
+            name: "branch_misses",
+            kind: PERF_TYPE_HARDWARE,
+            config: 5,
+        },
+        Event {
+            name: "cache_misses",
+            kind: PERF_TYPE_HARDWARE,
+            config: 3,
+        },
+    ];
+
+    const SOFTWARE: &[Event] = &[
+        Event {
+            name: "task_clock_ns",
+            kind: PERF_TYPE_SOFTWARE,
+            config: 1,
+        },
+        Event {
+            name: "page_faults",
+            kind: PERF_TYPE_SOFTWARE,
+            config: 2,
+        },
+        Event {
+            name: "context_switches",
+            kind: PERF_TYPE_SOFTWARE,
+            config: 3,
+        },
+    ];
+
+    /// `struct perf_event_attr` as of its first version
+    /// (`PERF_ATTR_SIZE_VER0`), which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// Open a counter on the calling thread, running from now on.
+    fn open(event: &Event) -> Option<File> {
+        let attr = PerfEventAttr {
+            kind: event.kind,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config: event.config,
+            read_format: READ_FORMAT,
+            flags: EXCLUDE_KERNEL_AND_HV,
+            ..Default::default()
+        };
+        // SAFETY: `attr` is a valid VER0 attribute that outlives the call.
+        // pid 0 with cpu -1 counts the calling thread on any CPU.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0 as libc::pid_t,
+                -1 as libc::c_int,
+                -1 as libc::c_int,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return None;
+        }
+        // SAFETY: the kernel just returned this descriptor; nothing else owns it.
+        Some(unsafe { File::from_raw_fd(fd as libc::c_int) })
+    }
+
+    /// Read a counter, scaled up if the kernel multiplexed it.
+    fn read(mut file: &File) -> Option<u64> {
+        let mut buf = [0u8; 24];
+        file.read_exact(&mut buf).ok()?;
+        let word = |i: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&buf[i * 8..i * 8 + 8]);
+            u64::from_ne_bytes(bytes)
+        };
+        let (value, enabled, running) = (word(0), word(1), word(2));
+        if running == 0 {
+            return None;
+        }
+        if running >= enabled {
+            return Some(value);
+        }
+        Some((value as u128 * enabled as u128 / running as u128) as u64)
+    }
+
+    /// The events this process can open, probed on first use: the hardware
+    /// counters if any open, otherwise the software ones.
+    fn available() -> &'static [&'static Event] {
+        static AVAILABLE: OnceLock<Vec<&'static Event>> = OnceLock::new();
+        AVAILABLE.get_or_init(|| {
+            let hardware: Vec<_> = HARDWARE.iter().filter(|e| open(e).is_some()).collect();
+            if !hardware.is_empty() {
+                return hardware;
+            }
+            SOFTWARE.iter().filter(|e| open(e).is_some()).collect()
+        })
+    }
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, BTreeMap<String, u64>) {
+        let counters: Vec<_> = available()
+            .iter()
+            .filter_map(|event| Some((event.name, open(event)?)))
+            .collect();
+        let result = f();
+        let totals = counters
+            .iter()
+            .filter_map(|(name, file)| Some((name.to_string(), read(file)?)))
+            .collect();
+        (result, totals)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::collections::BTreeMap;
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, BTreeMap<String, u64>) {
+        (f(), BTreeMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_disabled_reports_nothing() {
+        let (value, counters) = measure(false, || 42);
+        assert_eq!(value, 42);
+        assert!(counters.is_empty());
+    }
+
+    #[test]
+    fn test_measure_counts_instructions_when_available() {
+        let (_, counters) = measure(true, || {
+            (0..100_000u64).map(std::hint::black_box).sum::<u64>()
+        });
+        // Perf events are often unavailable in CI containers; then the run
+        // just reports no counters.
+        if let Some(&instructions) = counters.get("instructions") {
+            assert!(instructions >= 100_000, "{counters:?}");
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! Pipe transport for the benchmark harness.
+//!
+//! Instead of listening on a TCP port, a harness spawned with
+//! `HYPOBENCH_TRANSPORT=pipe` reads one [`PipeRequest`] per line from stdin
+//! and writes one [`PipeResponse`] per line to stdout. Each request is routed
+//! through the same axum router that serves HTTP, so claims, leases and error
+//! responses behave identically on both transports.
+
+use anyhow::Context;
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::CONTENT_TYPE, Method},
+    Router,
+};
+use hypobench_core::protocol::{PipeRequest, PipeResponse, CLAIM_HEADER};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+
+/// Serve protocol requests from `reader` until it closes or `/shutdown` is
+/// requested, writing responses to `writer`.
+///
+/// Requests are handled concurrently, as they would be over HTTP, so a
+/// heartbeat isn't stuck behind a long `/run`; responses carry the request's
+/// `id` and may be written out of order.
+pub(crate) async fn serve_pipe<R, W>(
+    app: Router,
+    reader: R,
+    mut writer: W,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> anyhow::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (response_tx, mut response_rx) = mpsc::unbounded_channel::<PipeResponse>();
+
+    // A single writer keeps each response on its own line. Benchmarks may
+    // print to stdout too; the orchestrator skips lines it can't parse, and
+    // finds a response after output that lacks a trailing newline.
+    let writer_task = tokio::spawn(async move {
+        while let Some(response) = response_rx.recv().await {
+            // Send any output the benchmark left in std's buffer first, so it
+            // can't land in the middle of the response.
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            let mut line = serde_json::to_vec(&response)?;
+            line.push(b'\n');
+            writer.write_all(&line).await?;
+            writer.flush().await?;
+        }
+        anyhow::Ok(())
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    let mut in_flight = JoinSet::new();
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line.context("Failed to read pipe request")?,
+            _ = shutdown_rx.wait_for(|requested| *requested) => break,
+        };
+        // EOF: the orchestrator is gone, so nobody is left to serve.
+        let Some(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request: PipeRequest = match serde_json::from_str(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                eprintln!("[harness] Ignoring malformed pipe request: {}", e);
+                continue;
+            }
+        };
+        let app = app.clone();
+        let response_tx = response_tx.clone();
+        in_flight.spawn(async move {
+            let _ = response_tx.send(dispatch(app, request).await);
+        });
+    }
+
+    // Let in-flight requests (including the shutdown itself) answer first.
+    while in_flight.join_next().await.is_some() {}
+    drop(response_tx);
+    writer_task
+        .await
+        .context("Pipe writer panicked")?
+        .context("Failed to write pipe response")?;
+
+    eprintln!("Shutting down benchmark harness");
+    Ok(())
+}
+
+/// Run one pipe request through the HTTP router and capture its response.
+async fn dispatch(app: Router, request: PipeRequest) -> PipeResponse {
+    let id = request.id;
+    let endpoint = request.endpoint;
+
+    let mut builder = Request::builder()
+        .method(if endpoint.is_post() {
+            Method::POST
+        } else {
+            Method::GET
+        })
+        .uri(endpoint.path());
+    if let Some(claim) = &request.claim {
+        builder = builder.header(CLAIM_HEADER, claim);
+    }
+    let body = if request.body.is_null() {
+        Body::empty()
+    } else {
+        builder = builder.header(CONTENT_TYPE, "application/json");
+        Body::from(request.body.to_string())
+    };
+    let http_request = match builder.body(body) {
+        Ok(http_request) => http_request,
+        Err(e) => {
+            return PipeResponse {
+                id,
+                status: 400,
+                body: serde_json::json!({ "error": e.to_string() }),
+            }
+        }
+    };
+
+    let response = match app.oneshot(http_request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+    let status = response.status().as_u16();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap_or_default();
+    // Handlers answer in JSON, but axum's own rejections (e.g. a malformed
+    // body) are plain text; wrap those so the orchestrator sees an `error`.
+    let body = match serde_json::from_slice(&bytes) {
+        Ok(body) => body,
+        Err(_) if bytes.is_empty() => serde_json::Value::Null,
+        Err(_) => serde_json::json!({ "error": String::from_utf8_lossy(&bytes) }),
+    };
+
+    PipeResponse { id, status, body }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::build_app;
+    use crate::BenchmarkRegistry;
+    use hypobench_core::protocol::{
+        ClaimRequest, Endpoint, HealthResponse, RunIterationRequest, RunIterationResponse,
+    };
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, Lines};
+    use tokio::task::JoinHandle;
+
+    struct PipePeer {
+        stdin: DuplexStream,
+        stdout: Lines<BufReader<DuplexStream>>,
+        server: JoinHandle<anyhow::Result<()>>,
+    }
+
+    impl PipePeer {
+        fn start() -> Self {
+            let mut registry = BenchmarkRegistry::new();
+            registry.register("test_bench", |n| Duration::from_nanos(n * 10));
+            let (app, shutdown_rx) = build_app(registry, None);
+
+            let (stdin, server_stdin) = tokio::io::duplex(64 * 1024);
+            let (server_stdout, stdout) = tokio::io::duplex(64 * 1024);
+            let server = tokio::spawn(serve_pipe(app, server_stdin, server_stdout, shutdown_rx));
+            Self {
+                stdin,
+                stdout: BufReader::new(stdout).lines(),
+                server,
+            }
+        }
+
+        async fn send(&mut self, request: &PipeRequest) {
+            let mut line = serde_json::to_vec(request).unwrap();
+            line.push(b'\n');
+            self.stdin.write_all(&line).await.unwrap();
+        }
+
+        async fn recv(&mut self) -> PipeResponse {
+            let line = self.stdout.next_line().await.unwrap().unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        async fn call(
+            &mut self,
+            id: u64,
+            endpoint: Endpoint,
+            claim: Option<&str>,
+            body: serde_json::Value,
+        ) -> PipeResponse {
+            self.send(&PipeRequest {
+                id,
+                endpoint,
+                claim: claim.map(str::to_string),
+                body,
+            })
+            .await;
+            let response = self.recv().await;
+            assert_eq!(response.id, id);
+            response
+        }
+    }
+
+    fn json<T: serde::Serialize>(value: &T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pipe_health_and_run() {
+        let mut peer = PipePeer::start();
+
+        let response = peer
+            .call(1, Endpoint::Health, None, serde_json::Value::Null)
+            .await;
+        assert_eq!(response.status, 200);
+        let health: HealthResponse = serde_json::from_value(response.body).unwrap();
+        assert_eq!(health.status, "healthy");
+
+        let response = peer
+            .call(
+                2,
+                Endpoint::Run,
+                None,
+                json(&RunIterationRequest::new("test_bench", 5)),
+            )
+            .await;
+        assert_eq!(response.status, 200);
+        let run: RunIterationResponse = serde_json::from_value(response.body).unwrap();
+        assert_eq!(run.duration(), Duration::from_nanos(50));
+    }
+
+    #[tokio::test]
+    async fn test_pipe_enforces_claim() {
+        let mut peer = PipePeer::start();
+
+        let response = peer
+            .call(1, Endpoint::Claim, None, json(&ClaimRequest::new("nonce")))
+            .await;
+        assert_eq!(response.status, 200);
+
+        let run = json(&RunIterationRequest::new("test_bench", 1));
+        let response = peer.call(2, Endpoint::Run, None, run.clone()).await;
+        assert_eq!(response.status, 403);
+        assert!(response.body["error"].is_string());
+
+        let response = peer.call(3, Endpoint::Run, Some("nonce"), run).await;
+        assert_eq!(response.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_pipe_wraps_plain_text_rejections() {
+        let mut peer = PipePeer::start();
+
+        let response = peer
+            .call(1, Endpoint::Run, None, serde_json::json!({ "nope": true }))
+            .await;
+        assert_eq!(response.status, 422);
+        assert!(response.body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_pipe_skips_malformed_lines() {
+        let mut peer = PipePeer::start();
+
+        peer.stdin.write_all(b"not json\n\n").await.unwrap();
+        let response = peer
+            .call(9, Endpoint::Health, None, serde_json::Value::Null)
+            .await;
+        assert_eq!(response.status, 200);
+    }
+
+    #[tokio::test]
+    async fn test_pipe_shutdown_answers_then_exits() {
+        let mut peer = PipePeer::start();
+
+        let response = peer
+            .call(1, Endpoint::Shutdown, None, serde_json::Value::Null)
+            .await;
+        assert_eq!(response.status, 200);
+        peer.server.await.unwrap().unwrap();
+        assert!(peer.stdout.next_line().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipe_exits_when_stdin_closes() {
+        let peer = PipePeer::start();
+
+        drop(peer.stdin);
+        peer.server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+//! Per-run resource usage of the benchmark thread.
+//!
+//! The harness reads `getrusage(RUSAGE_THREAD)` before and after each run and
+//! reports the difference, so the orchestrator can tell CPU time from wall
+//! time and spot samples that were preempted. `RUSAGE_THREAD` is Linux-only;
+//! elsewhere no usage is reported.
+
+use hypobench_core::protocol::ResourceUsage;
+
+/// Run `f` and measure the resources this thread used meanwhile.
+pub(crate) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<ResourceUsage>) {
+    sys::measure(f)
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use hypobench_core::protocol::ResourceUsage;
+
+    fn thread_usage() -> Option<libc::rusage> {
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+        // SAFETY: `usage` is a valid out-pointer; it's only read on success,
+        // when the kernel has filled it in.
+        let rc = unsafe { libc::getrusage(libc::RUSAGE_THREAD, usage.as_mut_ptr()) };
+        (rc == 0).then(|| unsafe { usage.assume_init() })
+    }
+
+    fn timeval_ns(tv: libc::timeval) -> u64 {
+        tv.tv_sec as u64 * 1_000_000_000 + tv.tv_usec as u64 * 1_000
+    }
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<ResourceUsage>) {
+        let before = thread_usage();
+        let result = f();
+        let usage = before.zip(thread_usage()).map(|(before, after)| {
+            let delta = |after: libc::c_long, before: libc::c_long| (after - before).max(0) as u64;
+            ResourceUsage {
+                user_time_ns: timeval_ns(after.ru_utime)
+                    .saturating_sub(timeval_ns(before.ru_utime)),
+                system_time_ns: timeval_ns(after.ru_stime)
+                    .saturating_sub(timeval_ns(before.ru_stime)),
+                minor_faults: delta(after.ru_minflt, before.ru_minflt),
+                major_faults: delta(after.ru_majflt, before.ru_majflt),
+                voluntary_context_switches: delta(after.ru_nvcsw, before.ru_nvcsw),
+                involuntary_context_switches: delta(after.ru_nivcsw, before.ru_nivcsw),
+            }
+        });
+        (result, usage)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use hypobench_core::protocol::ResourceUsage;
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<ResourceUsage>) {
+        (f(), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_measure_reports_cpu_time() {
+        let (_, usage) = measure(|| {
+            let start = std::time::Instant::now();
+            while start.elapsed() < std::time::Duration::from_millis(20) {
+                std::hint::black_box(0);
+            }
+        });
+        let usage = usage.expect("getrusage works on Linux");
+        // A 20 ms busy loop is mostly user time, at the kernel's tick granularity.
+        assert!(usage.user_time_ns + usage.system_time_ns > 0, "{usage:?}");
+    }
+}
@@ -6,19 +6,34 @@
 //!
 //! The harness supports exclusive claiming via nonce to prevent multiple
 //! orchestrators from accidentally using the same harness simultaneously.
+//! Claims may carry a lease TTL; the holder extends it via `/heartbeat`, and
+//! a lapsed lease is dropped so a crashed orchestrator can't wedge the harness.
+//!
+//! The server binds to loopback unless told otherwise, and can require a
+//! shared-secret bearer token on every request (see [`HarnessConfig`]).
+//!
+//! When spawned with [`Transport::Pipe`], the same router is driven by
+//! newline-delimited JSON on stdin/stdout instead (see [`crate::pipe`]).
 
+use std::net::{IpAddr, Ipv4Addr};
 use std::sync::Arc;
+use std::time::Instant;
 
+use anyhow::Context;
 use axum::{
-    extract::State,
-    http::{HeaderMap, StatusCode},
-    response::IntoResponse,
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
 use hypobench_core::protocol::{
-    BenchmarkListResponse, ClaimRequest, ClaimResponse, HealthResponse, ReleaseRequest,
-    ReleaseResponse, RunIterationRequest, RunIterationResponse, ShutdownResponse, CLAIM_HEADER,
+    listen_announcement, BenchmarkListResponse, ClaimLease, ClaimRequest, ClaimResponse,
+    HarnessInfo, HealthResponse, HeartbeatRequest, HeartbeatResponse, ReleaseRequest,
+    ReleaseResponse, RunBatchRequest, RunBatchResponse, RunIterationRequest, RunIterationResponse,
+    ShutdownResponse, Transport, VerifyResponse, AUTH_TOKEN_ENV, BIND_ADDR_ENV, CLAIM_HEADER,
+    TRANSPORT_ENV,
 };
 use tokio::sync::{watch, Mutex};
 
@@ -29,16 +44,93 @@ use std::sync::atomic::{AtomicU64, Ordering};
 /// How often to log iteration stats (every N iterations).
 const LOG_INTERVAL: u64 = 100;
 
+/// Network settings for the harness server.
+#[derive(Debug, Clone)]
+pub struct HarnessConfig {
+    /// Port to listen on (0 picks a free port).
+    pub port: u16,
+    /// Address to bind to. Defaults to loopback so a harness on a developer
+    /// machine isn't reachable from the network.
+    pub bind_addr: IpAddr,
+    /// Shared secret every request must present as `Authorization: Bearer <token>`.
+    /// `None` disables authentication.
+    pub auth_token: Option<String>,
+    /// Serve over HTTP, or over stdin/stdout when spawned by an orchestrator.
+    /// `port`, `bind_addr` and `auth_token` only apply to HTTP.
+    pub transport: Transport,
+}
+
+impl HarnessConfig {
+    /// Listen on `port` on loopback, without authentication.
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            bind_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            auth_token: None,
+            transport: Transport::Http,
+        }
+    }
+
+    /// Listen on `port`, reading the bind address from `HYPOBENCH_BIND_ADDR`,
+    /// the bearer token from `HYPOBENCH_AUTH_TOKEN` and the transport from
+    /// `HYPOBENCH_TRANSPORT`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `HYPOBENCH_BIND_ADDR` is set but isn't an IP address,
+    /// or `HYPOBENCH_TRANSPORT` names an unknown transport.
+    pub fn from_env(port: u16) -> anyhow::Result<Self> {
+        let mut config = Self::new(port);
+        if let Ok(addr) = std::env::var(BIND_ADDR_ENV) {
+            config.bind_addr = addr.parse().with_context(|| {
+                format!("{} must be an IP address, got {:?}", BIND_ADDR_ENV, addr)
+            })?;
+        }
+        config.auth_token = std::env::var(AUTH_TOKEN_ENV)
+            .ok()
+            .filter(|token| !token.is_empty());
+        if let Ok(transport) = std::env::var(TRANSPORT_ENV) {
+            config.transport = Transport::parse(&transport).with_context(|| {
+                format!(
+                    "{} must be \"http\" or \"pipe\", got {:?}",
+                    TRANSPORT_ENV, transport
+                )
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Bind to `addr` instead of loopback.
+    pub fn with_bind_addr(mut self, addr: IpAddr) -> Self {
+        self.bind_addr = addr;
+        self
+    }
+
+    /// Require `token` as a bearer token on every request.
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Serve over `transport` instead of HTTP.
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+}
+
 /// Shared state for the HTTP server.
 struct AppState {
     /// The benchmark registry containing all registered benchmarks.
     registry: Arc<BenchmarkRegistry>,
     /// Sender to signal shutdown.
     shutdown_tx: watch::Sender<bool>,
-    /// Current claim nonce (None if unclaimed).
-    claim: Mutex<Option<String>>,
+    /// Current claim lease (None if unclaimed).
+    claim: Mutex<Option<ClaimLease>>,
     /// Total iterations run since startup.
     iteration_count: AtomicU64,
+    /// Bearer token required on every request (None disables auth).
+    auth_token: Option<String>,
 }
 
 /// Health check endpoint.
@@ -49,6 +141,14 @@ async fn health() -> Json<HealthResponse> {
     Json(HealthResponse::healthy())
 }
 
+/// Report how the harness binary was built.
+///
+/// GET /info
+/// Returns: { "crate_name": "...", "git_sha": "...", "rustc_version": "...", "pid": ..., ... }
+async fn info(State(state): State<Arc<AppState>>) -> Json<HarnessInfo> {
+    Json(state.registry.info().clone())
+}
+
 /// List all available benchmarks.
 ///
 /// GET /benchmarks
@@ -82,43 +182,96 @@ async fn run_iteration(
         return response;
     }
 
+    let (status, response) = run_one(&state, &request);
+    (status, Json(response)).into_response()
+}
+
+/// Run several samples back to back.
+///
+/// POST /run_batch
+/// Body: { "runs": [{ "benchmark_id": "...", "iterations": N }, ...] }
+/// Returns: { "results": [{ "duration_ns": ..., "success": true/false, ... }, ...] }
+///
+/// Failed entries are reported in place and don't stop the rest of the batch.
+async fn run_batch(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<RunBatchRequest>,
+) -> impl IntoResponse {
+    // Check claim if harness is claimed
+    if let Err(response) = check_claim(&state, &headers).await {
+        return response;
+    }
+
+    let results = request
+        .runs
+        .iter()
+        .map(|run| run_one(&state, run).1)
+        .collect();
+    (StatusCode::OK, Json(RunBatchResponse::new(results))).into_response()
+}
+
+/// Run one sample, shared by `/run` and `/run_batch`.
+fn run_one(state: &AppState, request: &RunIterationRequest) -> (StatusCode, RunIterationResponse) {
     if request.iterations == 0 {
         return (
             StatusCode::BAD_REQUEST,
-            Json(RunIterationResponse::failure("iterations must be >= 1")),
-        )
-            .into_response();
+            RunIterationResponse::failure("iterations must be >= 1"),
+        );
     }
 
-    match state
-        .registry
-        .run(&request.benchmark_id, request.iterations)
-    {
+    let (((duration, allocations), rusage), counters) =
+        crate::perf::measure(request.perf_counters, || {
+            crate::rusage::measure(|| {
+                crate::alloc::measure(|| {
+                    state
+                        .registry
+                        .run(&request.benchmark_id, request.iterations)
+                })
+            })
+        });
+    match duration {
         Some(duration) => {
             let count = state.iteration_count.fetch_add(1, Ordering::Relaxed) + 1;
-            if count % LOG_INTERVAL == 0 {
+            if count.is_multiple_of(LOG_INTERVAL) {
                 eprintln!("[harness] {} run calls completed", count);
             }
             (
                 StatusCode::OK,
-                Json(RunIterationResponse::success(request.iterations, duration)),
+                RunIterationResponse::success(request.iterations, duration)
+                    .with_allocations(allocations)
+                    .with_rusage(rusage)
+                    .with_counters(counters),
             )
-                .into_response()
         }
         None => {
             eprintln!("[harness] Benchmark '{}' not found", request.benchmark_id);
             (
                 StatusCode::NOT_FOUND,
-                Json(RunIterationResponse::failure(format!(
+                RunIterationResponse::failure(format!(
                     "Benchmark '{}' not found",
                     request.benchmark_id
-                ))),
+                )),
             )
-                .into_response()
         }
     }
 }
 
+/// Run each benchmark registered with an output once and digest the outputs.
+///
+/// POST /verify
+/// Returns: { "digests": { "bench1": "<16 hex digits>", ... } }
+async fn verify(State(state): State<Arc<AppState>>, headers: HeaderMap) -> impl IntoResponse {
+    // Check claim if harness is claimed
+    if let Err(response) = check_claim(&state, &headers).await {
+        return response;
+    }
+
+    let digests = state.registry.digests();
+    eprintln!("[harness] Verified {} benchmark output(s)", digests.len());
+    (StatusCode::OK, Json(VerifyResponse::new(digests))).into_response()
+}
+
 /// Trigger graceful shutdown of the server.
 ///
 /// POST /shutdown
@@ -137,33 +290,83 @@ async fn shutdown(State(state): State<Arc<AppState>>, headers: HeaderMap) -> imp
 /// Claim exclusive access to the harness.
 ///
 /// POST /claim
-/// Body: { "nonce": "unique-session-id" }
-/// Returns: { "success": true/false, "error": "..." }
+/// Body: { "nonce": "unique-session-id", "ttl_ms": 30000, "force": false }
+/// Returns: { "success": true/false, "expires_in_ms": ..., "error": "..." }
+///
+/// Re-claiming with the held nonce starts a fresh lease with the new TTL.
+/// A `ttl_ms` of 0 is rejected with 400.
 async fn claim(
     State(state): State<Arc<AppState>>,
     Json(request): Json<ClaimRequest>,
 ) -> impl IntoResponse {
+    if request.ttl_ms == Some(0) {
+        // A zero lease would expire the moment it was granted.
+        eprintln!("[harness] Claim rejected - ttl_ms must be positive");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ClaimResponse::invalid("ttl_ms must be positive")),
+        );
+    }
+    let now = Instant::now();
     let mut claim = state.claim.lock().await;
+    drop_expired_claim(&mut claim, now);
 
-    match &*claim {
-        Some(existing) if existing != &request.nonce => {
+    match &mut *claim {
+        Some(existing) if !existing.is_held_by(&request.nonce) && !request.force => {
             // Already claimed by someone else
             eprintln!("[harness] Claim rejected - already claimed by another orchestrator");
             (StatusCode::CONFLICT, Json(ClaimResponse::already_claimed()))
         }
-        Some(_) => {
-            // Already claimed by us (idempotent)
+        Some(existing) if existing.is_held_by(&request.nonce) => {
+            // Already claimed by us (idempotent); the new TTL applies
             eprintln!("[harness] Claim refreshed (same nonce)");
-            (StatusCode::OK, Json(ClaimResponse::success()))
+            *existing = ClaimLease::new(request.nonce.clone(), request.ttl(), now);
+            (
+                StatusCode::OK,
+                Json(ClaimResponse::success(existing.remaining(now))),
+            )
         }
-        None => {
-            // Claim it
+        previous => {
+            if previous.is_some() {
+                eprintln!("[harness] Claim forcibly taken over from another orchestrator");
+            }
             eprintln!(
                 "[harness] Claimed by orchestrator (nonce: {}...)",
                 &request.nonce[..8.min(request.nonce.len())]
             );
-            *claim = Some(request.nonce);
-            (StatusCode::OK, Json(ClaimResponse::success()))
+            let lease = ClaimLease::new(request.nonce.clone(), request.ttl(), now);
+            let remaining = lease.remaining(now);
+            *previous = Some(lease);
+            (StatusCode::OK, Json(ClaimResponse::success(remaining)))
+        }
+    }
+}
+
+/// Extend the lease on a claim.
+///
+/// POST /heartbeat
+/// Body: { "nonce": "unique-session-id" }
+/// Returns: { "success": true/false, "expires_in_ms": ..., "error": "..." }
+async fn heartbeat(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<HeartbeatRequest>,
+) -> impl IntoResponse {
+    let now = Instant::now();
+    let mut claim = state.claim.lock().await;
+    drop_expired_claim(&mut claim, now);
+
+    match &mut *claim {
+        Some(existing) if existing.is_held_by(&request.nonce) => {
+            existing.renew(now);
+            (
+                StatusCode::OK,
+                Json(HeartbeatResponse::success(existing.remaining(now))),
+            )
+        }
+        _ => {
+            // Lease lapsed or was taken over; the holder must re-claim.
+            eprintln!("[harness] Heartbeat rejected - claim no longer held by this nonce");
+            (StatusCode::CONFLICT, Json(HeartbeatResponse::lost()))
         }
     }
 }
@@ -180,7 +383,7 @@ async fn release(
     let mut claim = state.claim.lock().await;
 
     match &*claim {
-        Some(existing) if existing == &request.nonce => {
+        Some(existing) if existing.is_held_by(&request.nonce) => {
             // Release the claim
             eprintln!("[harness] Released by orchestrator");
             *claim = None;
@@ -197,19 +400,67 @@ async fn release(
     }
 }
 
+/// Clear the claim if its lease has lapsed.
+fn drop_expired_claim(claim: &mut Option<ClaimLease>, now: Instant) {
+    if claim.as_ref().is_some_and(|lease| lease.is_expired(now)) {
+        eprintln!("[harness] Claim lease expired without heartbeat; harness is unclaimed");
+        *claim = None;
+    }
+}
+
+/// Reject requests without the configured bearer token.
+///
+/// Applied to every route, including `/health` and `/shutdown`: anyone who can
+/// reach an unauthenticated harness can run arbitrary benchmarks or stop it.
+async fn require_auth(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(expected) = &state.auth_token {
+        let provided = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if !provided.is_some_and(|token| tokens_match(token, expected)) {
+            eprintln!("[harness] Rejected request with missing or invalid bearer token");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(serde_json::json!({
+                    "error": "Missing or invalid bearer token"
+                })),
+            )
+                .into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Compare tokens without short-circuiting on the first mismatched byte.
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
 /// Check if the request has a valid claim header (if harness is claimed).
 async fn check_claim(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<(), axum::response::Response> {
-    let claim = state.claim.lock().await;
+    let mut claim = state.claim.lock().await;
+    drop_expired_claim(&mut claim, Instant::now());
 
-    if let Some(expected_nonce) = &*claim {
+    if let Some(lease) = &*claim {
         // Harness is claimed, check the header
         match headers.get(CLAIM_HEADER) {
             Some(value) => {
                 let provided = value.to_str().unwrap_or("");
-                if provided != expected_nonce {
+                if !lease.is_held_by(provided) {
                     return Err((
                         StatusCode::FORBIDDEN,
                         Json(serde_json::json!({
@@ -238,23 +489,53 @@ async fn check_claim(
 fn build_router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/health", get(health))
+        .route("/info", get(info))
         .route("/benchmarks", get(list_benchmarks))
         .route("/run", post(run_iteration))
+        .route("/run_batch", post(run_batch))
+        .route("/verify", post(verify))
         .route("/shutdown", post(shutdown))
         .route("/claim", post(claim))
+        .route("/heartbeat", post(heartbeat))
         .route("/release", post(release))
+        .layer(middleware::from_fn_with_state(state.clone(), require_auth))
         .with_state(state)
 }
 
+/// Build a fresh harness router over `registry`.
+///
+/// Returns the router together with a receiver that flips to `true` once
+/// `/shutdown` is requested.
+pub(crate) fn build_app(
+    registry: BenchmarkRegistry,
+    auth_token: Option<String>,
+) -> (Router, watch::Receiver<bool>) {
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let state = Arc::new(AppState {
+        registry: Arc::new(registry),
+        shutdown_tx,
+        claim: Mutex::new(None),
+        iteration_count: AtomicU64::new(0),
+        auth_token,
+    });
+    (build_router(state), shutdown_rx)
+}
+
 /// Run the harness HTTP server.
 ///
 /// This function starts an HTTP server on the specified port and blocks
 /// until shutdown is requested via the `/shutdown` endpoint.
 ///
+/// The bind address and bearer token are read from the environment; see
+/// [`HarnessConfig::from_env`]. Once bound, the server prints a
+/// [`listen_announcement`] line to stdout so an orchestrator that passed
+/// port 0 can discover the assigned port. An orchestrator that spawned the
+/// harness with `HYPOBENCH_TRANSPORT=pipe` gets stdin/stdout instead of a port.
+///
 /// # Arguments
 ///
 /// * `registry` - The benchmark registry containing all benchmarks to expose
-/// * `port` - The port to listen on (binds to 0.0.0.0)
+/// * `port` - The port to listen on (0 picks a free port)
 ///
 /// # Errors
 ///
@@ -276,10 +557,21 @@ fn build_router(state: Arc<AppState>) -> Router {
 /// run_harness(registry, 8080).unwrap();
 /// ```
 pub fn run_harness(registry: BenchmarkRegistry, port: u16) -> anyhow::Result<()> {
+    run_harness_with_config(registry, HarnessConfig::from_env(port)?)
+}
+
+/// Run the harness HTTP server with explicit network settings.
+///
+/// Like [`run_harness`], but ignores the `HYPOBENCH_BIND_ADDR` and
+/// `HYPOBENCH_AUTH_TOKEN` environment variables.
+pub fn run_harness_with_config(
+    registry: BenchmarkRegistry,
+    config: HarnessConfig,
+) -> anyhow::Result<()> {
     // Create a tokio runtime for the async server
     let runtime = tokio::runtime::Runtime::new()?;
 
-    runtime.block_on(async { run_harness_async(registry, port).await })
+    runtime.block_on(async { run_harness_with_config_async(registry, config).await })
 }
 
 /// Async implementation of the harness server.
@@ -287,25 +579,39 @@ pub fn run_harness(registry: BenchmarkRegistry, port: u16) -> anyhow::Result<()>
 /// Use this when you're already in a tokio runtime (e.g., in async tests).
 /// For standalone use, prefer `run_harness` which creates its own runtime.
 pub async fn run_harness_async(registry: BenchmarkRegistry, port: u16) -> anyhow::Result<()> {
-    // Create shutdown channel
-    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+    run_harness_with_config_async(registry, HarnessConfig::from_env(port)?).await
+}
 
-    // Create shared state
-    let state = Arc::new(AppState {
-        registry: Arc::new(registry),
-        shutdown_tx,
-        claim: Mutex::new(None),
-        iteration_count: AtomicU64::new(0),
-    });
+/// Async variant of [`run_harness_with_config`].
+pub async fn run_harness_with_config_async(
+    registry: BenchmarkRegistry,
+    config: HarnessConfig,
+) -> anyhow::Result<()> {
+    if config.transport == Transport::Pipe {
+        // Only the process that spawned us holds the pipe, so there is
+        // nobody to authenticate.
+        let (app, shutdown_rx) = build_app(registry, None);
+        eprintln!("Benchmark harness serving requests on stdin/stdout");
+        return crate::pipe::serve_pipe(app, tokio::io::stdin(), tokio::io::stdout(), shutdown_rx)
+            .await;
+    }
+
+    if config.auth_token.is_none() && !config.bind_addr.is_loopback() {
+        eprintln!(
+            "Warning: harness is reachable on {} without authentication; set {} to require a token",
+            config.bind_addr, AUTH_TOKEN_ENV
+        );
+    }
 
-    // Build the router
-    let app = build_router(state);
+    let (app, mut shutdown_rx) = build_app(registry, config.auth_token);
 
     // Create the listener
-    let addr = format!("0.0.0.0:{}", port);
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    let listener = tokio::net::TcpListener::bind((config.bind_addr, config.port)).await?;
+    let local_addr = listener.local_addr()?;
 
-    eprintln!("Benchmark harness listening on {}", addr);
+    eprintln!("Benchmark harness listening on {}", local_addr);
+    // Machine-readable handshake for orchestrators that asked for port 0.
+    println!("{}", listen_announcement(local_addr));
 
     // Run the server with graceful shutdown
     axum::serve(listener, app)
@@ -332,10 +638,15 @@ mod tests {
     use tower::ServiceExt;
 
     fn create_test_state() -> Arc<AppState> {
+        create_test_state_with_auth(None)
+    }
+
+    fn create_test_state_with_auth(auth_token: Option<&str>) -> Arc<AppState> {
         let mut registry = BenchmarkRegistry::new();
         // `test_bench` reports a fixed total elapsed regardless of the
         // requested iteration count, which is fine for these HTTP-level tests.
         registry.register("test_bench", |_n| Duration::from_millis(42));
+        registry.register_with_output("answer_bench", |_n| (Duration::from_millis(1), 42u64));
 
         let (shutdown_tx, _) = watch::channel(false);
 
@@ -344,6 +655,7 @@ mod tests {
             shutdown_tx,
             claim: Mutex::new(None),
             iteration_count: AtomicU64::new(0),
+            auth_token: auth_token.map(str::to_string),
         })
     }
 
@@ -371,6 +683,55 @@ mod tests {
         assert_eq!(health.status, "healthy");
     }
 
+    #[tokio::test]
+    async fn test_info_endpoint() {
+        let state = create_test_state();
+        let app = build_router(state);
+
+        let response = app
+            .oneshot(Request::builder().uri("/info").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let info: HarnessInfo = serde_json::from_slice(&body).unwrap();
+        assert_eq!(info.pid, std::process::id());
+        assert_eq!(info.harness_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_endpoint() {
+        let state = create_test_state();
+        let app = build_router(state);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/verify")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let verify: VerifyResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(verify.digests.len(), 1);
+        assert_eq!(
+            verify.digests["answer_bench"],
+            crate::digest::digest(&42u64)
+        );
+    }
+
     #[tokio::test]
     async fn test_list_benchmarks_endpoint() {
         let state = create_test_state();
@@ -503,4 +864,241 @@ mod tests {
         let result: ShutdownResponse = serde_json::from_slice(&body).unwrap();
         assert_eq!(result.status, "shutting_down");
     }
+
+    /// POST a JSON body to `uri` and return the status plus parsed body.
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        app: &Router,
+        uri: &str,
+        body: &str,
+    ) -> (StatusCode, T) {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_run_batch_reports_each_entry() {
+        let app = build_router(create_test_state());
+
+        let (status, batch): (_, RunBatchResponse) = post_json(
+            &app,
+            "/run_batch",
+            r#"{"runs": [
+                {"benchmark_id": "test_bench", "iterations": 3},
+                {"benchmark_id": "missing", "iterations": 1},
+                {"benchmark_id": "test_bench", "iterations": 0}
+            ]}"#,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(batch.results.len(), 3);
+        assert!(batch.results[0].success);
+        assert_eq!(batch.results[0].iterations, 3);
+        assert_eq!(batch.results[0].duration_ns, 42_000_000);
+        assert!(!batch.results[1].success);
+        assert!(batch.results[1].error.as_ref().unwrap().contains("missing"));
+        assert!(!batch.results[2].success);
+    }
+
+    #[tokio::test]
+    async fn test_run_batch_requires_claim() {
+        let app = build_router(create_test_state());
+
+        let (status, _): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "holder"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body): (_, serde_json::Value) = post_json(
+            &app,
+            "/run_batch",
+            r#"{"runs": [{"benchmark_id": "test_bench", "iterations": 1}]}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_claim_conflict_without_force() {
+        let app = build_router(create_test_state());
+
+        let (status, _): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "first", "ttl_ms": 60000}"#).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, result): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "second"}"#).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn test_claim_forced_takeover() {
+        let app = build_router(create_test_state());
+
+        let _: (_, ClaimResponse) = post_json(&app, "/claim", r#"{"nonce": "first"}"#).await;
+        let (status, result): (_, ClaimResponse) = post_json(
+            &app,
+            "/claim",
+            r#"{"nonce": "second", "ttl_ms": 60000, "force": true}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(result.success);
+        assert!(result.expires_in_ms.is_some());
+
+        // The previous holder has lost its lease.
+        let (status, result): (_, HeartbeatResponse) =
+            post_json(&app, "/heartbeat", r#"{"nonce": "first"}"#).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn test_claim_rejects_zero_ttl() {
+        let app = build_router(create_test_state());
+
+        let (status, result): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "holder", "ttl_ms": 0}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("ttl_ms"));
+
+        // Nothing was claimed.
+        let (status, _): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "other"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_applies_new_ttl() {
+        let app = build_router(create_test_state());
+
+        let _: (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "holder", "ttl_ms": 60000}"#).await;
+        let (status, result): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "holder", "ttl_ms": 5000}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(result.expires_in_ms.unwrap() <= 5000);
+
+        // Heartbeats renew by the new TTL too.
+        let (_, result): (_, HeartbeatResponse) =
+            post_json(&app, "/heartbeat", r#"{"nonce": "holder"}"#).await;
+        assert!(result.expires_in_ms.unwrap() <= 5000);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_extends_live_lease() {
+        let app = build_router(create_test_state());
+
+        let _: (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "holder", "ttl_ms": 60000}"#).await;
+        let (status, result): (_, HeartbeatResponse) =
+            post_json(&app, "/heartbeat", r#"{"nonce": "holder"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(result.success);
+        assert!(result.expires_in_ms.unwrap() > 59_000);
+    }
+
+    #[tokio::test]
+    async fn test_expired_lease_frees_harness() {
+        let app = build_router(create_test_state());
+
+        let _: (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "crashed", "ttl_ms": 1}"#).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // No claim header needed once the lease has lapsed.
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/benchmarks")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // A new orchestrator can claim without forcing.
+        let (status, _): (_, ClaimResponse) =
+            post_json(&app, "/claim", r#"{"nonce": "next"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_auth_rejects_missing_and_wrong_token() {
+        let app = build_router(create_test_state_with_auth(Some("s3cret")));
+
+        for uri in ["/health", "/benchmarks"] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{uri}");
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/shutdown")
+                    .header("authorization", "Bearer wrong")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_auth_accepts_matching_token() {
+        let app = build_router(create_test_state_with_auth(Some("s3cret")));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .header("authorization", "Bearer s3cret")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc", "abc"));
+        assert!(!tokens_match("abd", "abc"));
+        assert!(!tokens_match("ab", "abc"));
+        assert!(!tokens_match("", "abc"));
+    }
+
+    #[test]
+    fn test_harness_config_defaults_to_loopback_without_auth() {
+        let config = HarnessConfig::new(9100);
+        assert!(config.bind_addr.is_loopback());
+        assert!(config.auth_token.is_none());
+        assert_eq!(config.transport, Transport::Http);
+    }
 }
@@ -4,6 +4,7 @@
 //! It locates Cargo.toml, runs cargo build, and finds the resulting benchmark
 //! binary in the target directory.
 
+use hypobench_core::protocol::{FEATURES_ENV, GIT_SHA_ENV};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -156,6 +157,7 @@ impl BuildManager {
         for flag in &self.cargo_flags {
             cmd.arg(flag);
         }
+        self.set_provenance_env(&mut cmd, source_path);
 
         let status = spawn_and_stream(&mut cmd, label)?;
 
@@ -191,6 +193,7 @@ impl BuildManager {
         for flag in &self.cargo_flags {
             cmd.arg(flag);
         }
+        self.set_provenance_env(&mut cmd, source_path);
 
         let status = spawn_and_stream(&mut cmd, label)?;
 
@@ -204,6 +207,55 @@ impl BuildManager {
         Ok(())
     }
 
+    /// Bake the source commit and selected features into the harness.
+    ///
+    /// The harness reads these at compile time and reports them from `/info`.
+    fn set_provenance_env(&self, cmd: &mut Command, source_path: &Path) {
+        if let Some(sha) = head_commit(source_path) {
+            cmd.env(GIT_SHA_ENV, sha);
+        }
+        cmd.env(FEATURES_ENV, self.requested_features().join(","));
+    }
+
+    /// The features selected by the configured cargo flags.
+    ///
+    /// `default` is included unless `--no-default-features` is given, and
+    /// `--all-features` is recorded as `all`.
+    fn requested_features(&self) -> Vec<String> {
+        if self.cargo_flags.iter().any(|flag| flag == "--all-features") {
+            return vec!["all".to_string()];
+        }
+
+        let mut features = Vec::new();
+        if !self
+            .cargo_flags
+            .iter()
+            .any(|flag| flag == "--no-default-features")
+        {
+            features.push("default".to_string());
+        }
+
+        let mut flags = self.cargo_flags.iter();
+        while let Some(flag) = flags.next() {
+            let list = match flag.as_str() {
+                "--features" | "-F" => flags.next().map(String::as_str),
+                _ => flag
+                    .strip_prefix("--features=")
+                    .or_else(|| flag.strip_prefix("-F")),
+            };
+            for feature in list
+                .unwrap_or_default()
+                .split([',', ' '])
+                .filter(|feature| !feature.is_empty())
+            {
+                if !features.iter().any(|existing| existing == feature) {
+                    features.push(feature.to_string());
+                }
+            }
+        }
+        features
+    }
+
     /// Find the benchmark binary in the target directory.
     ///
     /// Looks in `target/{profile}/deps/` for executable files matching the
@@ -368,6 +420,19 @@ impl BuildManager {
     }
 }
 
+/// The commit checked out at `source_path`, if it is inside a git repository.
+fn head_commit(source_path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(source_path)
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 /// Spawn `cmd`, pipe its stdout and stderr, and forward every line to this
 /// process's stderr with a `[cargo {label}] ...` prefix. Returns the child's
 /// exit status once both streams have been drained and the child has exited.
@@ -443,6 +508,31 @@ mod tests {
         assert_eq!(bench.target_dir_name(), "bench");
     }
 
+    #[test]
+    fn test_requested_features() {
+        let features = |flags: &[&str]| {
+            BuildManager::new(
+                "release".to_string(),
+                flags.iter().map(|flag| flag.to_string()).collect(),
+            )
+            .requested_features()
+        };
+
+        assert_eq!(features(&[]), vec!["default"]);
+        assert_eq!(
+            features(&["--features", "simd,serde", "-Fjson"]),
+            vec!["default", "simd", "serde", "json"]
+        );
+        assert_eq!(
+            features(&["--no-default-features", "--features=simd"]),
+            vec!["simd"]
+        );
+        assert_eq!(
+            features(&["--all-features", "--features", "x"]),
+            vec!["all"]
+        );
+    }
+
     #[test]
     fn test_no_cargo_toml_error() {
         let manager = BuildManager::new("release".to_string(), vec![]);
@@ -0,0 +1,245 @@
+//! A/A calibration: how noisy is this setup?
+//!
+//! `hypobench calibrate` runs one binary as both baseline and candidate,
+//! several times over. Any significant difference it finds is a false
+//! positive. Each run is Bonferroni-corrected across its benchmarks, so the
+//! share of repeats flagging any benchmark is the setup's empirical
+//! family-wise false-positive rate, to hold against the nominal
+//! `1 - confidence_level`.
+//! The width of the confidence intervals gives each benchmark's noise floor:
+//! changes smaller than that can't be told apart from noise.
+
+use std::io::{self, Write};
+
+use hypobench_core::stats::bootstrap::quantile;
+use hypobench_core::stats::TestResult;
+
+/// A/A results for one benchmark across all repeats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkCalibration {
+    /// Benchmark name.
+    pub name: String,
+    /// Number of A/A runs that included this benchmark.
+    pub runs: usize,
+    /// Runs that reported a significant difference.
+    pub false_positives: usize,
+    /// Median half-width of the change confidence interval, in percent: the
+    /// smallest change a single run could detect.
+    pub noise_floor: f64,
+    /// Largest absolute change seen in any run, in percent.
+    pub max_abs_change: f64,
+}
+
+impl BenchmarkCalibration {
+    /// Share of runs that reported a significant difference.
+    pub fn false_positive_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.false_positives as f64 / self.runs as f64
+        }
+    }
+
+    /// A `minimum_effect_size` that none of the A/A runs would have crossed
+    /// and that sits above the noise floor, rounded up to a tenth of a percent.
+    pub fn suggested_minimum_effect_size(&self) -> f64 {
+        let raw = self.noise_floor.max(self.max_abs_change);
+        // Round before taking the ceiling so 0.3 doesn't become 0.4.
+        ((raw * 10.0 * 1e6).round() / 1e6).ceil() / 10.0
+    }
+}
+
+/// A/A results for a whole suite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    /// Per-benchmark results, in the order benchmarks first appeared.
+    pub benchmarks: Vec<BenchmarkCalibration>,
+    /// Number of A/A repeats.
+    pub repeats: usize,
+    /// Repeats in which at least one benchmark reported a significant
+    /// difference.
+    pub flagged_repeats: usize,
+    /// Confidence level the runs were analyzed at.
+    pub confidence_level: f64,
+    /// Minimum effect size (percent) the runs were analyzed with.
+    pub minimum_effect_size: f64,
+}
+
+impl Calibration {
+    /// Summarize repeated A/A runs; `runs[i]` holds each benchmark's name and
+    /// test result from repeat `i`.
+    pub fn from_runs(
+        runs: &[Vec<(String, TestResult)>],
+        confidence_level: f64,
+        minimum_effect_size: f64,
+    ) -> Self {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, _) in runs.iter().flatten() {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        let benchmarks = names
+            .into_iter()
+            .map(|name| {
+                let results: Vec<&TestResult> = runs
+                    .iter()
+                    .flatten()
+                    .filter(|(n, _)| n == name)
+                    .map(|(_, result)| result)
+                    .collect();
+                let half_widths: Vec<f64> = results
+                    .iter()
+                    .map(|r| (r.change_ci_high - r.change_ci_low).abs() / 2.0)
+                    .collect();
+                BenchmarkCalibration {
+                    name: name.to_string(),
+                    runs: results.len(),
+                    false_positives: results.iter().filter(|r| r.winner.is_some()).count(),
+                    noise_floor: quantile(&half_widths, 0.5),
+                    max_abs_change: results
+                        .iter()
+                        .map(|r| r.effect_size.abs())
+                        .fold(0.0, f64::max),
+                }
+            })
+            .collect();
+        Self {
+            benchmarks,
+            repeats: runs.len(),
+            flagged_repeats: runs
+                .iter()
+                .filter(|run| run.iter().any(|(_, r)| r.winner.is_some()))
+                .count(),
+            confidence_level,
+            minimum_effect_size,
+        }
+    }
+
+    /// Share of repeats in which any benchmark reported a significant
+    /// difference: the family-wise rate the correction holds to
+    /// `1 - confidence_level`.
+    pub fn false_positive_rate(&self) -> f64 {
+        if self.repeats == 0 {
+            0.0
+        } else {
+            self.flagged_repeats as f64 / self.repeats as f64
+        }
+    }
+
+    /// Write the calibration as a plain-text table with suggestions.
+    pub fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "A/A calibration at {:.0}% confidence, minimum effect {}%",
+            self.confidence_level * 100.0,
+            self.minimum_effect_size
+        )?;
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{:<30} {:>15} {:>12} {:>12} {:>14}",
+            "Benchmark", "False positives", "Noise floor", "Max |change|", "Suggested min"
+        )?;
+        for bench in &self.benchmarks {
+            writeln!(
+                writer,
+                "{:<30} {:>15} {:>11.2}% {:>11.2}% {:>13.1}%",
+                bench.name,
+                format!("{}/{}", bench.false_positives, bench.runs),
+                bench.noise_floor,
+                bench.max_abs_change,
+                bench.suggested_minimum_effect_size()
+            )?;
+        }
+        writeln!(writer)?;
+
+        writeln!(
+            writer,
+            "Family-wise false-positive rate: {}/{} repeats ({:.1}%), nominal at most {:.1}%",
+            self.flagged_repeats,
+            self.repeats,
+            self.false_positive_rate() * 100.0,
+            (1.0 - self.confidence_level) * 100.0
+        )?;
+        let suggested = self
+            .benchmarks
+            .iter()
+            .map(BenchmarkCalibration::suggested_minimum_effect_size)
+            .fold(0.0, f64::max);
+        if suggested > self.minimum_effect_size {
+            writeln!(
+                writer,
+                "Suggestion: set hypothesis.minimum_effect_size to at least {:.1} for this setup",
+                suggested
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypobench_core::stats::Side;
+
+    fn result(effect_size: f64, half_width: f64, winner: Option<Side>) -> TestResult {
+        TestResult {
+            p_value: if winner.is_some() { 0.01 } else { 0.5 },
+            statistically_significant: winner.is_some(),
+            effect_size,
+            change_ci_low: effect_size - half_width,
+            change_ci_high: effect_size + half_width,
+            confidence_level: 0.95,
+            winner,
+            baseline_mean_ns: 100.0,
+            candidate_mean_ns: 100.0,
+            equivalence: None,
+            power: None,
+            posterior: None,
+            change_interval: None,
+        }
+    }
+
+    #[test]
+    fn test_calibration_counts_false_positives_and_noise() {
+        let runs = vec![
+            vec![
+                ("fast".to_string(), result(0.1, 0.2, None)),
+                ("noisy".to_string(), result(-2.5, 1.0, Some(Side::Baseline))),
+            ],
+            vec![
+                ("fast".to_string(), result(-0.2, 0.4, None)),
+                ("noisy".to_string(), result(0.5, 3.0, None)),
+            ],
+            vec![
+                ("fast".to_string(), result(0.0, 0.3, None)),
+                ("noisy".to_string(), result(1.0, 2.0, None)),
+            ],
+        ];
+        let calibration = Calibration::from_runs(&runs, 0.95, 1.0);
+
+        let fast = &calibration.benchmarks[0];
+        assert_eq!(fast.name, "fast");
+        assert_eq!((fast.false_positives, fast.runs), (0, 3));
+        assert!((fast.noise_floor - 0.3).abs() < 1e-9);
+        assert!((fast.max_abs_change - 0.2).abs() < 1e-9);
+        assert!((fast.suggested_minimum_effect_size() - 0.3).abs() < 1e-9);
+
+        let noisy = &calibration.benchmarks[1];
+        assert_eq!(noisy.false_positives, 1);
+        assert!((noisy.noise_floor - 2.0).abs() < 1e-9);
+        assert!((noisy.suggested_minimum_effect_size() - 2.5).abs() < 1e-9);
+
+        assert_eq!((calibration.flagged_repeats, calibration.repeats), (1, 3));
+        assert!((calibration.false_positive_rate() - 1.0 / 3.0).abs() < 1e-9);
+
+        let mut out = Vec::new();
+        calibration.write(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(
+            "Family-wise false-positive rate: 1/3 repeats (33.3%), nominal at most 5.0%"
+        ));
+        assert!(out.contains("minimum_effect_size to at least 2.5"));
+    }
+}
@@ -1,6 +1,7 @@
 //! Command-line interface for hypobench.
 
 use crate::config::Config;
+use crate::report::HeadlineMetric;
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
@@ -25,6 +26,11 @@ pub enum Command {
     Run(RunArgs),
     /// Re-render a previously produced JSON report without re-running benchmarks.
     Report(ReportArgs),
+    /// Check the machine for sources of benchmark noise (governor, turbo, load, ...).
+    Doctor(DoctorArgs),
+    /// Run one commit against itself repeatedly to measure the setup's
+    /// false-positive rate and noise floor.
+    Calibrate(CalibrateArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -45,6 +51,10 @@ pub struct RunArgs {
     #[arg(long, conflicts_with = "candidate", requires = "baseline_url")]
     pub candidate_url: Option<String>,
 
+    /// Take over harnesses already claimed by another orchestrator (manual mode)
+    #[arg(long)]
+    pub force_claim: bool,
+
     /// Print harness stdout/stderr for debugging
     #[arg(long)]
     pub harness_output: bool,
@@ -53,6 +63,11 @@ pub struct RunArgs {
     #[arg(long)]
     pub confidence_level: Option<f64>,
 
+    /// Also test for equivalence: report "equivalent" when the change is
+    /// confidently within ±PCT percent
+    #[arg(long, value_name = "PCT")]
+    pub equivalence_margin: Option<f64>,
+
     /// Number of sample iterations per benchmark
     #[arg(long)]
     pub sample_size: Option<u32>,
@@ -61,6 +76,18 @@ pub struct RunArgs {
     #[arg(long)]
     pub target_sample_ms: Option<u64>,
 
+    /// Count perf events (instructions, cycles, ...) and compare them too (Linux)
+    #[arg(long)]
+    pub perf_counters: bool,
+
+    /// Drop samples that were likely preempted (context-switch spikes) before analysis
+    #[arg(long)]
+    pub reject_contaminated: bool,
+
+    /// Refuse to run if the preflight system check finds sources of noise
+    #[arg(long)]
+    pub strict: bool,
+
     /// Path to config file
     #[arg(long, default_value = ".hypobench.toml")]
     pub config: String,
@@ -80,6 +107,11 @@ pub struct RunArgs {
     /// Report format for stdout.
     #[arg(long, value_enum, default_value_t = ReportFormat::Terminal)]
     pub format: ReportFormat,
+
+    /// Statistic the report leads with: `mean`, `median` or a percentile
+    /// such as `p99`
+    #[arg(long, default_value_t = HeadlineMetric::Mean)]
+    pub metric: HeadlineMetric,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -92,6 +124,45 @@ pub struct ReportArgs {
     /// Output format.
     #[arg(long, value_enum, default_value_t = ReportFormat::Terminal)]
     pub format: ReportFormat,
+
+    /// Statistic the report leads with: `mean`, `median` or a percentile
+    /// such as `p99`. Quantiles must be in the report.
+    #[arg(long, default_value_t = HeadlineMetric::Mean)]
+    pub metric: HeadlineMetric,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct DoctorArgs {
+    /// Exit with an error if any check fails.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct CalibrateArgs {
+    /// Commit/branch to build and run as both baseline and candidate
+    #[arg(long, default_value = "HEAD")]
+    pub commit: String,
+
+    /// Number of A/A runs
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    pub repeats: u32,
+
+    /// Number of sample iterations per benchmark
+    #[arg(long)]
+    pub sample_size: Option<u32>,
+
+    /// Path to project within repo (for monorepos/subdirectories)
+    #[arg(long)]
+    pub project_path: Option<PathBuf>,
+
+    /// Specific bench target(s) to build and run (repeatable)
+    #[arg(long)]
+    pub bench: Vec<String>,
+
+    /// Print harness stdout/stderr for debugging
+    #[arg(long)]
+    pub harness_output: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -110,7 +181,9 @@ impl Cli {
         match &self.command {
             None => Some(&self.run),
             Some(Command::Run(args)) => Some(args),
-            Some(Command::Report(_)) => None,
+            Some(Command::Report(_)) | Some(Command::Doctor(_)) | Some(Command::Calibrate(_)) => {
+                None
+            }
         }
     }
 }
@@ -123,6 +196,14 @@ impl RunArgs {
 
     /// Validate that either git-mode args or manual-mode args are present.
     pub fn validate(&self) -> Result<(), String> {
+        if let Some(margin) = self.equivalence_margin {
+            if margin.is_nan() || margin <= 0.0 {
+                return Err(format!(
+                    "--equivalence-margin must be positive, got {}",
+                    margin
+                ));
+            }
+        }
         if self.is_manual_mode() {
             return Ok(());
         }
@@ -132,6 +213,12 @@ impl RunArgs {
                     .to_string(),
             );
         }
+        if self.force_claim {
+            // Spawned harnesses are fresh processes; there is never a claim to take over.
+            return Err(
+                "--force-claim only applies with --baseline-url/--candidate-url".to_string(),
+            );
+        }
         Ok(())
     }
 
@@ -140,12 +227,31 @@ impl RunArgs {
         if let Some(confidence_level) = self.confidence_level {
             config.hypothesis.confidence_level = confidence_level;
         }
+        if let Some(margin) = self.equivalence_margin {
+            config.hypothesis.equivalence_margin = Some(margin);
+        }
         if let Some(sample_size) = self.sample_size {
             config.orchestration.sample_size = sample_size;
         }
         if let Some(target_sample_ms) = self.target_sample_ms {
             config.orchestration.target_sample_ms = target_sample_ms;
         }
+        if self.perf_counters {
+            config.orchestration.perf_counters = true;
+        }
+        if self.reject_contaminated {
+            config.orchestration.reject_contaminated = true;
+        }
+        // Leading with a quantile needs it computed.
+        if let HeadlineMetric::Quantile(q) = self.metric {
+            let quantiles = &mut config.hypothesis.quantiles;
+            if !quantiles
+                .iter()
+                .any(|&existing| (existing - q).abs() < 1e-9)
+            {
+                quantiles.push(q);
+            }
+        }
     }
 }
 
@@ -159,15 +265,21 @@ mod tests {
             candidate: None,
             baseline_url: None,
             candidate_url: None,
+            force_claim: false,
             harness_output: false,
             confidence_level: None,
+            equivalence_margin: None,
             sample_size: None,
             target_sample_ms: None,
+            perf_counters: false,
+            reject_contaminated: false,
+            strict: false,
             config: ".hypobench.toml".to_string(),
             project_path: None,
             bench: vec![],
             verbose: false,
             format: ReportFormat::Terminal,
+            metric: HeadlineMetric::Mean,
         }
     }
 
@@ -177,15 +289,60 @@ mod tests {
         args.baseline = Some("main".to_string());
         args.candidate = Some("feature".to_string());
         args.confidence_level = Some(0.99);
+        args.equivalence_margin = Some(2.0);
         args.sample_size = Some(200);
         args.target_sample_ms = Some(20);
+        args.perf_counters = true;
+        args.reject_contaminated = true;
 
         let mut config = Config::default();
         args.apply_to_config(&mut config);
 
         assert_eq!(config.hypothesis.confidence_level, 0.99);
+        assert_eq!(config.hypothesis.equivalence_margin, Some(2.0));
         assert_eq!(config.orchestration.sample_size, 200);
         assert_eq!(config.orchestration.target_sample_ms, 20);
+        assert!(config.orchestration.perf_counters);
+        assert!(config.orchestration.reject_contaminated);
+    }
+
+    #[test]
+    fn test_validate_rejects_non_positive_equivalence_margin() {
+        let mut args = default_run_args();
+        args.baseline = Some("main".to_string());
+        args.candidate = Some("feature".to_string());
+        args.equivalence_margin = Some(0.0);
+        assert!(args
+            .validate()
+            .unwrap_err()
+            .contains("--equivalence-margin"));
+
+        args.equivalence_margin = Some(1.0);
+        assert!(args.validate().is_ok());
+    }
+
+    #[test]
+    fn test_metric_flag_adds_its_quantile() {
+        let cli = Cli::parse_from([
+            "hypobench",
+            "--baseline",
+            "main",
+            "--candidate",
+            "HEAD",
+            "--metric",
+            "p95",
+        ]);
+        let run = cli.as_run_args().expect("run mode");
+        assert_eq!(run.metric, HeadlineMetric::Quantile(0.95));
+
+        let mut config = Config::default();
+        run.apply_to_config(&mut config);
+        run.apply_to_config(&mut config);
+        assert_eq!(config.hypothesis.quantiles, vec![0.5, 0.9, 0.99, 0.95]);
+
+        assert!(
+            Cli::try_parse_from(["hypobench", "report", "--in", "-", "--metric", "p100"]).is_err()
+        );
     }
 
     #[test]
@@ -249,6 +406,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_doctor_subcommand() {
+        let cli = Cli::parse_from(["hypobench", "doctor", "--strict"]);
+        assert!(cli.as_run_args().is_none());
+        match cli.command {
+            Some(Command::Doctor(args)) => assert!(args.strict),
+            _ => panic!("expected Doctor subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_cli_calibrate_subcommand() {
+        let cli = Cli::parse_from(["hypobench", "calibrate", "--repeats", "3"]);
+        match cli.command {
+            Some(Command::Calibrate(args)) => {
+                assert_eq!(args.commit, "HEAD");
+                assert_eq!(args.repeats, 3);
+            }
+            _ => panic!("expected Calibrate subcommand"),
+        }
+        assert!(Cli::try_parse_from(["hypobench", "calibrate", "--repeats", "0"]).is_err());
+    }
+
     #[test]
     fn test_cli_manual_mode_parses() {
         let cli = Cli::parse_from([
@@ -263,6 +443,31 @@ mod tests {
         assert_eq!(run.baseline_url.as_deref(), Some("http://localhost:9100"));
     }
 
+    #[test]
+    fn test_cli_force_claim_requires_manual_mode() {
+        let cli = Cli::parse_from([
+            "hypobench",
+            "--baseline-url",
+            "http://localhost:9100",
+            "--candidate-url",
+            "http://localhost:9101",
+            "--force-claim",
+        ]);
+        let run = cli.as_run_args().expect("run mode");
+        assert!(run.force_claim);
+        assert!(run.validate().is_ok());
+
+        let cli = Cli::parse_from([
+            "hypobench",
+            "--baseline",
+            "main",
+            "--candidate",
+            "HEAD",
+            "--force-claim",
+        ]);
+        assert!(cli.as_run_args().expect("run mode").validate().is_err());
+    }
+
     #[test]
     fn test_cli_bench_targets() {
         let cli = Cli::parse_from([
@@ -3,8 +3,16 @@
 //! Supports loading configuration from TOML files, with sensible defaults
 //! for all settings.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use hypobench_core::protocol::Transport;
+use hypobench_core::report::QuantileComparison;
+use hypobench_core::stats::{
+    BayesianTest, Bootstrap, ChangeInterval, SlopeTest, StatisticalTest, TestResult, WelchTTest,
+};
+
+use crate::orchestrator::{BenchmarkSamples, Sampling};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Top-level configuration for hypobench.
@@ -19,6 +27,24 @@ pub struct Config {
     pub build: BuildConfig,
     /// Network settings for harness communication.
     pub network: NetworkConfig,
+    /// Settings for the suite-level summary.
+    pub suite: SuiteConfig,
+}
+
+/// Configuration for the suite-level summary.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuiteConfig {
+    /// Per-benchmark weights in the suite's geometric mean, keyed by benchmark
+    /// name. Unlisted benchmarks weigh 1; a weight of 0 leaves one out.
+    pub weights: BTreeMap<String, f64>,
+}
+
+impl SuiteConfig {
+    /// Weight of benchmark `name`.
+    pub fn weight(&self, name: &str) -> f64 {
+        self.weights.get(name).copied().unwrap_or(1.0)
+    }
 }
 
 /// Configuration for statistical hypothesis testing.
@@ -34,6 +60,143 @@ pub struct HypothesisConfig {
     /// test's effective α is `(1 - confidence_level) / N` instead of
     /// `1 - confidence_level`, controlling family-wise false-positive rate.
     pub correct_multiple_comparisons: bool,
+    /// Equivalence margin (in percent) for a two-one-sided-tests check. When
+    /// set, a benchmark with no significant change whose change is confidently
+    /// within ±margin is reported as equivalent rather than inconclusive.
+    pub equivalence_margin: Option<f64>,
+    /// Power (e.g., 0.8) at which each comparison reports its minimum
+    /// detectable effect and the sample size needed to detect
+    /// `minimum_effect_size`.
+    pub power: f64,
+    /// Which test compares the two sides: `"welch"` (default) or `"bayesian"`,
+    /// which also reports the posterior probability that the candidate is
+    /// faster.
+    pub method: TestMethod,
+    /// Quantiles of each side's samples to compare alongside the mean, each
+    /// with a bootstrap confidence interval on its change.
+    pub quantiles: Vec<f64>,
+    /// Resample count, seed and interval method (`"percentile"` or `"bca"`)
+    /// for every bootstrap confidence interval, under `[hypothesis.bootstrap]`.
+    /// The Bayesian test takes its draw count and seed from here too.
+    pub bootstrap: Bootstrap,
+    /// How the CI on the mean (or slope) change is computed: `"bootstrap"`
+    /// (default), or analytically with `"fieller"` or `"delta"`. Ignored by
+    /// the Bayesian test.
+    pub change_interval: ChangeInterval,
+}
+
+impl HypothesisConfig {
+    /// The test configured by `method` and its settings.
+    pub fn statistical_test(&self) -> Box<dyn StatisticalTest> {
+        match self.method {
+            TestMethod::Welch => {
+                let test = WelchTTest::new(self.confidence_level)
+                    .with_minimum_effect_size(self.minimum_effect_size)
+                    .with_power(self.power)
+                    .with_bootstrap(self.bootstrap)
+                    .with_change_interval(self.change_interval);
+                Box::new(match self.equivalence_margin {
+                    Some(margin) => test.with_equivalence_margin(margin),
+                    None => test,
+                })
+            }
+            TestMethod::Bayesian => {
+                let test = BayesianTest::new(self.confidence_level)
+                    .with_minimum_effect_size(self.minimum_effect_size)
+                    .with_bootstrap(self.bootstrap);
+                Box::new(match self.equivalence_margin {
+                    Some(margin) => test.with_equivalence_margin(margin),
+                    None => test,
+                })
+            }
+        }
+    }
+
+    /// The slope comparison for linearly sampled benchmarks, configured like
+    /// the Welch test it replaces.
+    pub fn slope_test(&self) -> SlopeTest {
+        let test = SlopeTest::new(self.confidence_level)
+            .with_minimum_effect_size(self.minimum_effect_size)
+            .with_bootstrap(self.bootstrap)
+            .with_change_interval(self.change_interval);
+        match self.equivalence_margin {
+            Some(margin) => test.with_equivalence_margin(margin),
+            None => test,
+        }
+    }
+
+    /// The `(iterations, elapsed_ns)` runs whose slopes [`Self::compare`]
+    /// tests: those of a linearly sampled benchmark under Welch. The
+    /// Bayesian test always compares per-sample means.
+    #[allow(clippy::type_complexity)]
+    pub fn slope_runs(
+        &self,
+        sample: &BenchmarkSamples,
+    ) -> Option<(Vec<(f64, f64)>, Vec<(f64, f64)>)> {
+        (self.method == TestMethod::Welch)
+            .then(|| sample.linear_runs())
+            .flatten()
+    }
+
+    /// Compare one benchmark's sides with `test` (from
+    /// [`Self::statistical_test`]), or by their slopes when
+    /// [`Self::slope_runs`] has them.
+    pub fn compare(&self, test: &dyn StatisticalTest, sample: &BenchmarkSamples) -> TestResult {
+        match self.slope_runs(sample) {
+            Some((baseline, candidate)) => self.slope_test().analyze_runs(&baseline, &candidate),
+            None => test.analyze(&sample.baseline_samples, &sample.candidate_samples),
+        }
+    }
+
+    /// Compare the configured quantiles of one benchmark's samples, in
+    /// nanoseconds, out of `family_size` benchmarks. Under Bonferroni
+    /// correction the intervals are taken at `1 - α/family_size`, so a
+    /// quantile headline is corrected like the mean's.
+    pub fn compare_quantiles(
+        &self,
+        baseline: &[f64],
+        candidate: &[f64],
+        family_size: usize,
+    ) -> Vec<QuantileComparison> {
+        let mut confidence = self.confidence_level;
+        if self.correct_multiple_comparisons && family_size > 1 {
+            confidence = 1.0 - (1.0 - confidence) / family_size as f64;
+        }
+        self.quantiles
+            .iter()
+            .map(|&q| {
+                QuantileComparison::analyze(
+                    baseline,
+                    candidate,
+                    q,
+                    confidence,
+                    self.minimum_effect_size,
+                    &self.bootstrap,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Statistical test used to compare baseline and candidate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestMethod {
+    /// Welch's t-test with a bootstrap confidence interval.
+    #[default]
+    Welch,
+    /// Bayesian comparison of log-times, with a credible interval.
+    Bayesian,
+}
+
+impl TestMethod {
+    /// The name used in config files and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TestMethod::Welch => "welch",
+            TestMethod::Bayesian => "bayesian",
+        }
+    }
 }
 
 /// Configuration for benchmark orchestration.
@@ -44,6 +207,13 @@ pub struct OrchestrationConfig {
     pub interleave_interval_ms: u64,
     /// Number of samples to collect for each benchmark after calibration.
     pub sample_size: u32,
+    /// Samples taken per side before switching to the other side.
+    ///
+    /// Each block is a single batch request, so larger blocks cut round-trips
+    /// and `interleave_interval_ms` sleeps at the cost of coarser
+    /// interleaving. Blocks alternate ABBA-style, so `1` is the classic pair
+    /// alternation.
+    pub block_size: u32,
     /// Target minimum elapsed time per sample, in milliseconds.
     ///
     /// Calibration picks an iteration count `n` such that one sample
@@ -53,6 +223,44 @@ pub struct OrchestrationConfig {
     /// Safety cap on the iteration count chosen during calibration.
     /// Prevents pathological benchmarks from exploding.
     pub max_calibration_iters: u64,
+    /// How many iterations each sample runs: `"flat"` (default) runs the
+    /// calibrated count every time; `"linear"` runs increasing counts and
+    /// compares the slopes of elapsed time against the count, so fixed
+    /// per-sample overhead doesn't inflate per-iteration times.
+    pub sampling: Sampling,
+    /// Ask the harnesses to count perf events (instructions, cycles, branch
+    /// and cache misses) around each sample and compare them alongside time.
+    /// Linux only; harnesses fall back to software counters, or none, when
+    /// hardware counters aren't available.
+    pub perf_counters: bool,
+    /// Drop samples whose involuntary context switches spiked before
+    /// analysis. They are always flagged in the report; this also leaves them
+    /// out of the statistics. Needs harnesses that report resource usage.
+    pub reject_contaminated: bool,
+    /// CPUs to pin spawned harnesses to (Linux only). Both harnesses share
+    /// them and take turns, since samples are interleaved. Empty leaves
+    /// placement to the kernel.
+    pub cpus: Vec<usize>,
+    /// CPUs for the baseline harness alone, overriding `cpus`.
+    pub baseline_cpus: Vec<usize>,
+    /// CPUs for the candidate harness alone, overriding `cpus`.
+    pub candidate_cpus: Vec<usize>,
+    /// Nice value for spawned harnesses (Linux only). Negative values need
+    /// `CAP_SYS_NICE`.
+    pub nice: Option<i32>,
+    /// Run spawned harnesses under `SCHED_FIFO` at this priority, 1–99
+    /// (Linux only). Needs `CAP_SYS_NICE`; a busy benchmark at real-time
+    /// priority can starve everything else on its CPUs, so pin it too.
+    pub fifo_priority: Option<u32>,
+    /// Command to run spawned harnesses under, with the binary path appended,
+    /// e.g. `["numactl", "--cpunodebind=0", "--"]`. `{side}` in any argument
+    /// becomes `baseline` or `candidate`. The launcher must exec the harness
+    /// (or forward signals to it), since it is what gets killed on shutdown.
+    pub launcher: Vec<String>,
+    /// Extra environment variables for the spawned baseline harness.
+    pub baseline_env: BTreeMap<String, String>,
+    /// Extra environment variables for the spawned candidate harness.
+    pub candidate_env: BTreeMap<String, String>,
 }
 
 /// Configuration for building benchmark binaries.
@@ -71,10 +279,21 @@ pub struct BuildConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct NetworkConfig {
-    /// Base port for harness HTTP servers.
-    pub base_port: u16,
+    /// Fixed base port for spawned harness HTTP servers (baseline uses this,
+    /// candidate uses the next port). When unset, each harness binds a free
+    /// port and announces it on stdout, so concurrent runs can't collide.
+    pub base_port: Option<u16>,
     /// Timeout in milliseconds for harness communication.
     pub harness_timeout_ms: u64,
+    /// Lease duration in milliseconds requested when claiming a harness.
+    ///
+    /// The orchestrator heartbeats at a third of this interval; if it crashes,
+    /// the harness drops the claim once the lease lapses.
+    pub claim_ttl_ms: u64,
+    /// How the orchestrator talks to harnesses it spawns: `"http"` (default)
+    /// or `"pipe"`, which uses the child's stdin/stdout and binds no ports.
+    /// Pipe needs harnesses built against a `hypobench-harness` that supports it.
+    pub transport: Transport,
 }
 
 impl Default for HypothesisConfig {
@@ -83,6 +302,12 @@ impl Default for HypothesisConfig {
             confidence_level: 0.95,
             minimum_effect_size: 1.0, // 1% minimum effect size
             correct_multiple_comparisons: true,
+            equivalence_margin: None,
+            power: 0.8,
+            method: TestMethod::Welch,
+            quantiles: vec![0.5, 0.9, 0.99],
+            bootstrap: Bootstrap::default(),
+            change_interval: ChangeInterval::Bootstrap,
         }
     }
 }
@@ -92,8 +317,20 @@ impl Default for OrchestrationConfig {
         Self {
             interleave_interval_ms: 100,
             sample_size: 100,
+            block_size: 1,
             target_sample_ms: 10,
             max_calibration_iters: 1_000_000_000,
+            sampling: Sampling::Flat,
+            perf_counters: false,
+            reject_contaminated: false,
+            cpus: Vec::new(),
+            baseline_cpus: Vec::new(),
+            candidate_cpus: Vec::new(),
+            nice: None,
+            fifo_priority: None,
+            launcher: Vec::new(),
+            baseline_env: BTreeMap::new(),
+            candidate_env: BTreeMap::new(),
         }
     }
 }
@@ -111,8 +348,10 @@ impl Default for BuildConfig {
 impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
-            base_port: 9100,
+            base_port: None,
             harness_timeout_ms: 30_000, // 30 seconds
+            claim_ttl_ms: 30_000,
+            transport: Transport::Http,
         }
     }
 }
@@ -129,17 +368,55 @@ impl Config {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be read or parsed.
+    /// Returns an error if the file cannot be read or parsed, or if it holds
+    /// settings no run could work with.
     pub fn load(path: &Path) -> Result<Config> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
         let config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        config
+            .validate()
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
 
         Ok(config)
     }
 
+    /// Reject settings that parse but can't work, and warn about ones that
+    /// make results unreliable.
+    fn validate(&self) -> Result<()> {
+        if self.network.claim_ttl_ms == 0 {
+            bail!("network.claim_ttl_ms must be positive; a zero lease expires as soon as it is granted");
+        }
+        if let Some(margin) = self.hypothesis.equivalence_margin {
+            if margin.is_nan() || margin <= 0.0 {
+                bail!(
+                    "hypothesis.equivalence_margin must be positive, got {}",
+                    margin
+                );
+            }
+        }
+        let power = self.hypothesis.power;
+        if !(power > 0.0 && power < 1.0) {
+            bail!(
+                "hypothesis.power must be between 0 and 1 exclusive, got {}",
+                power
+            );
+        }
+        let resamples = self.hypothesis.bootstrap.resamples;
+        if resamples == 0 {
+            bail!("hypothesis.bootstrap.resamples must be positive; with no resamples every interval collapses to its point estimate");
+        }
+        if resamples < 1000 {
+            eprintln!(
+                "Warning: hypothesis.bootstrap.resamples = {} leaves the interval endpoints noisy; use at least 1000",
+                resamples
+            );
+        }
+        Ok(())
+    }
+
     /// Load configuration from the default file (`.hypobench.toml`) or use defaults.
     ///
     /// This function searches for the configuration file in the current directory.
@@ -182,6 +459,7 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hypobench_core::stats::BootstrapInterval;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -193,13 +471,17 @@ mod tests {
         assert_eq!(config.hypothesis.minimum_effect_size, 1.0);
         assert_eq!(config.orchestration.interleave_interval_ms, 100);
         assert_eq!(config.orchestration.sample_size, 100);
+        assert_eq!(config.orchestration.block_size, 1);
         assert_eq!(config.orchestration.target_sample_ms, 10);
         assert_eq!(config.orchestration.max_calibration_iters, 1_000_000_000);
+        assert_eq!(config.orchestration.sampling, Sampling::Flat);
         assert_eq!(config.build.profile, "release");
         assert!(config.build.cargo_flags.is_empty());
         assert!(config.build.bench_targets.is_empty());
-        assert_eq!(config.network.base_port, 9100);
+        assert_eq!(config.network.base_port, None);
         assert_eq!(config.network.harness_timeout_ms, 30_000);
+        assert_eq!(config.network.claim_ttl_ms, 30_000);
+        assert_eq!(config.network.transport, Transport::Http);
     }
 
     #[test]
@@ -233,11 +515,32 @@ sample_size = 200
 [hypothesis]
 confidence_level = 0.99
 minimum_effect_size = 2.5
+equivalence_margin = 2.0
+power = 0.9
+method = "bayesian"
+quantiles = [0.5, 0.999]
+change_interval = "fieller"
+
+[hypothesis.bootstrap]
+resamples = 2000
+interval = "bca"
 
 [orchestration]
 interleave_interval_ms = 50
 sample_size = 200
 target_sample_ms = 25
+sampling = "linear"
+baseline_cpus = [2]
+candidate_cpus = [3]
+nice = -5
+launcher = ["valgrind", "--tool=cachegrind", "--cachegrind-out-file=cg.{side}.out"]
+
+[orchestration.candidate_env]
+RAYON_NUM_THREADS = "1"
+
+[suite.weights]
+"parse/large" = 2.0
+"parse/tiny" = 0.0
 
 [build]
 profile = "bench"
@@ -246,6 +549,8 @@ cargo_flags = ["--features", "test-feature"]
 [network]
 base_port = 8000
 harness_timeout_ms = 60000
+claim_ttl_ms = 10000
+transport = "pipe"
 "#;
 
         let mut file = NamedTempFile::new().unwrap();
@@ -255,13 +560,38 @@ harness_timeout_ms = 60000
 
         assert_eq!(config.hypothesis.confidence_level, 0.99);
         assert_eq!(config.hypothesis.minimum_effect_size, 2.5);
+        assert_eq!(config.hypothesis.equivalence_margin, Some(2.0));
+        assert_eq!(config.hypothesis.power, 0.9);
+        assert_eq!(config.hypothesis.method, TestMethod::Bayesian);
+        assert_eq!(config.hypothesis.quantiles, vec![0.5, 0.999]);
+        assert_eq!(config.hypothesis.bootstrap.resamples, 2000);
+        assert_eq!(config.hypothesis.bootstrap.seed, 0xC0FFEE);
+        assert_eq!(config.hypothesis.bootstrap.interval, BootstrapInterval::Bca);
+        assert_eq!(config.hypothesis.change_interval, ChangeInterval::Fieller);
         assert_eq!(config.orchestration.interleave_interval_ms, 50);
         assert_eq!(config.orchestration.sample_size, 200);
         assert_eq!(config.orchestration.target_sample_ms, 25);
+        assert_eq!(config.orchestration.sampling, Sampling::Linear);
+        assert!(config.orchestration.cpus.is_empty());
+        assert_eq!(config.orchestration.baseline_cpus, vec![2]);
+        assert_eq!(config.suite.weight("parse/large"), 2.0);
+        assert_eq!(config.suite.weight("parse/tiny"), 0.0);
+        assert_eq!(config.suite.weight("parse/other"), 1.0);
+        assert_eq!(config.orchestration.candidate_cpus, vec![3]);
+        assert_eq!(config.orchestration.nice, Some(-5));
+        assert_eq!(config.orchestration.fifo_priority, None);
+        assert_eq!(config.orchestration.launcher.len(), 3);
+        assert!(config.orchestration.baseline_env.is_empty());
+        assert_eq!(
+            config.orchestration.candidate_env.get("RAYON_NUM_THREADS"),
+            Some(&"1".to_string())
+        );
         assert_eq!(config.build.profile, "bench");
         assert_eq!(config.build.cargo_flags, vec!["--features", "test-feature"]);
-        assert_eq!(config.network.base_port, 8000);
+        assert_eq!(config.network.base_port, Some(8000));
         assert_eq!(config.network.harness_timeout_ms, 60000);
+        assert_eq!(config.network.claim_ttl_ms, 10000);
+        assert_eq!(config.network.transport, Transport::Pipe);
     }
 
     #[test]
@@ -279,6 +609,110 @@ harness_timeout_ms = 60000
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_load_rejects_zero_claim_ttl() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"[network]\nclaim_ttl_ms = 0\n").unwrap();
+
+        let err = Config::load(file.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("claim_ttl_ms"), "{err:#}");
+    }
+
+    #[test]
+    fn test_load_rejects_non_positive_equivalence_margin() {
+        for margin in ["0.0", "-1.0"] {
+            let mut file = NamedTempFile::new().unwrap();
+            write!(file, "[hypothesis]\nequivalence_margin = {margin}\n").unwrap();
+
+            let err = Config::load(file.path()).unwrap_err();
+            assert!(format!("{err:#}").contains("equivalence_margin"), "{err:#}");
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_power_outside_unit_interval() {
+        for power in ["0.0", "1.0", "1.5"] {
+            let mut file = NamedTempFile::new().unwrap();
+            write!(file, "[hypothesis]\npower = {power}\n").unwrap();
+
+            let err = Config::load(file.path()).unwrap_err();
+            assert!(format!("{err:#}").contains("power"), "{err:#}");
+        }
+    }
+
+    #[test]
+    fn test_load_rejects_zero_resamples() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"[hypothesis.bootstrap]\nresamples = 0\n")
+            .unwrap();
+
+        let err = Config::load(file.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("resamples"), "{err:#}");
+    }
+
+    #[test]
+    fn test_bayesian_ignores_linear_runs() {
+        use std::time::Duration;
+
+        // 100ns per iteration plus 1000ns per sample on the baseline, 90ns
+        // on the candidate.
+        let mut sample = BenchmarkSamples::new("linear");
+        for n in 1..=20u64 {
+            let noise = if n % 2 == 0 { 3 } else { 0 };
+            let baseline = Duration::from_nanos(1000 + 100 * n + noise);
+            let candidate = Duration::from_nanos(1000 + 90 * n + noise);
+            sample.baseline_samples.push(baseline / n as u32);
+            sample.baseline_totals.push((n, baseline));
+            sample.candidate_samples.push(candidate / n as u32);
+            sample.candidate_totals.push((n, candidate));
+        }
+
+        let welch = HypothesisConfig::default();
+        assert!(welch.slope_runs(&sample).is_some());
+        let result = welch.compare(welch.statistical_test().as_ref(), &sample);
+        assert!((result.baseline_mean_ns - 100.0).abs() < 0.5);
+        assert!(result.posterior.is_none());
+
+        let bayesian = HypothesisConfig {
+            method: TestMethod::Bayesian,
+            ..HypothesisConfig::default()
+        };
+        assert!(bayesian.slope_runs(&sample).is_none());
+        let result = bayesian.compare(bayesian.statistical_test().as_ref(), &sample);
+        assert!(result.posterior.is_some());
+        // Per-sample means, overhead included.
+        assert!(
+            result.baseline_mean_ns > 200.0,
+            "{}",
+            result.baseline_mean_ns
+        );
+    }
+
+    #[test]
+    fn test_quantiles_are_bonferroni_corrected() {
+        // A median regression of about 2% that only clears the uncorrected level.
+        let baseline: Vec<f64> = (0..100).map(|i| 1000.0 + ((i * 37) % 100) as f64).collect();
+        let candidate: Vec<f64> = (0..100).map(|i| 1020.0 + ((i * 53) % 100) as f64).collect();
+        let hypothesis = HypothesisConfig {
+            quantiles: vec![0.5],
+            ..HypothesisConfig::default()
+        };
+
+        let alone = hypothesis.compare_quantiles(&baseline, &candidate, 1);
+        assert!(alone[0].statistically_significant, "{alone:?}");
+        let in_suite = hypothesis.compare_quantiles(&baseline, &candidate, 20);
+        assert!(!in_suite[0].statistically_significant, "{in_suite:?}");
+        assert!(in_suite[0].change_ci_low < alone[0].change_ci_low);
+        assert_eq!(in_suite[0].change, alone[0].change);
+
+        let uncorrected = HypothesisConfig {
+            correct_multiple_comparisons: false,
+            ..hypothesis
+        };
+        let in_suite = uncorrected.compare_quantiles(&baseline, &candidate, 20);
+        assert!(in_suite[0].statistically_significant);
+    }
+
     #[test]
     fn test_load_or_default_no_file() {
         // This test assumes .hypobench.toml doesn't exist in the test directory
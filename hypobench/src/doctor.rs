@@ -0,0 +1,317 @@
+//! Preflight checks for common sources of system noise.
+//!
+//! A run on a machine with the `powersave` governor, turbo boost, a busy CPU
+//! or a laptop on battery wastes everyone's time: the samples are noisy and
+//! the effect sizes drift between runs. These checks read the relevant
+//! `/sys` and `/proc` files, so they only find anything on Linux. A check
+//! whose files don't exist (e.g. no cpufreq in a VM) is skipped.
+
+use std::fs;
+use std::path::Path;
+
+use hypobench_core::report::SystemCheck;
+
+/// A 1-minute load average at or above this, per online CPU, means other
+/// work is keeping half the machine busy and contending for caches and
+/// memory bandwidth.
+const LOAD_PER_CPU_THRESHOLD: f64 = 0.5;
+
+/// Check the running system. Empty on platforms other than Linux.
+pub fn check_system() -> Vec<SystemCheck> {
+    if cfg!(target_os = "linux") {
+        check_root(Path::new("/"))
+    } else {
+        Vec::new()
+    }
+}
+
+/// Check the system whose `/sys` and `/proc` live under `root`.
+pub fn check_root(root: &Path) -> Vec<SystemCheck> {
+    [
+        cpu_governor(root),
+        turbo_boost(root),
+        load_average(root),
+        aslr(root),
+        smt(root),
+        power_source(root),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The failed checks in `checks`.
+pub fn warnings(checks: &[SystemCheck]) -> impl Iterator<Item = &SystemCheck> {
+    checks.iter().filter(|check| !check.ok)
+}
+
+fn read(root: &Path, path: &str) -> Option<String> {
+    fs::read_to_string(root.join(path))
+        .ok()
+        .map(|content| content.trim().to_string())
+}
+
+fn passed(name: &str, detail: String) -> SystemCheck {
+    SystemCheck {
+        name: name.to_string(),
+        ok: true,
+        detail,
+        advice: None,
+    }
+}
+
+fn failed(name: &str, detail: String, advice: &str) -> SystemCheck {
+    SystemCheck {
+        name: name.to_string(),
+        ok: false,
+        detail,
+        advice: Some(advice.to_string()),
+    }
+}
+
+/// Names of the `cpuN` directories under `/sys/devices/system/cpu`.
+fn cpus(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root.join("sys/devices/system/cpu")) else {
+        return Vec::new();
+    };
+    let mut cpus: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| {
+            name.strip_prefix("cpu")
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .collect();
+    cpus.sort();
+    cpus
+}
+
+fn cpu_governor(root: &Path) -> Option<SystemCheck> {
+    let governors: Vec<String> = cpus(root)
+        .iter()
+        .filter_map(|cpu| {
+            read(
+                root,
+                &format!("sys/devices/system/cpu/{}/cpufreq/scaling_governor", cpu),
+            )
+        })
+        .collect();
+    if governors.is_empty() {
+        return None;
+    }
+    let mut slow: Vec<&str> = governors
+        .iter()
+        .map(String::as_str)
+        .filter(|governor| *governor != "performance")
+        .collect();
+    let slow_count = slow.len();
+    if slow_count == 0 {
+        return Some(passed(
+            "cpu_governor",
+            format!("all {} CPUs use the performance governor", governors.len()),
+        ));
+    }
+    slow.sort_unstable();
+    slow.dedup();
+    Some(failed(
+        "cpu_governor",
+        format!(
+            "{} of {} CPUs use the {} governor",
+            slow_count,
+            governors.len(),
+            slow.join("/")
+        ),
+        "sudo cpupower frequency-set -g performance",
+    ))
+}
+
+fn turbo_boost(root: &Path) -> Option<SystemCheck> {
+    // intel_pstate inverts the flag; acpi-cpufreq and amd-pstate use `boost`.
+    let (enabled, advice) = match read(root, "sys/devices/system/cpu/intel_pstate/no_turbo") {
+        Some(no_turbo) => (
+            no_turbo == "0",
+            "echo 1 | sudo tee /sys/devices/system/cpu/intel_pstate/no_turbo",
+        ),
+        None => (
+            read(root, "sys/devices/system/cpu/cpufreq/boost")? == "1",
+            "echo 0 | sudo tee /sys/devices/system/cpu/cpufreq/boost",
+        ),
+    };
+    Some(if enabled {
+        failed("turbo_boost", "turbo boost is enabled".to_string(), advice)
+    } else {
+        passed("turbo_boost", "turbo boost is disabled".to_string())
+    })
+}
+
+fn load_average(root: &Path) -> Option<SystemCheck> {
+    let loadavg = read(root, "proc/loadavg")?;
+    let load: f64 = loadavg.split_whitespace().next()?.parse().ok()?;
+    let cpus = online_cpus(root).unwrap_or_else(|| cpus(root).len()).max(1);
+    let per_cpu = load / cpus as f64;
+    let detail = format!(
+        "1-minute load average is {:.2} ({:.2} per CPU, {} online)",
+        load, per_cpu, cpus
+    );
+    Some(if per_cpu >= LOAD_PER_CPU_THRESHOLD {
+        failed(
+            "load_average",
+            detail,
+            "wait for other work to finish, or pin the harnesses to idle CPUs with `orchestration.cpus`",
+        )
+    } else {
+        passed("load_average", detail)
+    })
+}
+
+/// Number of CPUs in `/sys/devices/system/cpu/online`, a list of ranges like
+/// `0-3,6,8-11`.
+fn online_cpus(root: &Path) -> Option<usize> {
+    let online = read(root, "sys/devices/system/cpu/online")?;
+    online
+        .split(',')
+        .map(|range| match range.split_once('-') {
+            Some((first, last)) => {
+                let (first, last): (usize, usize) = (first.parse().ok()?, last.parse().ok()?);
+                last.checked_sub(first).map(|n| n + 1)
+            }
+            None => range.parse::<usize>().ok().map(|_| 1),
+        })
+        .sum()
+}
+
+fn aslr(root: &Path) -> Option<SystemCheck> {
+    let level = read(root, "proc/sys/kernel/randomize_va_space")?;
+    Some(if level == "0" {
+        passed("aslr", "ASLR is disabled".to_string())
+    } else {
+        failed(
+            "aslr",
+            format!("ASLR is enabled (randomize_va_space = {})", level),
+            "echo 0 | sudo tee /proc/sys/kernel/randomize_va_space, or set `orchestration.launcher = [\"setarch\", \"-R\"]`",
+        )
+    })
+}
+
+fn smt(root: &Path) -> Option<SystemCheck> {
+    let active = read(root, "sys/devices/system/cpu/smt/active")?;
+    Some(if active == "1" {
+        failed(
+            "smt",
+            "SMT (hyper-threading) is active".to_string(),
+            "pin the harnesses to CPUs on distinct cores with `orchestration.cpus`, or echo off | sudo tee /sys/devices/system/cpu/smt/control",
+        )
+    } else {
+        passed("smt", "SMT is inactive".to_string())
+    })
+}
+
+fn power_source(root: &Path) -> Option<SystemCheck> {
+    let supplies = fs::read_dir(root.join("sys/class/power_supply")).ok()?;
+    let mut has_battery = false;
+    for supply in supplies.filter_map(Result::ok) {
+        let path = supply.path();
+        let attribute = |name: &str| {
+            fs::read_to_string(path.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        if attribute("type").as_deref() == Some("Battery") {
+            has_battery = true;
+            if attribute("status").as_deref() == Some("Discharging") {
+                return Some(failed(
+                    "power_source",
+                    "running on battery power".to_string(),
+                    "plug the machine in",
+                ));
+            }
+        }
+    }
+    has_battery.then(|| passed("power_source", "running on AC power".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("{}\n", content)).unwrap();
+    }
+
+    #[test]
+    fn test_noisy_system_fails_every_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (cpu, governor) in [(0, "powersave"), (1, "performance"), (2, "schedutil")] {
+            write(
+                root,
+                &format!("sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor", cpu),
+                governor,
+            );
+        }
+        write(root, "sys/devices/system/cpu/intel_pstate/no_turbo", "0");
+        write(root, "proc/loadavg", "3.20 2.10 1.00 2/345 6789");
+        write(root, "proc/sys/kernel/randomize_va_space", "2");
+        write(root, "sys/devices/system/cpu/smt/active", "1");
+        write(root, "sys/class/power_supply/BAT0/type", "Battery");
+        write(root, "sys/class/power_supply/BAT0/status", "Discharging");
+
+        let checks = check_root(root);
+        assert_eq!(checks.len(), 6);
+        assert_eq!(warnings(&checks).count(), 6);
+        assert_eq!(
+            checks[0].detail,
+            "2 of 3 CPUs use the powersave/schedutil governor"
+        );
+        assert_eq!(
+            checks[2].detail,
+            "1-minute load average is 3.20 (1.07 per CPU, 3 online)"
+        );
+        assert!(checks.iter().all(|check| check.advice.is_some()));
+    }
+
+    #[test]
+    fn test_quiet_system_passes_and_missing_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+            "performance",
+        );
+        write(root, "sys/devices/system/cpu/cpufreq/boost", "0");
+        write(root, "proc/loadavg", "0.05 0.10 0.20 1/200 1234");
+        write(root, "proc/sys/kernel/randomize_va_space", "0");
+
+        let checks = check_root(root);
+        let names: Vec<&str> = checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["cpu_governor", "turbo_boost", "load_average", "aslr"]
+        );
+        assert_eq!(warnings(&checks).count(), 0);
+        assert!(check_root(&root.join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_load_is_relative_to_online_cpus() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "proc/loadavg", "3.20 2.10 1.00 2/345 6789");
+        write(root, "sys/devices/system/cpu/online", "0-15");
+        let check = load_average(root).unwrap();
+        assert!(check.ok, "{check:?}");
+        assert_eq!(
+            check.detail,
+            "1-minute load average is 3.20 (0.20 per CPU, 16 online)"
+        );
+
+        write(root, "sys/devices/system/cpu/online", "0-1,4,6-7");
+        assert_eq!(online_cpus(root), Some(5));
+        assert!(!load_average(root).unwrap().ok);
+
+        write(root, "sys/devices/system/cpu/online", "garbage");
+        assert_eq!(online_cpus(root), None);
+    }
+}
@@ -4,26 +4,38 @@
 //! two commits using interleaved execution and hypothesis testing.
 
 pub mod build;
+pub mod calibrate;
 pub mod cli;
 pub mod config;
+pub mod doctor;
 pub mod orchestrator;
 pub mod report;
+pub mod sched;
 pub mod source;
 
 // Re-export core types for convenience
 pub use hypobench_core::protocol;
 pub use hypobench_core::report::{
-    BenchmarkComparison, ConfigSnapshot, Report, ReportMetadata, SampleStats,
+    correct_secondary_tests, AllocationComparison, AllocationSummary, BenchmarkComparison,
+    ConfigSnapshot, Contamination, CountTest, HarnessPlacement, MetricComparison, OutputCheck,
+    QuantileComparison, Report, ReportMetadata, SampleQuantile, SampleStats, Stationarity,
+    SuiteSummary, SystemCheck,
+};
+pub use hypobench_core::stats::{
+    apply_bonferroni, BayesianTest, Bootstrap, BootstrapInterval, ChangeInterval, Equivalence,
+    LinearFit, Modality, Mode, Posterior, PowerAnalysis, Side, SlopeTest, StatisticalTest,
+    TestResult, Verdict, WelchTTest,
+};
+pub use report::{
+    GithubPrCommentReporter, HeadlineMetric, JsonReporter, ReportError, Reporter, TerminalReporter,
 };
-pub use hypobench_core::stats::{apply_bonferroni, Side, StatisticalTest, TestResult, WelchTTest};
-pub use report::{GithubPrCommentReporter, JsonReporter, ReportError, Reporter, TerminalReporter};
 
 // Re-export main types from this crate
 pub use build::BuildManager;
-pub use cli::{Cli, Command, ReportArgs, ReportFormat, RunArgs};
-pub use config::Config;
+pub use cli::{CalibrateArgs, Cli, Command, DoctorArgs, ReportArgs, ReportFormat, RunArgs};
+pub use config::{Config, TestMethod};
 pub use orchestrator::{
     run_with_urls, wait_for_health, BenchmarkSamples, HarnessHandle, Orchestrator,
-    OrchestratorError,
+    OrchestratorError, RunSample, Sampling,
 };
 pub use source::{GitWorktreeProvider, SourceProvider};
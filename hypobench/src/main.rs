@@ -1,13 +1,19 @@
 use anyhow::{Context, Result};
 use clap::Parser;
+use colored::Colorize;
+use hypobench::calibrate::Calibration;
+use hypobench::doctor;
+use hypobench::protocol::{HarnessInfo, AUTH_TOKEN_ENV};
 use hypobench::{
-    apply_bonferroni, run_with_urls, BenchmarkComparison, BuildManager, Cli, Command, Config,
-    ConfigSnapshot, GitWorktreeProvider, GithubPrCommentReporter, JsonReporter, Orchestrator,
-    Report, ReportArgs, ReportFormat, ReportMetadata, Reporter, RunArgs, SampleStats,
-    SourceProvider, StatisticalTest, TerminalReporter, WelchTTest,
+    apply_bonferroni, correct_secondary_tests, run_with_urls, AllocationComparison,
+    BenchmarkComparison, BuildManager, CalibrateArgs, Cli, Command, Config, ConfigSnapshot,
+    Contamination, DoctorArgs, GitWorktreeProvider, GithubPrCommentReporter, HarnessPlacement,
+    HeadlineMetric, JsonReporter, LinearFit, MetricComparison, Orchestrator, Report, ReportArgs,
+    ReportFormat, ReportMetadata, RunArgs, SampleStats, Sampling, SourceProvider, Stationarity,
+    SuiteSummary, SystemCheck, TerminalReporter, TestMethod, TestResult,
 };
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[tokio::main]
@@ -16,6 +22,8 @@ async fn main() -> Result<()> {
 
     match cli.command {
         Some(Command::Report(args)) => run_report_subcommand(args).await,
+        Some(Command::Doctor(args)) => run_doctor(args),
+        Some(Command::Calibrate(args)) => run_calibrate(args).await,
         Some(Command::Run(args)) => run_benchmarks(args).await,
         None => run_benchmarks(cli.run).await,
     }
@@ -31,31 +39,147 @@ async fn run_benchmarks(run_args: RunArgs) -> Result<()> {
         eprintln!("Configuration: {:?}", config);
     }
 
+    let system_checks = preflight(run_args.strict)?;
+    let (baseline_placement, candidate_placement) =
+        hypobench::sched::resolve(&config.orchestration).context("Invalid harness placement")?;
     let samples = if run_args.is_manual_mode() {
+        if !baseline_placement.is_default() || !candidate_placement.is_default() {
+            eprintln!(
+                "Warning: launcher, environment, CPU pinning and scheduling options only apply to harnesses hypobench spawns; ignoring them in manual mode"
+            );
+        }
         run_manual_mode(&run_args, &config).await?
     } else {
-        run_automatic_mode(&run_args, &config).await?
+        for (label, placement) in [
+            ("Baseline", &baseline_placement),
+            ("Candidate", &candidate_placement),
+        ] {
+            if !placement.is_default() {
+                eprintln!("{} placement: {}", label, placement.summary());
+            }
+        }
+        run_automatic_mode(
+            &run_args,
+            &config,
+            &baseline_placement,
+            &candidate_placement,
+        )
+        .await?
     };
+    // Only spawned harnesses are placed, so only their placement is recorded.
+    let recorded = |placement: HarnessPlacement| {
+        (!run_args.is_manual_mode() && !placement.is_default()).then_some(placement)
+    };
+    let baseline_placement = recorded(baseline_placement);
+    let candidate_placement = recorded(candidate_placement);
+
+    // One harness per side per bench target; every benchmark it ran carries its info.
+    let mut baseline_harnesses = Vec::new();
+    let mut candidate_harnesses = Vec::new();
+    for sample in &samples {
+        if let Some(info) = &sample.baseline_info {
+            if !baseline_harnesses.contains(info) {
+                baseline_harnesses.push(info.clone());
+            }
+        }
+        if let Some(info) = &sample.candidate_info {
+            if !candidate_harnesses.contains(info) {
+                candidate_harnesses.push(info.clone());
+            }
+        }
+    }
 
     eprintln!("Analyzing results...");
-    let test = WelchTTest::new(config.hypothesis.confidence_level)
-        .with_minimum_effect_size(config.hypothesis.minimum_effect_size);
+    let test = config.hypothesis.statistical_test();
     let mut comparisons = Vec::new();
-
-    for sample in samples {
-        let test_result = test.analyze(&sample.baseline_samples, &sample.candidate_samples);
-
-        let baseline_stats = calculate_stats(&sample.baseline_samples);
-        let candidate_stats = calculate_stats(&sample.candidate_samples);
-
+    let mut suite_inputs = Vec::new();
+
+    for mut sample in samples {
+        // Check for drift before any samples are rejected, while baseline
+        // and candidate samples still pair up in the order they were taken.
+        let stationarity = Stationarity::analyze(
+            &nanos(&sample.baseline_samples),
+            &nanos(&sample.candidate_samples),
+            1.0 - config.hypothesis.confidence_level,
+        );
+        if let Some(stationarity) = stationarity.filter(|s| !s.stationary()) {
+            eprintln!(
+                "  {}: samples drifted during the run: {}",
+                sample.name,
+                stationarity.findings().join("; ")
+            );
+        }
+        let (baseline_flagged, candidate_flagged) = sample.contaminated();
+        let contamination =
+            (!baseline_flagged.is_empty() || !candidate_flagged.is_empty()).then(|| {
+                let rejected = config.orchestration.reject_contaminated
+                    && sample.reject(&baseline_flagged, &candidate_flagged);
+                eprintln!(
+                    "  {}: {} baseline / {} candidate sample(s) had context-switch spikes{}",
+                    sample.name,
+                    baseline_flagged.len(),
+                    candidate_flagged.len(),
+                    if rejected { ", rejected" } else { "" }
+                );
+                Contamination {
+                    baseline_flagged: baseline_flagged.len(),
+                    candidate_flagged: candidate_flagged.len(),
+                    rejected,
+                }
+            });
+        let test_result = config.hypothesis.compare(test.as_ref(), &sample);
+        let baseline_ns = nanos(&sample.baseline_samples);
+        let candidate_ns = nanos(&sample.candidate_samples);
+
+        let quantiles = &config.hypothesis.quantiles;
+        let mut baseline_stats = SampleStats::from_samples(&baseline_ns, quantiles);
+        let mut candidate_stats = SampleStats::from_samples(&candidate_ns, quantiles);
+        // Linearly sampled benchmarks compare fitted per-iteration slopes
+        // instead of per-sample means, which carry each sample's overhead.
+        if let Some((baseline_runs, candidate_runs)) = config.hypothesis.slope_runs(&sample) {
+            baseline_stats.fit = LinearFit::fit(&baseline_runs);
+            candidate_stats.fit = LinearFit::fit(&candidate_runs);
+        }
+        let allocations = sample.has_allocations().then(|| {
+            AllocationComparison::analyze(
+                test.as_ref(),
+                &sample.baseline_allocations,
+                &sample.candidate_allocations,
+                sample.iterations,
+            )
+        });
+        let metrics = sample
+            .counter_names()
+            .into_iter()
+            .map(|name| {
+                let (baseline, candidate) = sample.counter_per_iter(&name);
+                MetricComparison::analyze(test.as_ref(), name, &baseline, &candidate)
+            })
+            .collect();
+
+        suite_inputs.push((baseline_ns, candidate_ns, config.suite.weight(&sample.name)));
         comparisons.push(BenchmarkComparison {
             name: sample.name,
             baseline_stats,
             candidate_stats,
             test_result,
+            allocations,
+            metrics,
+            contamination,
+            output_check: sample.output_check,
+            // Compared below, once the number of benchmarks is known.
+            quantiles: Vec::new(),
+            stationarity,
         });
     }
 
+    for (comparison, (baseline, candidate, _)) in comparisons.iter_mut().zip(&suite_inputs) {
+        comparison.quantiles =
+            config
+                .hypothesis
+                .compare_quantiles(baseline, candidate, suite_inputs.len());
+    }
+
     // Apply Bonferroni multiple-comparisons correction across the whole family.
     // Each test's effective α becomes (1 - confidence_level) / N, controlling
     // family-wise false-positive rate at the nominal level rather than letting
@@ -67,6 +191,7 @@ async fn run_benchmarks(run_args: RunArgs) -> Result<()> {
         for (c, updated) in comparisons.iter_mut().zip(results) {
             c.test_result = updated;
         }
+        correct_secondary_tests(&mut comparisons, family_alpha);
         eprintln!(
             "Applied Bonferroni correction: effective α = {:.2e} across {} benchmarks",
             family_alpha / comparisons.len() as f64,
@@ -74,15 +199,43 @@ async fn run_benchmarks(run_args: RunArgs) -> Result<()> {
         );
     }
 
-    let report = build_report(&run_args, &config, comparisons);
-    render(&run_args.format, &report)?;
+    let suite_inputs: Vec<(&[f64], &[f64], f64)> = suite_inputs
+        .iter()
+        .map(|(baseline, candidate, weight)| (&baseline[..], &candidate[..], *weight))
+        .collect();
+    let suite = SuiteSummary::compute(
+        &suite_inputs,
+        config.hypothesis.confidence_level,
+        !config.suite.weights.is_empty(),
+        &config.hypothesis.bootstrap,
+    );
+
+    let report = build_report(
+        &run_args,
+        &config,
+        comparisons,
+        suite,
+        baseline_harnesses,
+        candidate_harnesses,
+        baseline_placement,
+        candidate_placement,
+        system_checks,
+    );
+    render(&run_args.format, run_args.metric, &report)?;
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_report(
     run_args: &RunArgs,
     config: &Config,
     comparisons: Vec<BenchmarkComparison>,
+    suite: Option<SuiteSummary>,
+    baseline_harnesses: Vec<HarnessInfo>,
+    candidate_harnesses: Vec<HarnessInfo>,
+    baseline_placement: Option<HarnessPlacement>,
+    candidate_placement: Option<HarnessPlacement>,
+    system_checks: Vec<SystemCheck>,
 ) -> Report {
     let secs = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -109,9 +262,27 @@ fn build_report(
                 minimum_effect_size: config.hypothesis.minimum_effect_size,
                 sample_size: config.orchestration.sample_size,
                 correct_multiple_comparisons: config.hypothesis.correct_multiple_comparisons,
+                equivalence_margin: config.hypothesis.equivalence_margin,
+                // Only the Welch test on flat samples computes minimum
+                // detectable effects.
+                power: (config.hypothesis.method == TestMethod::Welch
+                    && config.orchestration.sampling == Sampling::Flat)
+                    .then_some(config.hypothesis.power),
+                method: Some(config.hypothesis.method.as_str().to_string()),
+                bootstrap: Some(config.hypothesis.bootstrap),
+                sampling: Some(config.orchestration.sampling.as_str().to_string()),
+                // The Bayesian test reports a credible interval instead.
+                change_interval: (config.hypothesis.method == TestMethod::Welch)
+                    .then(|| config.hypothesis.change_interval.as_str().to_string()),
             },
+            baseline_harnesses,
+            candidate_harnesses,
+            baseline_placement,
+            candidate_placement,
+            system_checks,
         },
         comparisons,
+        suite,
     }
 }
 
@@ -143,21 +314,25 @@ fn civil_from_days(z: i64) -> (i32, u32, u32) {
     (y as i32, m as u32, d as u32)
 }
 
-fn render(format: &ReportFormat, report: &Report) -> Result<()> {
+fn render(format: &ReportFormat, metric: HeadlineMetric, report: &Report) -> Result<()> {
+    if !metric.available_in(report) {
+        anyhow::bail!(
+            "the report has no {} for every benchmark; add it to `quantiles` under [hypothesis] and rerun",
+            metric
+        );
+    }
     let stdout = std::io::stdout();
     let mut out = stdout.lock();
     match format {
         ReportFormat::Terminal => {
-            // The TerminalReporter trait takes only the comparisons slice today.
-            // Metadata is visible to the user via the CI logs / step summary; if
-            // we later want it rendered inline, we can give TerminalReporter its
-            // own write_report(&Report) method.
             TerminalReporter::new()
-                .report(&report.comparisons)
+                .with_metric(metric)
+                .write_report(report, &mut out)
                 .context("terminal report failed")?;
         }
         ReportFormat::GithubPrComment => {
             GithubPrCommentReporter::new()
+                .with_metric(metric)
                 .write(report, &mut out)
                 .context("github-pr-comment report failed")?;
         }
@@ -183,7 +358,53 @@ async fn run_report_subcommand(args: ReportArgs) -> Result<()> {
     };
 
     let report: Report = serde_json::from_str(&json).context("parsing JSON report")?;
-    render(&args.format, &report)?;
+    render(&args.format, args.metric, &report)?;
+    Ok(())
+}
+
+/// Check the machine for noise before running, warning about what was found.
+/// With `strict`, any finding is an error.
+fn preflight(strict: bool) -> Result<Vec<SystemCheck>> {
+    let checks = doctor::check_system();
+    let warnings: Vec<&SystemCheck> = doctor::warnings(&checks).collect();
+    for check in &warnings {
+        eprintln!(
+            "Warning: {} (fix: {})",
+            check.detail,
+            check.advice.as_deref().unwrap_or("-")
+        );
+    }
+    if strict && !warnings.is_empty() {
+        anyhow::bail!(
+            "Preflight found {} source(s) of system noise; fix them or drop --strict (see `hypobench doctor`)",
+            warnings.len()
+        );
+    }
+    Ok(checks)
+}
+
+/// Print every preflight check; with `--strict`, fail if any did.
+fn run_doctor(args: DoctorArgs) -> Result<()> {
+    let checks = doctor::check_system();
+    if checks.is_empty() {
+        println!("No system checks are available on this platform.");
+        return Ok(());
+    }
+    for check in &checks {
+        let status = if check.ok {
+            "ok  ".green()
+        } else {
+            "warn".yellow()
+        };
+        println!("{} {}: {}", status, check.name, check.detail);
+        if let Some(advice) = &check.advice {
+            println!("     fix: {}", advice);
+        }
+    }
+    let failed = doctor::warnings(&checks).count();
+    if args.strict && failed > 0 {
+        anyhow::bail!("{} system check(s) failed", failed);
+    }
     Ok(())
 }
 
@@ -205,14 +426,23 @@ async fn run_manual_mode(
     eprintln!("  Baseline URL: {}", baseline_url);
     eprintln!("  Candidate URL: {}", candidate_url);
 
+    // Harnesses started with a token expect us to present the same one.
+    let auth_token = std::env::var(AUTH_TOKEN_ENV).ok();
+
     let samples = run_with_urls(
         baseline_url,
         candidate_url,
         Duration::from_millis(config.network.harness_timeout_ms),
         config.orchestration.sample_size,
         Duration::from_millis(config.orchestration.interleave_interval_ms),
+        config.orchestration.block_size,
         Duration::from_millis(config.orchestration.target_sample_ms),
         config.orchestration.max_calibration_iters,
+        Duration::from_millis(config.network.claim_ttl_ms),
+        run_args.force_claim,
+        auth_token.as_deref(),
+        config.orchestration.perf_counters,
+        config.orchestration.sampling,
     )
     .await
     .context("Failed to run benchmarks with URLs")?;
@@ -224,6 +454,8 @@ async fn run_manual_mode(
 async fn run_automatic_mode(
     run_args: &RunArgs,
     config: &Config,
+    baseline_placement: &HarnessPlacement,
+    candidate_placement: &HarnessPlacement,
 ) -> Result<Vec<hypobench::BenchmarkSamples>> {
     let baseline = run_args
         .baseline
@@ -285,16 +517,13 @@ async fn run_automatic_mode(
             .context("Failed to build candidate")?;
 
         eprintln!("Running benchmarks...");
-        let orchestrator = Orchestrator::new(
+        let orchestrator = new_orchestrator(
+            config,
             baseline_build.binary_path,
             candidate_build.binary_path,
-            config.network.base_port,
-            Duration::from_millis(config.network.harness_timeout_ms),
-            config.orchestration.sample_size,
-            Duration::from_millis(config.orchestration.interleave_interval_ms),
-            Duration::from_millis(config.orchestration.target_sample_ms),
-            config.orchestration.max_calibration_iters,
             run_args.harness_output,
+            baseline_placement,
+            candidate_placement,
         );
 
         all_samples.extend(
@@ -316,16 +545,13 @@ async fn run_automatic_mode(
                 .with_context(|| format!("Failed to build candidate for bench '{}'", bench_name))?;
 
             eprintln!("Running benchmarks for: {}", bench_name);
-            let orchestrator = Orchestrator::new(
+            let orchestrator = new_orchestrator(
+                config,
                 baseline_build.binary_path,
                 candidate_build.binary_path,
-                config.network.base_port,
-                Duration::from_millis(config.network.harness_timeout_ms),
-                config.orchestration.sample_size,
-                Duration::from_millis(config.orchestration.interleave_interval_ms),
-                Duration::from_millis(config.orchestration.target_sample_ms),
-                config.orchestration.max_calibration_iters,
                 run_args.harness_output,
+                baseline_placement,
+                candidate_placement,
             );
 
             all_samples.extend(
@@ -345,30 +571,157 @@ async fn run_automatic_mode(
     Ok(all_samples)
 }
 
-fn calculate_stats(samples: &[Duration]) -> SampleStats {
-    let ns_values: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
-    let n = ns_values.len();
+/// An orchestrator comparing `baseline_binary` with `candidate_binary`, set up
+/// from `config`.
+fn new_orchestrator(
+    config: &Config,
+    baseline_binary: PathBuf,
+    candidate_binary: PathBuf,
+    show_output: bool,
+    baseline_placement: &HarnessPlacement,
+    candidate_placement: &HarnessPlacement,
+) -> Orchestrator {
+    Orchestrator::new(
+        baseline_binary,
+        candidate_binary,
+        config.network.base_port,
+        Duration::from_millis(config.network.harness_timeout_ms),
+        config.orchestration.sample_size,
+        Duration::from_millis(config.orchestration.interleave_interval_ms),
+        config.orchestration.block_size,
+        Duration::from_millis(config.orchestration.target_sample_ms),
+        config.orchestration.max_calibration_iters,
+        show_output,
+        Duration::from_millis(config.network.claim_ttl_ms),
+        config.network.transport,
+        config.orchestration.perf_counters,
+        config.orchestration.sampling,
+        baseline_placement.clone(),
+        candidate_placement.clone(),
+    )
+}
 
-    let mean = ns_values.iter().sum::<f64>() / n as f64;
-    let variance = ns_values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
-    let std_dev = variance.sqrt();
+/// Run one commit against itself `repeats` times and report how often that
+/// looked like a significant change, and how small a change it could detect.
+async fn run_calibrate(args: CalibrateArgs) -> Result<()> {
+    let mut config = Config::load_or_default()?;
+    if let Some(sample_size) = args.sample_size {
+        config.orchestration.sample_size = sample_size;
+    }
+    preflight(false)?;
+    let (baseline_placement, candidate_placement) =
+        hypobench::sched::resolve(&config.orchestration).context("Invalid harness placement")?;
 
-    let min = samples
-        .iter()
-        .map(|d| d.as_nanos() as u64)
-        .min()
-        .unwrap_or(0);
-    let max = samples
-        .iter()
-        .map(|d| d.as_nanos() as u64)
-        .max()
-        .unwrap_or(0);
+    eprintln!("Preparing source...");
+    let source_provider = GitWorktreeProvider::new()?;
+    let source_path = source_provider
+        .prepare_source(&args.commit)
+        .context("Failed to prepare source")?;
+    // Clean up whether or not the runs succeed.
+    let runs = run_aa_repeats(
+        &args,
+        &config,
+        source_path,
+        &baseline_placement,
+        &candidate_placement,
+    )
+    .await;
+    eprintln!("Cleaning up...");
+    let cleanup = source_provider
+        .cleanup()
+        .context("Failed to cleanup sources");
+    let runs = runs?;
+    cleanup?;
+
+    let calibration = Calibration::from_runs(
+        &runs,
+        config.hypothesis.confidence_level,
+        config.hypothesis.minimum_effect_size,
+    );
+    calibration.write(&mut std::io::stdout().lock())?;
+    Ok(())
+}
+
+/// Build `source_path` and run it against itself `args.repeats` times,
+/// analyzing each run as a real one would be.
+async fn run_aa_repeats(
+    args: &CalibrateArgs,
+    config: &Config,
+    source_path: PathBuf,
+    baseline_placement: &HarnessPlacement,
+    candidate_placement: &HarnessPlacement,
+) -> Result<Vec<Vec<(String, TestResult)>>> {
+    let build_path = match &args.project_path {
+        Some(p) => source_path.join(p),
+        None => source_path,
+    };
+
+    eprintln!("Building benchmarks...");
+    let builder = BuildManager::new(
+        config.build.profile.clone(),
+        config.build.cargo_flags.clone(),
+    );
+    let bench_targets = if !args.bench.is_empty() {
+        args.bench.clone()
+    } else {
+        config.build.bench_targets.clone()
+    };
+    let binaries = if bench_targets.is_empty() {
+        vec![builder.build(&build_path, "calibrate")?.binary_path]
+    } else {
+        bench_targets
+            .iter()
+            .map(|bench_name| {
+                builder
+                    .build_bench(
+                        &build_path,
+                        bench_name,
+                        &format!("calibrate {}", bench_name),
+                    )
+                    .map(|build| build.binary_path)
+                    .with_context(|| format!("Failed to build bench '{}'", bench_name))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
 
-    SampleStats {
-        mean_ns: mean,
-        std_dev_ns: std_dev,
-        min_ns: min,
-        max_ns: max,
-        sample_count: n,
+    let test = config.hypothesis.statistical_test();
+    let mut runs = Vec::new();
+    for repeat in 1..=args.repeats {
+        eprintln!("A/A run {}/{}...", repeat, args.repeats);
+        let mut results = Vec::new();
+        for binary in &binaries {
+            let orchestrator = new_orchestrator(
+                config,
+                binary.clone(),
+                binary.clone(),
+                args.harness_output,
+                baseline_placement,
+                candidate_placement,
+            );
+            for sample in orchestrator
+                .run()
+                .await
+                .context("Failed to run benchmarks")?
+            {
+                // Analyze exactly as a real run would, slopes included.
+                let result = config.hypothesis.compare(test.as_ref(), &sample);
+                results.push((sample.name, result));
+            }
+        }
+        // Correct exactly as a real run would, so the rate is comparable.
+        if config.hypothesis.correct_multiple_comparisons && results.len() > 1 {
+            let mut corrected: Vec<_> = results.iter().map(|(_, r)| r.clone()).collect();
+            apply_bonferroni(&mut corrected, 1.0 - config.hypothesis.confidence_level);
+            for ((_, result), updated) in results.iter_mut().zip(corrected) {
+                *result = updated;
+            }
+        }
+        runs.push(results);
     }
+    Ok(runs)
+}
+
+/// Durations in nanoseconds.
+fn nanos(samples: &[Duration]) -> Vec<f64> {
+    samples.iter().map(|d| d.as_nanos() as f64).collect()
 }
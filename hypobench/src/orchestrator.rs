@@ -3,21 +3,41 @@
 //! The orchestrator spawns baseline and candidate harness processes, manages their
 //! lifecycle, and collects interleaved benchmark samples for statistical comparison.
 
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
 
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command as TokioCommand;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{oneshot, Mutex};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use uuid::Uuid;
 
 use hypobench_core::protocol::{
-    BenchmarkListResponse, ClaimRequest, ClaimResponse, HealthResponse, ReleaseRequest,
-    RunIterationRequest, RunIterationResponse, ShutdownResponse, CLAIM_HEADER, PROTOCOL_VERSION,
+    negotiate_version, parse_listen_announcement, supported_versions, AllocationStats,
+    BenchmarkListResponse, ClaimRequest, ClaimResponse, Endpoint, HarnessInfo, HealthResponse,
+    HeartbeatRequest, HeartbeatResponse, PipeRequest, PipeResponse, ReleaseRequest, ResourceUsage,
+    RunBatchRequest, RunBatchResponse, RunIterationRequest, RunIterationResponse, ShutdownResponse,
+    Transport, VerifyResponse, AUTH_TOKEN_ENV, BIND_ADDR_ENV, CLAIM_HEADER, MIN_PROTOCOL_VERSION,
+    PORT_ENV, PROTOCOL_VERSION, TRANSPORT_ENV,
 };
+use hypobench_core::report::{HarnessPlacement, OutputCheck};
+
+use crate::sched;
+
+/// How long [`HarnessHandle::spawn`] waits for an ephemeral-port announcement.
+const DEFAULT_ANNOUNCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long a single request to a harness may take.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Errors that can occur during orchestration.
 #[derive(Debug, Error)]
@@ -61,36 +81,383 @@ pub enum OrchestratorError {
     #[error("Failed to claim harness: {0}")]
     ClaimError(String),
 
-    /// Harness reports a protocol version this orchestrator cannot talk to.
+    /// Harness and orchestrator have no protocol version in common.
     #[error(
-        "Protocol mismatch at {url}: orchestrator speaks v{expected}, harness speaks v{actual}. \
-         Update `hypobench-harness` in the benchmarked project to a version that \
-         implements protocol v{expected} (typically by matching the `hypobench` \
-         CLI version), or downgrade the orchestrator to v{actual}."
+        "Protocol mismatch at {url}: orchestrator speaks v{min}-v{max}, harness speaks \
+         v{harness_min}-v{harness_max}. Update `hypobench-harness` in the benchmarked \
+         project to a version that implements protocol v{min} or newer (typically by \
+         matching the `hypobench` CLI version), or use an orchestrator that still \
+         speaks v{harness_max}."
     )]
     ProtocolVersionMismatch {
         url: String,
-        expected: u32,
-        actual: u32,
+        min: u32,
+        max: u32,
+        harness_min: u32,
+        harness_max: u32,
     },
+
+    /// Spawned harness never announced the ephemeral port it bound.
+    #[error(
+        "Harness {binary} did not announce its listen address within {timeout_secs}s. \
+         Harnesses built against an older `hypobench-harness` don't print the \
+         announcement; set `network.base_port` to run them on fixed ports."
+    )]
+    AnnounceTimeout { binary: String, timeout_secs: u64 },
+
+    /// Harness rejected our bearer token (or we didn't send one).
+    #[error(
+        "Harness at {url} rejected the request as unauthorized. Set {env} to the \
+         token the harness was started with."
+    )]
+    Unauthorized { url: String, env: &'static str },
 }
 
 /// Handle to a running harness process (spawned by us).
 pub struct HarnessHandle {
-    /// The child process (None for remote harnesses, uses std::process).
+    /// The child process (None for remote harnesses).
     process: Option<Child>,
-    /// Tokio child process (for async output streaming).
-    tokio_process: Option<tokio::process::Child>,
-    /// Base URL for the harness.
-    base_url: String,
-    /// HTTP client for communication.
-    client: reqwest::Client,
+    /// How requests reach the harness.
+    connection: Connection,
     /// Whether this is a managed process (spawned by us) or remote.
     is_managed: bool,
-    /// Output streaming tasks (if enabled).
+    /// Tasks draining (and optionally echoing) the child's stdout/stderr.
     output_tasks: Vec<JoinHandle<()>>,
     /// Claim nonce for exclusive access (None if not claimed).
     claim_nonce: Option<String>,
+    /// Background task keeping the claim lease alive (if the harness issued one).
+    heartbeat_task: Option<JoinHandle<()>>,
+    /// Set by the heartbeat task once the harness reports the lease lost.
+    claim_lost: Arc<AtomicBool>,
+    /// Protocol version negotiated by [`wait_for_health`]; newer requests
+    /// are emulated on older harnesses.
+    protocol_version: u32,
+    /// Whether run requests ask the harness for perf counters.
+    perf_counters: bool,
+}
+
+/// Responses awaited from a piped harness, keyed by request id.
+///
+/// `None` once the harness's stdout has closed, so new calls fail fast
+/// instead of waiting out the request timeout.
+type PendingResponses = Arc<StdMutex<Option<HashMap<u64, oneshot::Sender<PipeResponse>>>>>;
+
+/// Transport-specific half of a [`HarnessHandle`].
+///
+/// Cheap to clone, so the heartbeat task can share it.
+#[derive(Clone)]
+enum Connection {
+    /// HTTP to a base URL, for spawned HTTP harnesses and manual mode.
+    Http {
+        base_url: String,
+        client: reqwest::Client,
+    },
+    /// Newline-delimited JSON over the stdin/stdout of a harness we spawned.
+    Pipe(Arc<PipeConnection>),
+}
+
+/// Client side of [`Transport::Pipe`].
+struct PipeConnection {
+    /// Describes the harness in messages (`pipe:<binary>`).
+    location: String,
+    /// The child's stdin; each request is written as one line.
+    stdin: Mutex<ChildStdin>,
+    /// Shared with the stdout task, which completes these as responses arrive.
+    pending: PendingResponses,
+    /// Id for the next request.
+    next_id: AtomicU64,
+}
+
+impl Connection {
+    /// Where the harness lives, for messages.
+    fn location(&self) -> &str {
+        match self {
+            Connection::Http { base_url, .. } => base_url,
+            Connection::Pipe(pipe) => &pipe.location,
+        }
+    }
+
+    /// Send `body` to `endpoint`, returning the status and JSON response body.
+    async fn call(
+        &self,
+        endpoint: Endpoint,
+        claim: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<(u16, serde_json::Value), OrchestratorError> {
+        match self {
+            Connection::Http { base_url, client } => {
+                let url = format!("{}{}", base_url, endpoint.path());
+                let mut request = if endpoint.is_post() {
+                    client.post(&url)
+                } else {
+                    client.get(&url)
+                };
+                if let Some(nonce) = claim {
+                    request = request.header(CLAIM_HEADER, nonce);
+                }
+                if let Some(body) = &body {
+                    request = request.json(body);
+                }
+                let response = request.send().await?;
+                let status = response.status().as_u16();
+                let bytes = response.bytes().await?;
+                Ok((status, parse_body(&bytes)))
+            }
+            Connection::Pipe(pipe) => pipe.call(endpoint, claim, body).await,
+        }
+    }
+}
+
+impl PipeConnection {
+    /// Write one request line and wait for the matching response line.
+    async fn call(
+        &self,
+        endpoint: Endpoint,
+        claim: Option<&str>,
+        body: Option<serde_json::Value>,
+    ) -> Result<(u16, serde_json::Value), OrchestratorError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (response_tx, response_rx) = oneshot::channel();
+        match self.pending.lock().unwrap().as_mut() {
+            Some(pending) => {
+                pending.insert(id, response_tx);
+            }
+            None => return Err(self.closed()),
+        }
+
+        let request = PipeRequest {
+            id,
+            endpoint,
+            claim: claim.map(str::to_string),
+            body: body.unwrap_or_default(),
+        };
+        let mut line = serde_json::to_vec(&request).map_err(|e| {
+            OrchestratorError::HarnessError(format!("Failed to encode pipe request: {}", e))
+        })?;
+        line.push(b'\n');
+
+        let written = {
+            let mut stdin = self.stdin.lock().await;
+            match stdin.write_all(&line).await {
+                Ok(()) => stdin.flush().await,
+                Err(e) => Err(e),
+            }
+        };
+        if let Err(e) = written {
+            self.forget(id);
+            return Err(OrchestratorError::HarnessError(format!(
+                "Failed to write to {}: {}",
+                self.location, e
+            )));
+        }
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, response_rx).await {
+            Ok(Ok(response)) => Ok((response.status, response.body)),
+            Ok(Err(_)) => Err(self.closed()),
+            Err(_) => {
+                self.forget(id);
+                Err(OrchestratorError::HarnessError(format!(
+                    "{} did not answer {} within {}s",
+                    self.location,
+                    endpoint.path(),
+                    REQUEST_TIMEOUT.as_secs()
+                )))
+            }
+        }
+    }
+
+    /// Stop waiting for the response to request `id`.
+    fn forget(&self, id: u64) {
+        if let Some(pending) = self.pending.lock().unwrap().as_mut() {
+            pending.remove(&id);
+        }
+    }
+
+    fn closed(&self) -> OrchestratorError {
+        OrchestratorError::HarnessError(format!("{} closed its stdout", self.location))
+    }
+}
+
+/// Interpret a response body as JSON.
+///
+/// Axum's own rejections (e.g. a malformed request body) are plain text;
+/// those are wrapped as `{"error": ...}` like every other failure response.
+fn parse_body(bytes: &[u8]) -> serde_json::Value {
+    match serde_json::from_slice(bytes) {
+        Ok(body) => body,
+        Err(_) if bytes.is_empty() => serde_json::Value::Null,
+        Err(_) => serde_json::json!({ "error": String::from_utf8_lossy(bytes) }),
+    }
+}
+
+/// Decode a response body as `T`.
+///
+/// A body of the wrong shape is usually an error response (e.g. a 403 from
+/// the claim check), so its `error` message is surfaced when present.
+fn decode<T: DeserializeOwned>(
+    endpoint: Endpoint,
+    status: u16,
+    body: serde_json::Value,
+) -> Result<T, OrchestratorError> {
+    T::deserialize(&body).map_err(|e| {
+        OrchestratorError::HarnessError(match body.get("error").and_then(|e| e.as_str()) {
+            Some(message) => format!("{} failed ({}): {}", endpoint.path(), status, message),
+            None => format!("Malformed {} response ({}): {}", endpoint.path(), status, e),
+        })
+    })
+}
+
+/// A freshly spawned harness process, before we know how to reach it.
+struct SpawnedChild {
+    child: Child,
+    output_tasks: Vec<JoinHandle<()>>,
+    /// Completes with the address from the harness's listen announcement.
+    announced: oneshot::Receiver<SocketAddr>,
+}
+
+/// A command running `binary` under `placement`'s launcher, if any, with its
+/// extra environment. Callers set the protocol variables afterwards, so those
+/// can't be overridden.
+fn harness_command(binary: &Path, placement: &HarnessPlacement) -> Command {
+    let mut command = match placement.launcher.split_first() {
+        Some((launcher, args)) => {
+            let mut command = Command::new(launcher);
+            command.args(args).arg(binary);
+            command
+        }
+        None => Command::new(binary),
+    };
+    command.envs(&placement.env);
+    command
+}
+
+/// A pipe response on `line`, with any benchmark output before it. A
+/// benchmark that prints without a trailing newline leaves the next response
+/// on the end of its output's line.
+fn parse_pipe_response(line: &str) -> Option<(&str, PipeResponse)> {
+    if let Ok(response) = serde_json::from_str(line) {
+        return Some(("", response));
+    }
+    line.match_indices("{\"id\":").find_map(|(at, _)| {
+        let response = serde_json::from_str(&line[at..]).ok()?;
+        Some((&line[..at], response))
+    })
+}
+
+/// Spawn `command` with piped output, draining stdout/stderr in background tasks.
+///
+/// Both pipes are always drained so a chatty benchmark can't block on a
+/// full pipe buffer; lines are only echoed when a label was given. Stdout is
+/// also watched for a listen announcement (sent on the returned channel) and,
+/// when `pending` is given, for pipe-transport responses.
+fn spawn_child(
+    mut command: Command,
+    binary: &Path,
+    output_label: Option<&str>,
+    pending: Option<PendingResponses>,
+    placement: &HarnessPlacement,
+) -> Result<SpawnedChild, OrchestratorError> {
+    sched::apply(&mut command, placement);
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            let hint = if e.kind() == std::io::ErrorKind::PermissionDenied
+                && sched::needs_privilege(placement)
+            {
+                format!(
+                    " (placement {} needs CAP_SYS_NICE or root)",
+                    placement.summary()
+                )
+            } else {
+                String::new()
+            };
+            let via = match placement.launcher.first() {
+                Some(launcher) => format!(" via {}", launcher),
+                None => String::new(),
+            };
+            OrchestratorError::SpawnError(format!(
+                "Failed to spawn {}{}: {}{}",
+                binary.display(),
+                via,
+                e,
+                hint
+            ))
+        })?;
+
+    let mut output_tasks = Vec::new();
+    let (addr_tx, addr_rx) = oneshot::channel();
+
+    // Spawn task to stream stdout, watching for the listen announcement
+    if let Some(stdout) = child.stdout.take() {
+        let label = output_label.map(str::to_string);
+        output_tasks.push(tokio::spawn(async move {
+            let mut addr_tx = Some(addr_tx);
+            let reader = BufReader::new(stdout);
+            let mut lines = reader.lines();
+            while let Ok(Some(mut line)) = lines.next_line().await {
+                if let Some(pending) = &pending {
+                    if let Some((output, response)) = parse_pipe_response(&line) {
+                        let waiter = pending
+                            .lock()
+                            .unwrap()
+                            .as_mut()
+                            .and_then(|pending| pending.remove(&response.id));
+                        if let Some(waiter) = waiter {
+                            let _ = waiter.send(response);
+                        }
+                        if output.is_empty() {
+                            continue;
+                        }
+                        line = output.to_string();
+                    }
+                }
+                if let Some(addr) = parse_listen_announcement(&line) {
+                    if pending.is_some() {
+                        eprintln!(
+                            "Warning: harness ignored {}={} and is serving HTTP on {}; \
+                             it predates the pipe transport, so set network.transport = \"http\"",
+                            TRANSPORT_ENV,
+                            Transport::Pipe.as_str(),
+                            addr
+                        );
+                    }
+                    if let Some(tx) = addr_tx.take() {
+                        let _ = tx.send(addr);
+                    }
+                }
+                if let Some(label) = &label {
+                    eprintln!("[{} stdout] {}", label, line);
+                }
+            }
+            // Fail outstanding and future pipe calls now that no answer can come.
+            if let Some(pending) = &pending {
+                pending.lock().unwrap().take();
+            }
+        }));
+    }
+
+    // Spawn task to stream stderr
+    if let Some(stderr) = child.stderr.take() {
+        let label = output_label.map(str::to_string);
+        output_tasks.push(tokio::spawn(async move {
+            let reader = BufReader::new(stderr);
+            let mut lines = reader.lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(label) = &label {
+                    eprintln!("[{} stderr] {}", label, line);
+                }
+            }
+        }));
+    }
+
+    Ok(SpawnedChild {
+        child,
+        output_tasks,
+        announced: addr_rx,
+    })
 }
 
 impl HarnessHandle {
@@ -99,113 +466,166 @@ impl HarnessHandle {
     /// # Arguments
     ///
     /// * `binary` - Path to the harness binary
-    /// * `port` - Port for the harness to listen on
+    /// * `port` - Port for the harness to listen on, or `None` to let the
+    ///   harness pick a free port and announce it on stdout
     ///
     /// # Errors
     ///
     /// Returns an error if the process cannot be spawned.
-    pub async fn spawn(binary: &Path, port: u16) -> Result<Self, OrchestratorError> {
-        Self::spawn_with_output(binary, port, None).await
+    pub async fn spawn(binary: &Path, port: Option<u16>) -> Result<Self, OrchestratorError> {
+        Self::spawn_with_output(
+            binary,
+            port,
+            None,
+            DEFAULT_ANNOUNCE_TIMEOUT,
+            None,
+            &HarnessPlacement::default(),
+        )
+        .await
     }
 
     /// Spawn a new harness process with optional output streaming.
     ///
+    /// With `port: None` the harness is told to bind port 0, and this waits up
+    /// to `announce_timeout` for the
+    /// [`LISTEN_ANNOUNCEMENT_PREFIX`](hypobench_core::protocol::LISTEN_ANNOUNCEMENT_PREFIX) line on its
+    /// stdout to learn the port it actually got.
+    ///
     /// # Arguments
     ///
     /// * `binary` - Path to the harness binary
-    /// * `port` - Port for the harness to listen on
+    /// * `port` - Port for the harness to listen on, or `None` for an ephemeral port
     /// * `output_label` - If Some, stream stdout/stderr with this prefix to stderr
+    /// * `announce_timeout` - How long to wait for the harness to announce its port
+    /// * `auth_token` - Bearer token the harness should require (and we send)
+    /// * `placement` - CPUs and priority to run the harness with
     ///
     /// # Errors
     ///
-    /// Returns an error if the process cannot be spawned.
+    /// Returns an error if the process cannot be spawned, or if it exits or
+    /// times out before announcing an ephemeral port.
     pub async fn spawn_with_output(
         binary: &Path,
-        port: u16,
+        port: Option<u16>,
         output_label: Option<&str>,
+        announce_timeout: Duration,
+        auth_token: Option<&str>,
+        placement: &HarnessPlacement,
     ) -> Result<Self, OrchestratorError> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| {
-                OrchestratorError::SpawnError(format!("Failed to create HTTP client: {}", e))
-            })?;
-
-        if let Some(label) = output_label {
-            // Use tokio::process for async output streaming
-            let mut child = TokioCommand::new(binary)
-                .env("HYPOBENCH_PORT", port.to_string())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| {
-                    OrchestratorError::SpawnError(format!(
-                        "Failed to spawn {}: {}",
-                        binary.display(),
-                        e
-                    ))
-                })?;
-
-            let mut output_tasks = Vec::new();
-
-            // Spawn task to stream stdout
-            if let Some(stdout) = child.stdout.take() {
-                let label = label.to_string();
-                output_tasks.push(tokio::spawn(async move {
-                    let reader = BufReader::new(stdout);
-                    let mut lines = reader.lines();
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        eprintln!("[{} stdout] {}", label, line);
-                    }
-                }));
-            }
+        let client = build_client(auth_token)?;
+
+        let mut command = harness_command(binary, placement);
+        // We always talk to spawned harnesses over loopback, so don't let an
+        // inherited bind address expose them.
+        command
+            .env(TRANSPORT_ENV, Transport::Http.as_str())
+            .env(PORT_ENV, port.unwrap_or(0).to_string())
+            .env(BIND_ADDR_ENV, "127.0.0.1");
+        match auth_token {
+            Some(token) => command.env(AUTH_TOKEN_ENV, token),
+            None => command.env_remove(AUTH_TOKEN_ENV),
+        };
 
-            // Spawn task to stream stderr
-            if let Some(stderr) = child.stderr.take() {
-                let label = label.to_string();
-                output_tasks.push(tokio::spawn(async move {
-                    let reader = BufReader::new(stderr);
-                    let mut lines = reader.lines();
-                    while let Ok(Some(line)) = lines.next_line().await {
-                        eprintln!("[{} stderr] {}", label, line);
-                    }
-                }));
-            }
+        let SpawnedChild {
+            child,
+            output_tasks,
+            announced,
+        } = spawn_child(command, binary, output_label, None, placement)?;
+
+        let port = match port {
+            Some(port) => port,
+            // Any early return from here drops `child`, which kills it.
+            None => match tokio::time::timeout(announce_timeout, announced).await {
+                Ok(Ok(addr)) => addr.port(),
+                Ok(Err(_)) => {
+                    return Err(OrchestratorError::SpawnError(format!(
+                        "{} exited before announcing its listen address",
+                        binary.display()
+                    )))
+                }
+                Err(_) => {
+                    return Err(OrchestratorError::AnnounceTimeout {
+                        binary: binary.display().to_string(),
+                        timeout_secs: announce_timeout.as_secs(),
+                    })
+                }
+            },
+        };
 
-            Ok(Self {
-                process: None,
-                tokio_process: Some(child),
+        Ok(Self {
+            process: Some(child),
+            connection: Connection::Http {
                 base_url: format!("http://127.0.0.1:{}", port),
                 client,
-                is_managed: true,
-                output_tasks,
-                claim_nonce: None,
-            })
-        } else {
-            // Use std::process without output streaming
-            let process = Command::new(binary)
-                .env("HYPOBENCH_PORT", port.to_string())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .map_err(|e| {
-                    OrchestratorError::SpawnError(format!(
-                        "Failed to spawn {}: {}",
-                        binary.display(),
-                        e
-                    ))
-                })?;
+            },
+            is_managed: true,
+            output_tasks,
+            claim_nonce: None,
+            heartbeat_task: None,
+            claim_lost: Arc::default(),
+            protocol_version: PROTOCOL_VERSION,
+            perf_counters: false,
+        })
+    }
 
-            Ok(Self {
-                process: Some(process),
-                tokio_process: None,
-                base_url: format!("http://127.0.0.1:{}", port),
-                client,
-                is_managed: true,
-                output_tasks: Vec::new(),
-                claim_nonce: None,
-            })
-        }
+    /// Spawn a harness process that speaks the protocol over its stdin/stdout.
+    ///
+    /// No port is bound and no token is needed, since only we hold the pipe.
+    /// The harness must be built against a `hypobench-harness` that supports
+    /// [`Transport::Pipe`].
+    ///
+    /// # Arguments
+    ///
+    /// * `binary` - Path to the harness binary
+    /// * `output_label` - If Some, stream stdout/stderr with this prefix to stderr
+    /// * `placement` - CPUs and priority to run the harness with
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be spawned.
+    pub fn spawn_piped(
+        binary: &Path,
+        output_label: Option<&str>,
+        placement: &HarnessPlacement,
+    ) -> Result<Self, OrchestratorError> {
+        let mut command = harness_command(binary, placement);
+        command
+            .env(TRANSPORT_ENV, Transport::Pipe.as_str())
+            .env_remove(AUTH_TOKEN_ENV)
+            .stdin(Stdio::piped());
+
+        let pending: PendingResponses = Arc::new(StdMutex::new(Some(HashMap::new())));
+        let SpawnedChild {
+            mut child,
+            output_tasks,
+            ..
+        } = spawn_child(
+            command,
+            binary,
+            output_label,
+            Some(Arc::clone(&pending)),
+            placement,
+        )?;
+        let stdin = child.stdin.take().ok_or_else(|| {
+            OrchestratorError::SpawnError(format!("No stdin pipe for {}", binary.display()))
+        })?;
+
+        Ok(Self {
+            process: Some(child),
+            connection: Connection::Pipe(Arc::new(PipeConnection {
+                location: format!("pipe:{}", binary.display()),
+                stdin: Mutex::new(stdin),
+                pending,
+                next_id: AtomicU64::new(1),
+            })),
+            is_managed: true,
+            output_tasks,
+            claim_nonce: None,
+            heartbeat_task: None,
+            claim_lost: Arc::default(),
+            protocol_version: PROTOCOL_VERSION,
+            perf_counters: false,
+        })
     }
 
     /// Connect to an already-running harness at the given URL.
@@ -213,11 +633,12 @@ impl HarnessHandle {
     /// # Arguments
     ///
     /// * `url` - The base URL of the running harness (e.g., "http://localhost:9100")
+    /// * `auth_token` - Bearer token to send, if the harness requires one
     ///
     /// # Errors
     ///
     /// Returns an error if the URL is invalid or the client cannot be created.
-    pub fn connect(url: &str) -> Result<Self, OrchestratorError> {
+    pub fn connect(url: &str, auth_token: Option<&str>) -> Result<Self, OrchestratorError> {
         // Validate URL format
         if !url.starts_with("http://") && !url.starts_with("https://") {
             return Err(OrchestratorError::InvalidUrl(format!(
@@ -229,27 +650,48 @@ impl HarnessHandle {
         // Remove trailing slash if present
         let base_url = url.trim_end_matches('/').to_string();
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| {
-                OrchestratorError::SpawnError(format!("Failed to create HTTP client: {}", e))
-            })?;
+        let client = build_client(auth_token)?;
 
         Ok(Self {
             process: None,
-            tokio_process: None,
-            base_url,
-            client,
+            connection: Connection::Http { base_url, client },
             is_managed: false,
             output_tasks: Vec::new(),
             claim_nonce: None,
+            heartbeat_task: None,
+            claim_lost: Arc::default(),
+            protocol_version: PROTOCOL_VERSION,
+            perf_counters: false,
         })
     }
 
-    /// Get the base URL for this harness.
-    fn base_url(&self) -> &str {
-        &self.base_url
+    /// The protocol version used to talk to this harness.
+    pub fn protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    /// Where the harness lives: its base URL, or `pipe:<binary>`.
+    fn location(&self) -> &str {
+        self.connection.location()
+    }
+
+    /// Call `endpoint` with this handle's claim and decode the response.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint,
+        body: Option<serde_json::Value>,
+    ) -> Result<T, OrchestratorError> {
+        if self.claim_lost.load(Ordering::Relaxed) {
+            return Err(OrchestratorError::ClaimError(format!(
+                "Lost the claim on {}; its lease lapsed or was taken over",
+                self.location()
+            )));
+        }
+        let (status, body) = self
+            .connection
+            .call(endpoint, self.claim_nonce.as_deref(), body)
+            .await?;
+        decode(endpoint, status, body)
     }
 
     /// Check if the harness is healthy.
@@ -258,8 +700,14 @@ impl HarnessHandle {
     ///
     /// Returns an error if the health check fails.
     pub async fn health_check(&self) -> Result<HealthResponse, OrchestratorError> {
-        let url = format!("{}/health", self.base_url());
-        let response: HealthResponse = self.client.get(&url).send().await?.json().await?;
+        let (status, body) = self.connection.call(Endpoint::Health, None, None).await?;
+        if status == 401 {
+            return Err(OrchestratorError::Unauthorized {
+                url: self.location().to_string(),
+                env: AUTH_TOKEN_ENV,
+            });
+        }
+        let response: HealthResponse = decode(Endpoint::Health, status, body)?;
 
         if response.status == "healthy" {
             Ok(response)
@@ -273,24 +721,33 @@ impl HarnessHandle {
 
     /// Claim exclusive access to the harness.
     ///
+    /// The claim is requested as a lease of `ttl`. If the harness grants an
+    /// expiring lease, a background task heartbeats at a third of the TTL to
+    /// keep it alive until [`Self::release`]. With `force`, a live claim held
+    /// by another orchestrator is taken over instead of rejected.
+    ///
     /// # Errors
     ///
     /// Returns an error if the harness is already claimed by another orchestrator.
-    pub async fn claim(&mut self) -> Result<(), OrchestratorError> {
+    pub async fn claim(&mut self, ttl: Duration, force: bool) -> Result<(), OrchestratorError> {
         let nonce = Uuid::new_v4().to_string();
-        let url = format!("{}/claim", self.base_url());
-        let request = ClaimRequest::new(&nonce);
+        let mut request = ClaimRequest::new(&nonce).with_ttl(ttl);
+        if force {
+            request = request.forced();
+        }
 
         let response: ClaimResponse = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await?
-            .json()
+            .request(Endpoint::Claim, Some(to_body(&request)?))
             .await?;
 
         if response.success {
+            self.claim_lost.store(false, Ordering::Relaxed);
+            // Harnesses that predate leases never report an expiry; their
+            // claims are permanent and need no heartbeat.
+            if let Some(expires_in_ms) = response.expires_in_ms {
+                let interval = Duration::from_millis((expires_in_ms / 3).max(1));
+                self.heartbeat_task = Some(self.spawn_heartbeat(nonce.clone(), interval)?);
+            }
             self.claim_nonce = Some(nonce);
             Ok(())
         } else {
@@ -302,32 +759,114 @@ impl HarnessHandle {
         }
     }
 
+    /// Spawn a task that extends the claim lease every `interval`.
+    ///
+    /// The task stops on its own if the harness reports the lease as lost,
+    /// and marks the claim lost so that every later request through this
+    /// handle fails with a claim error rather than silently running
+    /// unclaimed on a harness anyone can now use.
+    fn spawn_heartbeat(
+        &self,
+        nonce: String,
+        interval: Duration,
+    ) -> Result<JoinHandle<()>, OrchestratorError> {
+        let connection = self.connection.clone();
+        let claim_lost = Arc::clone(&self.claim_lost);
+        let body = to_body(&HeartbeatRequest::new(nonce))?;
+        Ok(tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                let response = match connection
+                    .call(Endpoint::Heartbeat, None, Some(body.clone()))
+                    .await
+                {
+                    Ok((status, body)) => {
+                        decode::<HeartbeatResponse>(Endpoint::Heartbeat, status, body)
+                    }
+                    Err(e) => Err(e),
+                };
+                match response {
+                    Ok(response) if response.success => {}
+                    Ok(response) => {
+                        eprintln!(
+                            "Warning: lost claim on {}: {}",
+                            connection.location(),
+                            response.error.unwrap_or_else(|| "unknown".to_string())
+                        );
+                        claim_lost.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                    Err(e) => eprintln!(
+                        "Warning: heartbeat to {} failed: {}",
+                        connection.location(),
+                        e
+                    ),
+                }
+            }
+        }))
+    }
+
     /// Release the claim on the harness.
     ///
     /// # Errors
     ///
     /// Returns an error if the release request fails.
     pub async fn release(&mut self) -> Result<(), OrchestratorError> {
+        self.stop_heartbeat();
         if let Some(nonce) = self.claim_nonce.take() {
-            let url = format!("{}/release", self.base_url());
             let request = ReleaseRequest::new(&nonce);
-            let _ = self.client.post(&url).json(&request).send().await?;
+            let _ = self
+                .connection
+                .call(Endpoint::Release, None, Some(to_body(&request)?))
+                .await?;
         }
         Ok(())
     }
 
+    /// Stop the lease heartbeat task, if running.
+    fn stop_heartbeat(&mut self) {
+        if let Some(task) = self.heartbeat_task.take() {
+            task.abort();
+        }
+    }
+
+    /// Get the harness binary's build provenance.
+    ///
+    /// Returns `None` for harnesses that predate `/info` (protocol < 4).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn info(&self) -> Result<Option<HarnessInfo>, OrchestratorError> {
+        if self.protocol_version < 4 {
+            return Ok(None);
+        }
+        self.request(Endpoint::Info, None).await.map(Some)
+    }
+
+    /// Digest of each benchmark's output, for benchmarks registered with one.
+    ///
+    /// Returns `None` for harnesses older than protocol v5, which can't
+    /// report outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn verify(&self) -> Result<Option<BTreeMap<String, String>>, OrchestratorError> {
+        if self.protocol_version < 5 {
+            return Ok(None);
+        }
+        let response: VerifyResponse = self.request(Endpoint::Verify, None).await?;
+        Ok(Some(response.digests))
+    }
+
     /// Get the list of available benchmarks.
     ///
     /// # Errors
     ///
     /// Returns an error if the request fails.
     pub async fn list_benchmarks(&self) -> Result<Vec<String>, OrchestratorError> {
-        let url = format!("{}/benchmarks", self.base_url());
-        let mut req = self.client.get(&url);
-        if let Some(nonce) = &self.claim_nonce {
-            req = req.header(CLAIM_HEADER, nonce);
-        }
-        let response: BenchmarkListResponse = req.send().await?.json().await?;
+        let response: BenchmarkListResponse = self.request(Endpoint::Benchmarks, None).await?;
         Ok(response.benchmarks)
     }
 
@@ -336,6 +875,9 @@ impl HarnessHandle {
     /// Returns total elapsed across all iterations. Callers are responsible
     /// for dividing by `iterations` to obtain a per-iteration mean.
     ///
+    /// v1 harnesses run one iteration per request, so there this makes
+    /// `iterations` requests and sums their durations.
+    ///
     /// # Errors
     ///
     /// Returns an error if the iteration fails.
@@ -344,25 +886,113 @@ impl HarnessHandle {
         benchmark_id: &str,
         iterations: u64,
     ) -> Result<Duration, OrchestratorError> {
-        let url = format!("{}/run", self.base_url());
-        let request = RunIterationRequest::new(benchmark_id, iterations);
+        Ok(self.run_sample(benchmark_id, iterations).await?.elapsed)
+    }
 
-        let mut req = self.client.post(&url).json(&request);
-        if let Some(nonce) = &self.claim_nonce {
-            req = req.header(CLAIM_HEADER, nonce);
+    /// Like [`Self::run_iteration`], but also returns the allocations the
+    /// harness counted, if it counts them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the iteration fails.
+    pub async fn run_sample(
+        &self,
+        benchmark_id: &str,
+        iterations: u64,
+    ) -> Result<RunSample, OrchestratorError> {
+        if self.protocol_version < 2 {
+            let mut elapsed = Duration::ZERO;
+            for _ in 0..iterations {
+                elapsed += self.run_request(benchmark_id, 1).await?.elapsed;
+            }
+            return Ok(RunSample {
+                elapsed,
+                allocations: None,
+                counters: BTreeMap::new(),
+                rusage: None,
+            });
         }
+        self.run_request(benchmark_id, iterations).await
+    }
 
-        let response: RunIterationResponse = req.send().await?.json().await?;
+    /// Send a single `/run` request.
+    async fn run_request(
+        &self,
+        benchmark_id: &str,
+        iterations: u64,
+    ) -> Result<RunSample, OrchestratorError> {
+        let request = RunIterationRequest::new(benchmark_id, iterations)
+            .with_perf_counters(self.perf_counters);
+        let response: RunIterationResponse = self
+            .request(Endpoint::Run, Some(to_body(&request)?))
+            .await?;
+        RunSample::from_response(response)
+    }
 
-        if response.success {
-            Ok(response.duration())
-        } else {
-            Err(OrchestratorError::HarnessError(
-                response
-                    .error
-                    .unwrap_or_else(|| "Unknown error".to_string()),
-            ))
+    /// Run `count` samples of `iterations` inner iterations back to back, in
+    /// a single request.
+    ///
+    /// Returns each sample in order. Harnesses older than protocol v3 have no
+    /// batch endpoint, so there each sample is its own [`Self::run_sample`]
+    /// call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or any sample in the batch fails.
+    pub async fn run_batch(
+        &self,
+        benchmark_id: &str,
+        iterations: u64,
+        count: usize,
+    ) -> Result<Vec<RunSample>, OrchestratorError> {
+        self.run_batch_of(benchmark_id, &vec![iterations; count])
+            .await
+    }
+
+    /// Run one sample per entry of `iterations`, each of that many inner
+    /// iterations, back to back in a single request. As [`Self::run_batch`],
+    /// but the iteration count may differ from sample to sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or any sample in the batch fails.
+    pub async fn run_batch_of(
+        &self,
+        benchmark_id: &str,
+        iterations: &[u64],
+    ) -> Result<Vec<RunSample>, OrchestratorError> {
+        let count = iterations.len();
+        if self.protocol_version < 3 {
+            let mut samples = Vec::with_capacity(count);
+            for &iterations in iterations {
+                samples.push(self.run_sample(benchmark_id, iterations).await?);
+            }
+            return Ok(samples);
+        }
+
+        let request = RunBatchRequest::new(
+            iterations
+                .iter()
+                .map(|&iterations| RunIterationRequest::new(benchmark_id, iterations))
+                .collect(),
+        )
+        .with_perf_counters(self.perf_counters);
+        let response: RunBatchResponse = self
+            .request(Endpoint::RunBatch, Some(to_body(&request)?))
+            .await?;
+
+        if response.results.len() != count {
+            return Err(OrchestratorError::HarnessError(format!(
+                "Asked for {} samples, got {}",
+                count,
+                response.results.len()
+            )));
         }
+        response
+            .results
+            .into_iter()
+            .map(RunSample::from_response)
+            .collect()
     }
 
     /// Request the harness to shut down gracefully.
@@ -374,49 +1004,77 @@ impl HarnessHandle {
         // Release claim first
         self.release().await?;
 
-        let url = format!("{}/shutdown", self.base_url());
-        let mut req = self.client.post(&url);
-        if let Some(nonce) = &self.claim_nonce {
-            req = req.header(CLAIM_HEADER, nonce);
-        }
-        let _response: ShutdownResponse = req.send().await?.json().await?;
+        let _response: ShutdownResponse = self.request(Endpoint::Shutdown, None).await?;
         Ok(())
     }
 
     /// Kill the harness process forcefully (only for managed processes).
     pub fn kill(&mut self) {
+        self.stop_heartbeat();
+
         // Abort output streaming tasks
         for task in self.output_tasks.drain(..) {
             task.abort();
         }
 
-        // Kill std::process
+        // Kill the child (note: this is sync, use start_kill)
         if let Some(ref mut process) = self.process {
-            let _ = process.kill();
-        }
-
-        // Kill tokio::process (note: this is sync, use start_kill)
-        if let Some(ref mut process) = self.tokio_process {
             let _ = process.start_kill();
         }
     }
 
     /// Get the process ID of the harness (only for managed processes).
     pub fn pid(&self) -> Option<u32> {
-        self.process
-            .as_ref()
-            .map(|p| p.id())
-            .or_else(|| self.tokio_process.as_ref().and_then(|p| p.id()))
+        self.process.as_ref().and_then(|p| p.id())
     }
 
     /// Check if this is a managed (spawned) harness.
     pub fn is_managed(&self) -> bool {
         self.is_managed
     }
+
+    /// Ask the harness for perf counters on every subsequent run.
+    pub fn set_perf_counters(&mut self, enabled: bool) {
+        self.perf_counters = enabled;
+    }
+}
+
+/// Serialize a request body.
+fn to_body<T: Serialize>(request: &T) -> Result<serde_json::Value, OrchestratorError> {
+    serde_json::to_value(request)
+        .map_err(|e| OrchestratorError::HarnessError(format!("Failed to encode request: {}", e)))
+}
+
+/// Build the HTTP client used to talk to a harness.
+///
+/// The bearer token is installed as a default header so every request,
+/// including those from the heartbeat task, carries it.
+fn build_client(auth_token: Option<&str>) -> Result<reqwest::Client, OrchestratorError> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Some(token) = auth_token {
+        let mut value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| {
+                OrchestratorError::SpawnError(
+                    "Auth token contains characters not allowed in an HTTP header".to_string(),
+                )
+            })?;
+        value.set_sensitive(true);
+        headers.insert(reqwest::header::AUTHORIZATION, value);
+    }
+
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .default_headers(headers)
+        .build()
+        .map_err(|e| OrchestratorError::SpawnError(format!("Failed to create HTTP client: {}", e)))
 }
 
 impl Drop for HarnessHandle {
     fn drop(&mut self) {
+        // Remote harnesses outlive us, so stop heartbeating and let the lease
+        // lapse if we didn't get to release it.
+        self.stop_heartbeat();
+
         // Only kill managed processes
         if self.is_managed {
             self.kill();
@@ -434,20 +1092,124 @@ pub struct Orchestrator {
     baseline_binary: PathBuf,
     /// Path to the candidate harness binary.
     candidate_binary: PathBuf,
-    /// Base port for harness communication.
-    base_port: u16,
+    /// Base port for harness communication (`None` for ephemeral ports).
+    base_port: Option<u16>,
     /// Timeout for waiting for harnesses to become ready.
     timeout: Duration,
     /// Number of samples to collect per benchmark after calibration.
     sample_size: u32,
     /// Interval between interleaved benchmark runs.
     interleave_interval: Duration,
+    /// Samples per side in each interleaved block.
+    block_size: u32,
     /// Target minimum elapsed duration for a single sample.
     target_sample: Duration,
     /// Safety cap on iteration count chosen during calibration.
     max_calibration_iters: u64,
     /// Whether to show harness stdout/stderr output.
     show_output: bool,
+    /// Lease duration requested when claiming harnesses.
+    claim_ttl: Duration,
+    /// How to talk to the spawned harnesses.
+    transport: Transport,
+    /// Whether to ask the harnesses for perf counters.
+    perf_counters: bool,
+    /// Whether every sample runs the calibrated iteration count.
+    sampling: Sampling,
+    /// CPUs and priority for the baseline harness.
+    baseline_placement: HarnessPlacement,
+    /// CPUs and priority for the candidate harness.
+    candidate_placement: HarnessPlacement,
+}
+
+/// How many inner iterations each sample of a benchmark runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sampling {
+    /// Every sample runs the calibrated count `n`, and its elapsed time is
+    /// divided by `n`.
+    #[default]
+    Flat,
+    /// Samples run linearly increasing counts averaging `n`, so per-iteration
+    /// time can be fitted as the slope of elapsed time against the count,
+    /// separate from any fixed per-sample overhead.
+    Linear,
+}
+
+impl Sampling {
+    /// The name used in config files and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sampling::Flat => "flat",
+            Sampling::Linear => "linear",
+        }
+    }
+
+    /// Inner iterations for each of `sample_size` samples around the
+    /// calibrated count `iters`, in the order they should be taken.
+    ///
+    /// Linear sampling runs counts `d, 2d, …, sample_size·d` with `d` chosen
+    /// so that they average `iters`; if `iters` is too small for that, `d` is
+    /// 1 and samples run longer than calibrated. The counts are visited in a
+    /// fixed stride order rather than ascending, so drift over the run isn't
+    /// mistaken for a dependence on the count.
+    pub fn schedule(self, iters: u64, sample_size: u32) -> Vec<u64> {
+        let n = u64::from(sample_size);
+        match self {
+            Sampling::Flat => vec![iters; sample_size as usize],
+            Sampling::Linear => {
+                let step = ((2 * iters + n / 2) / (n + 1)).max(1);
+                let mut stride = ((n as f64 * 0.618).round() as u64).max(1);
+                while gcd(stride, n) != 1 {
+                    stride += 1;
+                }
+                (0..n).map(|k| step * ((k * stride) % n + 1)).collect()
+            }
+        }
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// One sample as reported by a harness: a benchmark run for some number of
+/// inner iterations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSample {
+    /// Total elapsed across all iterations.
+    pub elapsed: Duration,
+    /// Heap allocations across all iterations, if the harness counts them.
+    pub allocations: Option<AllocationStats>,
+    /// Perf counter totals across all iterations, if they were requested and
+    /// the harness could count them.
+    pub counters: BTreeMap<String, u64>,
+    /// Resource usage of the benchmark thread, if the harness measures it.
+    pub rusage: Option<ResourceUsage>,
+}
+
+impl RunSample {
+    /// Extract the sample from a `/run` response, surfacing a failure as an error.
+    fn from_response(response: RunIterationResponse) -> Result<Self, OrchestratorError> {
+        if response.success {
+            Ok(Self {
+                elapsed: response.duration(),
+                allocations: response.allocations,
+                counters: response.counters,
+                rusage: response.rusage,
+            })
+        } else {
+            Err(OrchestratorError::HarnessError(
+                response
+                    .error
+                    .unwrap_or_else(|| "Unknown error".to_string()),
+            ))
+        }
+    }
 }
 
 /// Collected benchmark samples for a single benchmark.
@@ -459,6 +1221,37 @@ pub struct BenchmarkSamples {
     pub baseline_samples: Vec<Duration>,
     /// Samples collected from the candidate.
     pub candidate_samples: Vec<Duration>,
+    /// Inner iterations per sample; the calibrated count under linear
+    /// sampling, where the count varies by sample.
+    pub iterations: u64,
+    /// Heap allocations of each baseline sample, in the same order as
+    /// `baseline_samples`. Empty unless the harness counts allocations.
+    pub baseline_allocations: Vec<AllocationStats>,
+    /// Heap allocations of each candidate sample.
+    pub candidate_allocations: Vec<AllocationStats>,
+    /// Perf counter totals of each baseline sample, in the same order as
+    /// `baseline_samples`. Maps are empty unless counters were requested and
+    /// the harness could count them.
+    pub baseline_counters: Vec<BTreeMap<String, u64>>,
+    /// Perf counter totals of each candidate sample.
+    pub candidate_counters: Vec<BTreeMap<String, u64>>,
+    /// Resource usage of each baseline sample, in the same order as
+    /// `baseline_samples`. Empty unless the harness measures it.
+    pub baseline_rusage: Vec<ResourceUsage>,
+    /// Resource usage of each candidate sample.
+    pub candidate_rusage: Vec<ResourceUsage>,
+    /// Inner iterations and total elapsed of each baseline sample, in the
+    /// same order as `baseline_samples`. Only recorded under linear sampling,
+    /// where each sample's count differs.
+    pub baseline_totals: Vec<(u64, Duration)>,
+    /// Inner iterations and total elapsed of each candidate sample.
+    pub candidate_totals: Vec<(u64, Duration)>,
+    /// Build provenance of the baseline harness, if it reports one.
+    pub baseline_info: Option<HarnessInfo>,
+    /// Build provenance of the candidate harness, if it reports one.
+    pub candidate_info: Option<HarnessInfo>,
+    /// Whether both sides produced the same output, if both digest it.
+    pub output_check: Option<OutputCheck>,
 }
 
 impl BenchmarkSamples {
@@ -468,6 +1261,18 @@ impl BenchmarkSamples {
             name: name.into(),
             baseline_samples: Vec::new(),
             candidate_samples: Vec::new(),
+            iterations: 1,
+            baseline_allocations: Vec::new(),
+            candidate_allocations: Vec::new(),
+            baseline_counters: Vec::new(),
+            candidate_counters: Vec::new(),
+            baseline_rusage: Vec::new(),
+            candidate_rusage: Vec::new(),
+            baseline_totals: Vec::new(),
+            candidate_totals: Vec::new(),
+            baseline_info: None,
+            candidate_info: None,
+            output_check: None,
         }
     }
 
@@ -480,6 +1285,183 @@ impl BenchmarkSamples {
     pub fn add_candidate(&mut self, duration: Duration) {
         self.candidate_samples.push(duration);
     }
+
+    /// Whether both sides reported allocations for every sample. Never under
+    /// linear sampling, as allocation totals are compared per iteration of a
+    /// fixed count.
+    pub fn has_allocations(&self) -> bool {
+        self.linear_runs().is_none()
+            && !self.baseline_samples.is_empty()
+            && self.baseline_allocations.len() == self.baseline_samples.len()
+            && self.candidate_allocations.len() == self.candidate_samples.len()
+    }
+
+    /// Counters that both sides reported for every sample, in name order.
+    pub fn counter_names(&self) -> Vec<String> {
+        let Some(first) = self.baseline_counters.first() else {
+            return Vec::new();
+        };
+        if self.baseline_counters.len() != self.baseline_samples.len()
+            || self.candidate_counters.len() != self.candidate_samples.len()
+        {
+            return Vec::new();
+        }
+        first
+            .keys()
+            .filter(|name| {
+                self.baseline_counters
+                    .iter()
+                    .chain(&self.candidate_counters)
+                    .all(|counters| counters.contains_key(*name))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Indices of baseline and candidate samples whose involuntary context
+    /// switches spiked, which likely means they were preempted mid-sample.
+    /// Empty for a side whose harness doesn't report resource usage.
+    pub fn contaminated(&self) -> (Vec<usize>, Vec<usize>) {
+        let spikes = |usage: &[ResourceUsage], samples: &[Duration]| {
+            if usage.len() == samples.len() {
+                context_switch_spikes(usage)
+            } else {
+                Vec::new()
+            }
+        };
+        (
+            spikes(&self.baseline_rusage, &self.baseline_samples),
+            spikes(&self.candidate_rusage, &self.candidate_samples),
+        )
+    }
+
+    /// Drop the given baseline and candidate samples, along with their
+    /// allocations, counters and resource usage.
+    ///
+    /// A side is left alone if dropping would leave it fewer than two
+    /// samples. Returns whether anything was dropped.
+    pub fn reject(&mut self, baseline: &[usize], candidate: &[usize]) -> bool {
+        let baseline_dropped = reject_side(
+            baseline,
+            &mut self.baseline_samples,
+            &mut self.baseline_allocations,
+            &mut self.baseline_counters,
+            &mut self.baseline_rusage,
+            &mut self.baseline_totals,
+        );
+        let candidate_dropped = reject_side(
+            candidate,
+            &mut self.candidate_samples,
+            &mut self.candidate_allocations,
+            &mut self.candidate_counters,
+            &mut self.candidate_rusage,
+            &mut self.candidate_totals,
+        );
+        baseline_dropped || candidate_dropped
+    }
+
+    /// Per-iteration values of counter `name` for each baseline and
+    /// candidate sample. Samples that lack the counter are skipped.
+    pub fn counter_per_iter(&self, name: &str) -> (Vec<f64>, Vec<f64>) {
+        let values = |samples: &[BTreeMap<String, u64>], totals: &[(u64, Duration)]| {
+            samples
+                .iter()
+                .enumerate()
+                .filter_map(|(i, counters)| {
+                    let iterations = totals.get(i).map_or(self.iterations, |&(n, _)| n);
+                    Some(*counters.get(name)? as f64 / iterations.max(1) as f64)
+                })
+                .collect()
+        };
+        (
+            values(&self.baseline_counters, &self.baseline_totals),
+            values(&self.candidate_counters, &self.candidate_totals),
+        )
+    }
+
+    /// Each baseline and candidate sample as `(iterations, elapsed_ns)`, for
+    /// fitting elapsed time against iteration count. `None` unless both
+    /// sides were sampled linearly.
+    #[allow(clippy::type_complexity)]
+    pub fn linear_runs(&self) -> Option<(Vec<(f64, f64)>, Vec<(f64, f64)>)> {
+        if self.baseline_totals.is_empty()
+            || self.baseline_totals.len() != self.baseline_samples.len()
+            || self.candidate_totals.len() != self.candidate_samples.len()
+        {
+            return None;
+        }
+        let runs = |totals: &[(u64, Duration)]| {
+            totals
+                .iter()
+                .map(|&(n, elapsed)| (n as f64, elapsed.as_nanos() as f64))
+                .collect()
+        };
+        Some((runs(&self.baseline_totals), runs(&self.candidate_totals)))
+    }
+}
+
+/// Indices of samples whose involuntary context switches exceed the median by
+/// more than three scaled MADs, and by at least two switches so that steady,
+/// low-level preemption across all samples isn't flagged.
+fn context_switch_spikes(usage: &[ResourceUsage]) -> Vec<usize> {
+    let median = |values: &mut Vec<f64>| -> f64 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        if values.is_empty() {
+            0.0
+        } else if values.len().is_multiple_of(2) {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    };
+    let switches: Vec<f64> = usage
+        .iter()
+        .map(|u| u.involuntary_context_switches as f64)
+        .collect();
+    let center = median(&mut switches.clone());
+    let mad = median(&mut switches.iter().map(|s| (s - center).abs()).collect());
+    // 1.4826 scales the MAD to a standard deviation for normal data.
+    let threshold = center + (3.0 * 1.4826 * mad).max(2.0);
+    switches
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| s >= threshold)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Remove `indices` from one side's samples and every per-sample vector that
+/// lines up with them.
+fn reject_side(
+    indices: &[usize],
+    samples: &mut Vec<Duration>,
+    allocations: &mut Vec<AllocationStats>,
+    counters: &mut Vec<BTreeMap<String, u64>>,
+    rusage: &mut Vec<ResourceUsage>,
+    totals: &mut Vec<(u64, Duration)>,
+) -> bool {
+    if indices.is_empty() || samples.len() < indices.len() + 2 {
+        return false;
+    }
+    fn retain<T>(values: &mut Vec<T>, len: usize, indices: &[usize]) {
+        if values.len() != len {
+            return;
+        }
+        let mut index = 0;
+        values.retain(|_| {
+            let keep = !indices.contains(&index);
+            index += 1;
+            keep
+        });
+    }
+    let len = samples.len();
+    retain(allocations, len, indices);
+    retain(counters, len, indices);
+    retain(rusage, len, indices);
+    retain(totals, len, indices);
+    retain(samples, len, indices);
+    true
 }
 
 impl Orchestrator {
@@ -489,24 +1471,39 @@ impl Orchestrator {
     ///
     /// * `baseline_binary` - Path to the baseline harness binary
     /// * `candidate_binary` - Path to the candidate harness binary
-    /// * `base_port` - Base port for harness communication (baseline uses base_port, candidate uses base_port + 1)
+    /// * `base_port` - Fixed base port (baseline uses base_port, candidate uses base_port + 1),
+    ///   or `None` to have each harness bind a free port and announce it
     /// * `timeout` - Timeout for waiting for harnesses to become ready
     /// * `sample_size` - Number of samples to collect per benchmark after calibration
     /// * `interleave_interval` - Interval between interleaved benchmark runs
+    /// * `block_size` - Samples per side in each interleaved block (one batch request)
     /// * `target_sample` - Target minimum elapsed for a single sample (calibration target)
     /// * `max_calibration_iters` - Safety cap on the chosen iteration count
     /// * `show_output` - Whether to show harness stdout/stderr
+    /// * `claim_ttl` - Lease duration requested when claiming the harnesses
+    /// * `transport` - Whether to drive the harnesses over HTTP or their stdin/stdout
+    /// * `perf_counters` - Whether to ask the harnesses for perf counters
+    /// * `sampling` - Whether samples run the calibrated iteration count or a linear range around it
+    /// * `baseline_placement` - CPUs and priority for the baseline harness
+    /// * `candidate_placement` - CPUs and priority for the candidate harness
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         baseline_binary: PathBuf,
         candidate_binary: PathBuf,
-        base_port: u16,
+        base_port: Option<u16>,
         timeout: Duration,
         sample_size: u32,
         interleave_interval: Duration,
+        block_size: u32,
         target_sample: Duration,
         max_calibration_iters: u64,
         show_output: bool,
+        claim_ttl: Duration,
+        transport: Transport,
+        perf_counters: bool,
+        sampling: Sampling,
+        baseline_placement: HarnessPlacement,
+        candidate_placement: HarnessPlacement,
     ) -> Self {
         Self {
             baseline_binary,
@@ -515,9 +1512,16 @@ impl Orchestrator {
             timeout,
             sample_size,
             interleave_interval,
+            block_size,
             target_sample,
             max_calibration_iters,
             show_output,
+            claim_ttl,
+            transport,
+            perf_counters,
+            sampling,
+            baseline_placement,
+            candidate_placement,
         }
     }
 
@@ -549,15 +1553,44 @@ impl Orchestrator {
             None
         };
 
-        let mut baseline =
-            HarnessHandle::spawn_with_output(&self.baseline_binary, self.base_port, baseline_label)
+        let (mut baseline, mut candidate) = match self.transport {
+            Transport::Http => {
+                // Fresh secret per run, so nothing else on the machine can drive our harnesses.
+                let auth_token = Uuid::new_v4().to_string();
+
+                let baseline = HarnessHandle::spawn_with_output(
+                    &self.baseline_binary,
+                    self.base_port,
+                    baseline_label,
+                    self.timeout,
+                    Some(&auth_token),
+                    &self.baseline_placement,
+                )
                 .await?;
-        let mut candidate = HarnessHandle::spawn_with_output(
-            &self.candidate_binary,
-            self.base_port + 1,
-            candidate_label,
-        )
-        .await?;
+                let candidate = HarnessHandle::spawn_with_output(
+                    &self.candidate_binary,
+                    self.base_port.map(|port| port + 1),
+                    candidate_label,
+                    self.timeout,
+                    Some(&auth_token),
+                    &self.candidate_placement,
+                )
+                .await?;
+                (baseline, candidate)
+            }
+            Transport::Pipe => (
+                HarnessHandle::spawn_piped(
+                    &self.baseline_binary,
+                    baseline_label,
+                    &self.baseline_placement,
+                )?,
+                HarnessHandle::spawn_piped(
+                    &self.candidate_binary,
+                    candidate_label,
+                    &self.candidate_placement,
+                )?,
+            ),
+        };
 
         // Use a guard to ensure harnesses are killed on error
         let result = self
@@ -596,13 +1629,18 @@ impl Orchestrator {
 
         // 3. Claim exclusive access to both harnesses
         eprint!("  Claiming baseline harness... ");
-        baseline.claim().await?;
+        baseline.claim(self.claim_ttl, false).await?;
         eprintln!("claimed");
 
         eprint!("  Claiming candidate harness... ");
-        candidate.claim().await?;
+        candidate.claim(self.claim_ttl, false).await?;
         eprintln!("claimed");
 
+        baseline.set_perf_counters(self.perf_counters);
+        candidate.set_perf_counters(self.perf_counters);
+
+        let (baseline_info, candidate_info) = fetch_infos(baseline, candidate).await?;
+
         // 3. Get benchmark lists and validate they match
         let baseline_benchmarks = baseline.list_benchmarks().await?;
         let candidate_benchmarks = candidate.list_benchmarks().await?;
@@ -626,15 +1664,20 @@ impl Orchestrator {
             baseline_sorted.join(", ")
         );
 
+        let output_checks = verify_outputs(baseline, candidate).await?;
+
         // 4. For each benchmark, collect samples
         let mut results = Vec::new();
         let total_benchmarks = baseline_benchmarks.len();
 
         for (idx, benchmark_name) in baseline_benchmarks.iter().enumerate() {
             eprintln!("  [{}/{}] {}", idx + 1, total_benchmarks, benchmark_name);
-            let samples = self
+            let mut samples = self
                 .collect_benchmark_samples(benchmark_name, baseline, candidate)
                 .await?;
+            samples.baseline_info = baseline_info.clone();
+            samples.candidate_info = candidate_info.clone();
+            samples.output_check = output_checks.get(benchmark_name).cloned();
             results.push(samples);
         }
 
@@ -645,17 +1688,15 @@ impl Orchestrator {
     ///
     /// Calibrates the iteration count on the baseline handle (geometric
     /// doubling up to `target_sample`), then collects `sample_size` samples
-    /// at that fixed iteration count on both sides. Records per-iteration
-    /// means (total elapsed / n) so Welch's t-test operates on comparable
-    /// units regardless of the chosen `n`.
+    /// at that iteration count, or a linear range around it, on both sides.
+    /// Records per-iteration means (total elapsed / n) so Welch's t-test
+    /// operates on comparable units regardless of the chosen `n`.
     async fn collect_benchmark_samples(
         &self,
         benchmark_name: &str,
         baseline: &HarnessHandle,
         candidate: &HarnessHandle,
     ) -> Result<BenchmarkSamples, OrchestratorError> {
-        let mut samples = BenchmarkSamples::new(benchmark_name);
-
         // Calibrate iteration count on baseline; reuse for candidate.
         eprint!("      calibrating... ");
         let iters = calibrate_iterations(
@@ -667,73 +1708,160 @@ impl Orchestrator {
         .await?;
         eprintln!("n={}", iters);
 
-        // Collect interleaved samples at fixed `iters`.
+        collect_interleaved_samples(
+            benchmark_name,
+            baseline,
+            candidate,
+            iters,
+            self.sample_size,
+            self.sampling,
+            self.block_size,
+            self.interleave_interval,
+        )
+        .await
+    }
+}
+
+/// Compare the output digests of every benchmark both harnesses can digest,
+/// and print a summary before sampling starts.
+async fn verify_outputs(
+    baseline: &HarnessHandle,
+    candidate: &HarnessHandle,
+) -> Result<HashMap<String, OutputCheck>, OrchestratorError> {
+    let (Some(baseline_digests), Some(candidate_digests)) =
+        (baseline.verify().await?, candidate.verify().await?)
+    else {
+        return Ok(HashMap::new());
+    };
+    let checks: HashMap<String, OutputCheck> = baseline_digests
+        .iter()
+        .filter_map(|(name, digest)| {
+            let other = candidate_digests.get(name)?;
+            Some((name.clone(), OutputCheck::compare(digest, other)))
+        })
+        .collect();
+    if !checks.is_empty() {
+        let mut mismatched: Vec<&str> = checks
+            .iter()
+            .filter(|(_, check)| check.is_mismatch())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        mismatched.sort();
         eprint!(
-            "      collecting {} samples (n={})... ",
-            self.sample_size, iters
+            "  Verified outputs: {} match",
+            checks.len() - mismatched.len()
         );
-        for i in 0..self.sample_size {
-            self.run_interleaved_pair(
-                benchmark_name,
-                baseline,
-                candidate,
-                i % 2 == 0,
-                iters,
-                &mut samples,
-            )
-            .await?;
-
-            // Progress indicator every 10 samples
-            if (i + 1) % 10 == 0 {
-                eprint!("{}", i + 1);
-                if i + 1 < self.sample_size {
-                    eprint!("...");
-                }
-            }
+        if mismatched.is_empty() {
+            eprintln!();
+        } else {
+            eprintln!(", {} MISMATCH: {}", mismatched.len(), mismatched.join(", "));
         }
-        eprintln!(" done");
+    }
+    Ok(checks)
+}
 
-        Ok(samples)
+/// Ask both harnesses how they were built, and print what they report.
+async fn fetch_infos(
+    baseline: &HarnessHandle,
+    candidate: &HarnessHandle,
+) -> Result<(Option<HarnessInfo>, Option<HarnessInfo>), OrchestratorError> {
+    let baseline_info = baseline.info().await?;
+    let candidate_info = candidate.info().await?;
+    for (label, info) in [("Baseline", &baseline_info), ("Candidate", &candidate_info)] {
+        if let Some(info) = info {
+            eprintln!("  {} build: {}", label, info.summary());
+        }
     }
+    Ok((baseline_info, candidate_info))
+}
 
-    /// Run one baseline/candidate pair, alternating which side goes first.
-    ///
-    /// Both durations are divided by `iters` and recorded as per-iteration
-    /// means.
-    #[allow(clippy::too_many_arguments)]
-    async fn run_interleaved_pair(
-        &self,
-        benchmark_name: &str,
-        baseline: &HarnessHandle,
-        candidate: &HarnessHandle,
-        baseline_first: bool,
-        iters: u64,
-        samples: &mut BenchmarkSamples,
-    ) -> Result<(), OrchestratorError> {
-        let (first_handle, second_handle, first_is_baseline) = if baseline_first {
-            (baseline, candidate, true)
+/// Collect `sample_size` samples per side at `iters` inner iterations, or
+/// under linear sampling at the counts of [`Sampling::schedule`], recording
+/// each sample's count and total elapsed too.
+///
+/// Samples are taken in blocks of `block_size` per side, each block a single
+/// `/run_batch` request followed by `interleave_interval`. Blocks alternate
+/// which side goes first (A…A B…B, then B…B A…A), so drift over the run
+/// affects both sides equally; `block_size = 1` gives the classic ABBA
+/// pair alternation. Both sides' durations are recorded as per-iteration means.
+#[allow(clippy::too_many_arguments)]
+async fn collect_interleaved_samples(
+    benchmark_name: &str,
+    baseline: &HarnessHandle,
+    candidate: &HarnessHandle,
+    iters: u64,
+    sample_size: u32,
+    sampling: Sampling,
+    block_size: u32,
+    interleave_interval: Duration,
+) -> Result<BenchmarkSamples, OrchestratorError> {
+    let mut samples = BenchmarkSamples::new(benchmark_name);
+    samples.iterations = iters;
+    let block_size = block_size.max(1);
+    let schedule = sampling.schedule(iters, sample_size);
+
+    match (schedule.iter().min(), schedule.iter().max()) {
+        (Some(lo), Some(hi)) if lo != hi => eprint!(
+            "      collecting {} samples (n={}..{})... ",
+            sample_size, lo, hi
+        ),
+        _ => eprint!("      collecting {} samples (n={})... ", sample_size, iters),
+    }
+    let mut collected = 0;
+    let mut baseline_first = true;
+    while collected < sample_size {
+        let count = block_size.min(sample_size - collected) as usize;
+        let block = &schedule[collected as usize..collected as usize + count];
+        let (first_handle, second_handle) = if baseline_first {
+            (baseline, candidate)
         } else {
-            (candidate, baseline, false)
+            (candidate, baseline)
         };
 
-        let first_elapsed = first_handle.run_iteration(benchmark_name, iters).await?;
-        sleep(self.interleave_interval).await;
-        let second_elapsed = second_handle.run_iteration(benchmark_name, iters).await?;
-        sleep(self.interleave_interval).await;
-
-        let first_per_iter = per_iter_mean(first_elapsed, iters);
-        let second_per_iter = per_iter_mean(second_elapsed, iters);
+        let first_elapsed = first_handle.run_batch_of(benchmark_name, block).await?;
+        sleep(interleave_interval).await;
+        let second_elapsed = second_handle.run_batch_of(benchmark_name, block).await?;
+        sleep(interleave_interval).await;
 
-        if first_is_baseline {
-            samples.add_baseline(first_per_iter);
-            samples.add_candidate(second_per_iter);
+        let (baseline_elapsed, candidate_elapsed) = if baseline_first {
+            (first_elapsed, second_elapsed)
         } else {
-            samples.add_candidate(first_per_iter);
-            samples.add_baseline(second_per_iter);
+            (second_elapsed, first_elapsed)
+        };
+        let linear = sampling == Sampling::Linear;
+        for (sample, &iters) in baseline_elapsed.into_iter().zip(block) {
+            samples.add_baseline(per_iter_mean(sample.elapsed, iters));
+            if linear {
+                samples.baseline_totals.push((iters, sample.elapsed));
+            }
+            samples.baseline_allocations.extend(sample.allocations);
+            samples.baseline_counters.push(sample.counters);
+            samples.baseline_rusage.extend(sample.rusage);
+        }
+        for (sample, &iters) in candidate_elapsed.into_iter().zip(block) {
+            samples.add_candidate(per_iter_mean(sample.elapsed, iters));
+            if linear {
+                samples.candidate_totals.push((iters, sample.elapsed));
+            }
+            samples.candidate_allocations.extend(sample.allocations);
+            samples.candidate_counters.push(sample.counters);
+            samples.candidate_rusage.extend(sample.rusage);
         }
 
-        Ok(())
+        // Progress indicator every 10 samples
+        let before = collected;
+        collected += count as u32;
+        if collected / 10 > before / 10 {
+            eprint!("{}", collected);
+            if collected < sample_size {
+                eprint!("...");
+            }
+        }
+        baseline_first = !baseline_first;
     }
+    eprintln!(" done");
+
+    Ok(samples)
 }
 
 /// Divide an elapsed duration by an iteration count to get per-iteration mean.
@@ -774,12 +1902,13 @@ async fn calibrate_iterations(
     }
 }
 
-/// Wait for a harness to become healthy, with retries. Also verifies that the
-/// harness reports a protocol version the orchestrator knows how to talk to.
+/// Wait for a harness to become healthy, with retries. Also negotiates the
+/// protocol version: the highest one both sides speak.
 ///
-/// Protocol mismatch is *not* retried — a v1 harness won't become v2 by waiting.
+/// Protocol mismatch is *not* retried — a harness won't learn a new protocol
+/// version by waiting. Neither is an auth rejection, for the same reason.
 pub async fn wait_for_health(
-    harness: &HarnessHandle,
+    harness: &mut HarnessHandle,
     timeout: Duration,
 ) -> Result<(), OrchestratorError> {
     let start = std::time::Instant::now();
@@ -789,15 +1918,31 @@ pub async fn wait_for_health(
     loop {
         match harness.health_check().await {
             Ok(response) => {
-                if response.protocol_version != PROTOCOL_VERSION {
+                let theirs = response.supported_versions();
+                let Some(version) = negotiate_version(supported_versions(), theirs.clone()) else {
                     return Err(OrchestratorError::ProtocolVersionMismatch {
-                        url: harness.base_url().to_string(),
-                        expected: PROTOCOL_VERSION,
-                        actual: response.protocol_version,
+                        url: harness.location().to_string(),
+                        min: MIN_PROTOCOL_VERSION,
+                        max: PROTOCOL_VERSION,
+                        harness_min: *theirs.start(),
+                        harness_max: *theirs.end(),
                     });
+                };
+                if version < PROTOCOL_VERSION {
+                    eprint!(
+                        "(protocol v{}{}) ",
+                        version,
+                        if version < 2 {
+                            ", one request per iteration"
+                        } else {
+                            ""
+                        }
+                    );
                 }
+                harness.protocol_version = version;
                 return Ok(());
             }
+            Err(e @ OrchestratorError::Unauthorized { .. }) => return Err(e),
             Err(e) if start.elapsed() < timeout => {
                 last_error = Some(e);
                 sleep(retry_interval).await;
@@ -807,7 +1952,7 @@ pub async fn wait_for_health(
                     .map(|le| le.to_string())
                     .unwrap_or_else(|| e.to_string());
                 return Err(OrchestratorError::TimeoutError {
-                    url: harness.base_url().to_string(),
+                    url: harness.location().to_string(),
                     timeout_secs: timeout.as_secs(),
                     last_error: error_msg,
                 });
@@ -828,39 +1973,57 @@ pub async fn wait_for_health(
 /// * `timeout` - Timeout for waiting for harnesses to become healthy
 /// * `sample_size` - Number of samples to collect per benchmark after calibration
 /// * `interleave_interval` - Interval between interleaved benchmark runs
+/// * `block_size` - Samples per side in each interleaved block (one batch request)
 /// * `target_sample` - Target minimum elapsed for a single sample (calibration target)
 /// * `max_calibration_iters` - Safety cap on the iteration count chosen during calibration
+/// * `claim_ttl` - Lease duration requested when claiming the harnesses
+/// * `force_claim` - Take over harnesses already claimed by another orchestrator
+/// * `auth_token` - Bearer token to send, if the harnesses require one
+/// * `perf_counters` - Whether to ask the harnesses for perf counters
+/// * `sampling` - Whether samples run the calibrated iteration count or a linear range around it
+#[allow(clippy::too_many_arguments)]
 pub async fn run_with_urls(
     baseline_url: &str,
     candidate_url: &str,
     timeout: Duration,
     sample_size: u32,
     interleave_interval: Duration,
+    block_size: u32,
     target_sample: Duration,
     max_calibration_iters: u64,
+    claim_ttl: Duration,
+    force_claim: bool,
+    auth_token: Option<&str>,
+    perf_counters: bool,
+    sampling: Sampling,
 ) -> Result<Vec<BenchmarkSamples>, OrchestratorError> {
     // Connect to remote harnesses
-    let mut baseline = HarnessHandle::connect(baseline_url)?;
-    let mut candidate = HarnessHandle::connect(candidate_url)?;
+    let mut baseline = HarnessHandle::connect(baseline_url, auth_token)?;
+    let mut candidate = HarnessHandle::connect(candidate_url, auth_token)?;
 
     // Wait for health checks (also verifies protocol version)
     eprint!("  Waiting for baseline harness... ");
-    wait_for_health(&baseline, timeout).await?;
+    wait_for_health(&mut baseline, timeout).await?;
     eprintln!("ready");
 
     eprint!("  Waiting for candidate harness... ");
-    wait_for_health(&candidate, timeout).await?;
+    wait_for_health(&mut candidate, timeout).await?;
     eprintln!("ready");
 
     // Claim exclusive access to both harnesses
     eprint!("  Claiming baseline harness... ");
-    baseline.claim().await?;
+    baseline.claim(claim_ttl, force_claim).await?;
     eprintln!("claimed");
 
     eprint!("  Claiming candidate harness... ");
-    candidate.claim().await?;
+    candidate.claim(claim_ttl, force_claim).await?;
     eprintln!("claimed");
 
+    baseline.set_perf_counters(perf_counters);
+    candidate.set_perf_counters(perf_counters);
+
+    let (baseline_info, candidate_info) = fetch_infos(&baseline, &candidate).await?;
+
     // Get benchmark lists and validate they match
     let baseline_benchmarks = baseline.list_benchmarks().await?;
     let candidate_benchmarks = candidate.list_benchmarks().await?;
@@ -884,6 +2047,8 @@ pub async fn run_with_urls(
         baseline_sorted.join(", ")
     );
 
+    let output_checks = verify_outputs(&baseline, &candidate).await?;
+
     // Collect samples for each benchmark
     let mut results = Vec::new();
     let total_benchmarks = baseline_benchmarks.len();
@@ -891,8 +2056,6 @@ pub async fn run_with_urls(
     for (idx, benchmark_name) in baseline_benchmarks.iter().enumerate() {
         eprintln!("  [{}/{}] {}", idx + 1, total_benchmarks, benchmark_name);
 
-        let mut samples = BenchmarkSamples::new(benchmark_name);
-
         eprint!("      calibrating... ");
         let iters = calibrate_iterations(
             &baseline,
@@ -903,40 +2066,20 @@ pub async fn run_with_urls(
         .await?;
         eprintln!("n={}", iters);
 
-        eprint!("      collecting {} samples (n={})... ", sample_size, iters);
-        for i in 0..sample_size {
-            let baseline_first = i % 2 == 0;
-            let (first_handle, second_handle, first_is_baseline) = if baseline_first {
-                (&baseline, &candidate, true)
-            } else {
-                (&candidate, &baseline, false)
-            };
-
-            let first_elapsed = first_handle.run_iteration(benchmark_name, iters).await?;
-            sleep(interleave_interval).await;
-            let second_elapsed = second_handle.run_iteration(benchmark_name, iters).await?;
-            sleep(interleave_interval).await;
-
-            let first_per_iter = per_iter_mean(first_elapsed, iters);
-            let second_per_iter = per_iter_mean(second_elapsed, iters);
-
-            if first_is_baseline {
-                samples.add_baseline(first_per_iter);
-                samples.add_candidate(second_per_iter);
-            } else {
-                samples.add_candidate(first_per_iter);
-                samples.add_baseline(second_per_iter);
-            }
-
-            // Progress indicator every 10 samples
-            if (i + 1) % 10 == 0 {
-                eprint!("{}", i + 1);
-                if i + 1 < sample_size {
-                    eprint!("...");
-                }
-            }
-        }
-        eprintln!(" done");
+        let mut samples = collect_interleaved_samples(
+            benchmark_name,
+            &baseline,
+            &candidate,
+            iters,
+            sample_size,
+            sampling,
+            block_size,
+            interleave_interval,
+        )
+        .await?;
+        samples.baseline_info = baseline_info.clone();
+        samples.candidate_info = candidate_info.clone();
+        samples.output_check = output_checks.get(benchmark_name).cloned();
 
         results.push(samples);
     }
@@ -975,27 +2118,122 @@ mod tests {
         assert_eq!(samples.candidate_samples[1], Duration::from_micros(105));
     }
 
+    fn preempted(involuntary_context_switches: u64) -> ResourceUsage {
+        ResourceUsage {
+            involuntary_context_switches,
+            ..ResourceUsage::default()
+        }
+    }
+
+    #[test]
+    fn test_context_switch_spikes() {
+        let usage: Vec<_> = [0, 1, 0, 0, 7, 1, 0, 2]
+            .into_iter()
+            .map(preempted)
+            .collect();
+        assert_eq!(context_switch_spikes(&usage), vec![4]);
+
+        // Steady preemption in every sample is not a spike.
+        let usage: Vec<_> = [5, 6, 5, 4, 6, 5].into_iter().map(preempted).collect();
+        assert!(context_switch_spikes(&usage).is_empty());
+    }
+
+    #[test]
+    fn test_benchmark_samples_reject_contaminated() {
+        let mut samples = BenchmarkSamples::new("test");
+        for (i, switches) in [0, 0, 9, 0].into_iter().enumerate() {
+            samples.add_baseline(Duration::from_micros(100 + i as u64));
+            samples.baseline_rusage.push(preempted(switches));
+            samples.add_candidate(Duration::from_micros(100));
+        }
+
+        let (baseline, candidate) = samples.contaminated();
+        assert_eq!(baseline, vec![2]);
+        assert!(candidate.is_empty(), "candidate reports no rusage");
+
+        assert!(samples.reject(&baseline, &candidate));
+        assert_eq!(samples.baseline_samples.len(), 3);
+        assert_eq!(samples.baseline_samples[2], Duration::from_micros(103));
+        assert_eq!(samples.baseline_rusage.len(), 3);
+        assert_eq!(samples.candidate_samples.len(), 4);
+
+        // Never drop a side below two samples.
+        assert!(!samples.reject(&[0, 1], &[]));
+    }
+
     #[test]
     fn test_orchestrator_new() {
         let orchestrator = Orchestrator::new(
             PathBuf::from("/path/to/baseline"),
             PathBuf::from("/path/to/candidate"),
-            9100,
+            Some(9100),
             Duration::from_secs(30),
             100,
             Duration::from_millis(100),
+            1,
             Duration::from_millis(10),
             1_000_000_000,
             false,
+            Duration::from_secs(30),
+            Transport::Http,
+            false,
+            Sampling::Flat,
+            HarnessPlacement::default(),
+            HarnessPlacement::default(),
         );
 
-        assert_eq!(orchestrator.base_port, 9100);
+        assert_eq!(orchestrator.base_port, Some(9100));
         assert!(!orchestrator.show_output);
         assert_eq!(orchestrator.sample_size, 100);
         assert_eq!(orchestrator.target_sample, Duration::from_millis(10));
         assert_eq!(orchestrator.max_calibration_iters, 1_000_000_000);
     }
 
+    #[test]
+    fn test_sampling_schedule() {
+        assert_eq!(Sampling::Flat.schedule(7, 3), vec![7, 7, 7]);
+
+        // Linear counts average the calibrated n, visited out of order.
+        let schedule = Sampling::Linear.schedule(100, 9);
+        let mut sorted = schedule.clone();
+        sorted.sort();
+        assert_eq!(sorted, (1..=9).map(|k| 20 * k).collect::<Vec<u64>>());
+        assert_ne!(schedule, sorted);
+
+        // Too few iterations to spread: steps of one.
+        let mut schedule = Sampling::Linear.schedule(1, 4);
+        schedule.sort();
+        assert_eq!(schedule, vec![1, 2, 3, 4]);
+        assert!(Sampling::Linear.schedule(10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_linear_runs_and_counters_use_each_count() {
+        let mut samples = BenchmarkSamples::new("test");
+        samples.iterations = 2;
+        for n in [1, 2, 3] {
+            let elapsed = Duration::from_nanos(100 + 10 * n);
+            samples.add_baseline(per_iter_mean(elapsed, n));
+            samples.baseline_totals.push((n, elapsed));
+            samples
+                .baseline_counters
+                .push(BTreeMap::from([("instructions".to_string(), 6 * n)]));
+            samples.add_candidate(per_iter_mean(elapsed, n));
+            samples.candidate_totals.push((n, elapsed));
+            samples.candidate_counters.push(BTreeMap::new());
+        }
+
+        let (baseline, _) = samples.linear_runs().unwrap();
+        assert_eq!(baseline, vec![(1.0, 110.0), (2.0, 120.0), (3.0, 130.0)]);
+        assert_eq!(samples.counter_per_iter("instructions").0, vec![6.0; 3]);
+
+        assert!(samples.reject(&[1], &[]));
+        assert_eq!(samples.baseline_totals.len(), 2);
+
+        samples.candidate_totals.clear();
+        assert!(samples.linear_runs().is_none());
+    }
+
     #[test]
     fn test_per_iter_mean() {
         assert_eq!(
@@ -1007,20 +2245,20 @@ mod tests {
 
     #[test]
     fn test_harness_handle_connect_valid() {
-        let handle = HarnessHandle::connect("http://localhost:9100").unwrap();
+        let handle = HarnessHandle::connect("http://localhost:9100", None).unwrap();
         assert!(!handle.is_managed());
-        assert_eq!(handle.base_url(), "http://localhost:9100");
+        assert_eq!(handle.location(), "http://localhost:9100");
     }
 
     #[test]
     fn test_harness_handle_connect_trailing_slash() {
-        let handle = HarnessHandle::connect("http://localhost:9100/").unwrap();
-        assert_eq!(handle.base_url(), "http://localhost:9100");
+        let handle = HarnessHandle::connect("http://localhost:9100/", None).unwrap();
+        assert_eq!(handle.location(), "http://localhost:9100");
     }
 
     #[test]
     fn test_harness_handle_connect_invalid_url() {
-        let result = HarnessHandle::connect("not-a-url");
+        let result = HarnessHandle::connect("not-a-url", None);
         assert!(result.is_err());
         match result {
             Err(OrchestratorError::InvalidUrl(_)) => {}
@@ -1057,4 +2295,277 @@ mod tests {
         let err = OrchestratorError::InvalidUrl("bad-url".to_string());
         assert_eq!(err.to_string(), "Invalid URL: bad-url");
     }
+
+    /// Write an executable shell script standing in for a harness binary.
+    #[cfg(unix)]
+    fn fake_harness(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("fake-harness");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_discovers_announced_port() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(
+            dir.path(),
+            "echo 'unrelated benchmark output'\necho 'HYPOBENCH_LISTENING 0.0.0.0:41234'\nsleep 5",
+        );
+
+        let handle = HarnessHandle::spawn(&binary, None).await.unwrap();
+        assert_eq!(handle.location(), "http://127.0.0.1:41234");
+        assert!(handle.is_managed());
+        assert!(handle.pid().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_runs_under_launcher_with_extra_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(
+            dir.path(),
+            "echo \"HYPOBENCH_LISTENING 127.0.0.1:${FAKE_PORT}${FAKE_SUFFIX}\"\nsleep 5",
+        );
+        let placement = HarnessPlacement {
+            launcher: vec!["env".to_string(), "FAKE_SUFFIX=35".to_string()],
+            env: [("FAKE_PORT".to_string(), "412".to_string())].into(),
+            ..Default::default()
+        };
+
+        let handle = HarnessHandle::spawn_with_output(
+            &binary,
+            None,
+            None,
+            DEFAULT_ANNOUNCE_TIMEOUT,
+            None,
+            &placement,
+        )
+        .await
+        .unwrap();
+        assert_eq!(handle.location(), "http://127.0.0.1:41235");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_fails_if_harness_exits_without_announcing() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(dir.path(), "echo 'old harness'");
+
+        let result = HarnessHandle::spawn(&binary, None).await;
+        assert!(matches!(result, Err(OrchestratorError::SpawnError(_))));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_with_fixed_port_skips_announcement() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(dir.path(), "sleep 5");
+
+        let handle = HarnessHandle::spawn(&binary, Some(9123)).await.unwrap();
+        assert_eq!(handle.location(), "http://127.0.0.1:9123");
+    }
+
+    #[test]
+    fn test_decode_surfaces_harness_error() {
+        let body = serde_json::json!({ "error": "Invalid claim nonce" });
+        let err = decode::<BenchmarkListResponse>(Endpoint::Benchmarks, 403, body).unwrap_err();
+        assert!(err.to_string().contains("Invalid claim nonce"));
+        assert!(err.to_string().contains("/benchmarks"));
+
+        assert_eq!(parse_body(b""), serde_json::Value::Null);
+        assert_eq!(parse_body(b"oops")["error"], "oops");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_piped_matches_responses_and_skips_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(
+            dir.path(),
+            &format!(
+                r#"echo 'benchmark noise'
+while read -r line; do
+  id=$(printf '%s' "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+  echo "chatter for request $id"
+  echo "{{\"id\":$id,\"status\":200,\"body\":{{\"status\":\"healthy\",\"protocol_version\":{}}}}}"
+done"#,
+                PROTOCOL_VERSION
+            ),
+        );
+
+        let mut handle =
+            HarnessHandle::spawn_piped(&binary, None, &HarnessPlacement::default()).unwrap();
+        assert!(handle.is_managed());
+        assert!(handle.location().starts_with("pipe:"));
+
+        wait_for_health(&mut handle, Duration::from_secs(5))
+            .await
+            .unwrap();
+        let health = handle.health_check().await.unwrap();
+        assert_eq!(health.protocol_version, PROTOCOL_VERSION);
+    }
+
+    #[test]
+    fn test_parse_pipe_response_after_unterminated_output() {
+        let response = r#"{"id":7,"status":200,"body":null}"#;
+        let (output, parsed) = parse_pipe_response(response).unwrap();
+        assert_eq!((output, parsed.id), ("", 7));
+
+        let line = format!(r#"progress {{"id": 1}} 50%{response}"#);
+        let (output, parsed) = parse_pipe_response(&line).unwrap();
+        assert_eq!((output, parsed.id), (r#"progress {"id": 1} 50%"#, 7));
+
+        assert!(parse_pipe_response("benchmark noise").is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_piped_reads_responses_after_unterminated_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(
+            dir.path(),
+            &format!(
+                r#"while read -r line; do
+  id=$(printf '%s' "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+  printf 'working...'
+  echo "{{\"id\":$id,\"status\":200,\"body\":{{\"status\":\"healthy\",\"protocol_version\":{}}}}}"
+done"#,
+                PROTOCOL_VERSION
+            ),
+        );
+
+        let handle =
+            HarnessHandle::spawn_piped(&binary, None, &HarnessPlacement::default()).unwrap();
+        let health = tokio::time::timeout(Duration::from_secs(5), handle.health_check())
+            .await
+            .expect("response was delivered")
+            .unwrap();
+        assert_eq!(health.protocol_version, PROTOCOL_VERSION);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_piped_fails_fast_when_harness_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = fake_harness(dir.path(), "exit 0");
+
+        let handle =
+            HarnessHandle::spawn_piped(&binary, None, &HarnessPlacement::default()).unwrap();
+        let err = handle.health_check().await.unwrap_err();
+        assert!(
+            err.to_string().contains("closed its stdout")
+                || err.to_string().contains("Failed to write"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    /// Log of `(side, runs in the batch)`, one entry per `/run_batch` call.
+    type CallLog = Arc<StdMutex<Vec<(&'static str, usize)>>>;
+
+    /// Serve `/run_batch` on a local port, logging each call under `side`
+    /// and reporting `per_iter_ns` per iteration for every run.
+    async fn spawn_recording_harness(
+        side: &'static str,
+        per_iter_ns: u64,
+        log: CallLog,
+    ) -> HarnessHandle {
+        let app = axum::Router::new().route(
+            "/run_batch",
+            axum::routing::post(move |axum::Json(batch): axum::Json<RunBatchRequest>| {
+                let log = log.clone();
+                async move {
+                    log.lock().unwrap().push((side, batch.runs.len()));
+                    axum::Json(RunBatchResponse::new(
+                        batch
+                            .runs
+                            .iter()
+                            .map(|run| {
+                                RunIterationResponse::success(
+                                    run.iterations,
+                                    Duration::from_nanos(run.iterations * per_iter_ns),
+                                )
+                            })
+                            .collect(),
+                    ))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        HarnessHandle::connect(&url, None).unwrap()
+    }
+
+    async fn interleaved_call_order(
+        sample_size: u32,
+        block_size: u32,
+    ) -> (Vec<(&'static str, usize)>, BenchmarkSamples) {
+        let log = CallLog::default();
+        let baseline = spawn_recording_harness("baseline", 10, log.clone()).await;
+        let candidate = spawn_recording_harness("candidate", 20, log.clone()).await;
+        let samples = collect_interleaved_samples(
+            "bench",
+            &baseline,
+            &candidate,
+            5,
+            sample_size,
+            Sampling::Flat,
+            block_size,
+            Duration::ZERO,
+        )
+        .await
+        .unwrap();
+        let calls = log.lock().unwrap().clone();
+        (calls, samples)
+    }
+
+    #[tokio::test]
+    async fn test_interleaved_samples_alternate_abba_pairs() {
+        let (calls, samples) = interleaved_call_order(6, 1).await;
+        assert_eq!(
+            calls,
+            vec![
+                ("baseline", 1),
+                ("candidate", 1),
+                ("candidate", 1),
+                ("baseline", 1),
+                ("baseline", 1),
+                ("candidate", 1),
+                ("candidate", 1),
+                ("baseline", 1),
+                ("baseline", 1),
+                ("candidate", 1),
+                ("candidate", 1),
+                ("baseline", 1),
+            ]
+        );
+        assert_eq!(samples.baseline_samples, vec![Duration::from_nanos(10); 6]);
+        assert_eq!(samples.candidate_samples, vec![Duration::from_nanos(20); 6]);
+    }
+
+    #[tokio::test]
+    async fn test_interleaved_samples_alternate_abba_blocks() {
+        // Blocks of 3 with a short last block: 3, 3, 1 per side.
+        let (calls, samples) = interleaved_call_order(7, 3).await;
+        assert_eq!(
+            calls,
+            vec![
+                ("baseline", 3),
+                ("candidate", 3),
+                ("candidate", 3),
+                ("baseline", 3),
+                ("baseline", 1),
+                ("candidate", 1),
+            ]
+        );
+        assert_eq!(samples.baseline_samples, vec![Duration::from_nanos(10); 7]);
+        assert_eq!(samples.candidate_samples, vec![Duration::from_nanos(20); 7]);
+    }
 }
@@ -4,9 +4,13 @@
 //! consumers can deserialize reports without depending on the CLI. The concrete
 //! renderers and the `Reporter` trait live here — they're purely CLI concerns.
 
+use std::fmt;
 use std::io;
+use std::str::FromStr;
 
-use hypobench_core::BenchmarkComparison;
+use hypobench_core::report::quantile_label;
+use hypobench_core::stats::{ChangeInterval, Verdict};
+use hypobench_core::{BenchmarkComparison, Report};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -25,6 +29,178 @@ pub trait Reporter: Send + Sync {
     fn report(&self, results: &[BenchmarkComparison]) -> Result<(), ReportError>;
 }
 
+/// The statistic a rendered report leads with: the mean, or a quantile of
+/// the timing distribution such as p99.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum HeadlineMetric {
+    #[default]
+    Mean,
+    /// A quantile in `(0, 1)`, e.g. `0.99` for p99.
+    Quantile(f64),
+}
+
+impl HeadlineMetric {
+    /// Whether every comparison in `report` carries this metric.
+    pub fn available_in(&self, report: &Report) -> bool {
+        match self {
+            HeadlineMetric::Mean => true,
+            HeadlineMetric::Quantile(q) => {
+                report.comparisons.iter().all(|c| c.quantile(*q).is_some())
+            }
+        }
+    }
+
+    /// The figures to show for `comparison`. Falls back to the mean if the
+    /// quantile wasn't computed.
+    pub(crate) fn headline(&self, comparison: &BenchmarkComparison) -> Headline {
+        let quantile = match self {
+            HeadlineMetric::Mean => None,
+            HeadlineMetric::Quantile(q) => comparison.quantile(*q),
+        };
+        match quantile {
+            Some(quantile) => Headline {
+                baseline_ns: quantile.baseline_ns,
+                candidate_ns: quantile.candidate_ns,
+                change: quantile.change,
+                change_ci_low: quantile.change_ci_low,
+                change_ci_high: quantile.change_ci_high,
+                verdict: quantile.verdict(),
+                is_mean: false,
+            },
+            None => {
+                let result = &comparison.test_result;
+                Headline {
+                    baseline_ns: comparison.baseline_stats.per_iter_ns(),
+                    candidate_ns: comparison.candidate_stats.per_iter_ns(),
+                    change: result.effect_size,
+                    change_ci_low: result.change_ci_low,
+                    change_ci_high: result.change_ci_high,
+                    verdict: result.verdict(),
+                    is_mean: true,
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for HeadlineMetric {
+    type Err = String;
+
+    /// Parse `mean`, `median`, or a percentile such as `p99` or `p99.9`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let percentile = match s {
+            "mean" => return Ok(HeadlineMetric::Mean),
+            "median" => Some(50.0),
+            _ => s.strip_prefix('p').and_then(|p| p.parse::<f64>().ok()),
+        };
+        match percentile {
+            Some(p) if p > 0.0 && p < 100.0 => Ok(HeadlineMetric::Quantile(p / 100.0)),
+            _ => Err(format!(
+                "invalid metric `{s}`: expected `mean`, `median` or a percentile such as `p99`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HeadlineMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadlineMetric::Mean => f.write_str("mean"),
+            HeadlineMetric::Quantile(q) => f.write_str(&quantile_label(*q)),
+        }
+    }
+}
+
+/// One benchmark's figures under a [`HeadlineMetric`]. Same sign convention
+/// as `TestResult::effect_size`.
+pub(crate) struct Headline {
+    pub baseline_ns: f64,
+    pub candidate_ns: f64,
+    pub change: f64,
+    pub change_ci_low: f64,
+    pub change_ci_high: f64,
+    pub verdict: Verdict,
+    /// Whether these are the mean's figures, which come with a p-value.
+    pub is_mean: bool,
+}
+
+/// The modes of each multimodal side, e.g. `baseline 1.002 µs (60%), 1.498
+/// µs (40%)`, with times written by the renderer's `format_time`.
+pub(crate) fn format_modes(
+    comparison: &BenchmarkComparison,
+    format_time: impl Fn(f64) -> String,
+) -> String {
+    [
+        ("baseline", &comparison.baseline_stats),
+        ("candidate", &comparison.candidate_stats),
+    ]
+    .iter()
+    .filter_map(|(side, stats)| {
+        let modality = stats.modality.as_ref()?;
+        modality.multimodal().then_some((side, modality))
+    })
+    .map(|(side, modality)| {
+        let modes: Vec<String> = modality
+            .modes
+            .iter()
+            .map(|mode| format!("{} ({:.0}%)", format_time(mode.at_ns), mode.share * 100.0))
+            .collect();
+        format!("{} {}", side, modes.join(", "))
+    })
+    .collect::<Vec<_>>()
+    .join("; ")
+}
+
+/// Benchmarks whose change CI fell back to the bootstrap because the
+/// configured analytic interval was unbounded.
+pub(crate) fn change_ci_fallbacks(report: &Report) -> Vec<&BenchmarkComparison> {
+    if report
+        .metadata
+        .config
+        .change_interval
+        .as_deref()
+        .unwrap_or("bootstrap")
+        == "bootstrap"
+    {
+        return Vec::new();
+    }
+    report
+        .comparisons
+        .iter()
+        .filter(|c| c.test_result.change_interval == Some(ChangeInterval::Bootstrap))
+        .collect()
+}
+
+/// A per-iteration metric value: a time for `*_ns` metrics, written by the
+/// renderer's `format_time`, otherwise a count with a k/M/G suffix.
+pub(crate) fn format_metric_value(
+    metric: &str,
+    value: f64,
+    format_time: impl Fn(f64) -> String,
+) -> String {
+    if metric.ends_with("_ns") {
+        format_time(value)
+    } else if value >= 1e9 {
+        format!("{:.2}G", value / 1e9)
+    } else if value >= 1e6 {
+        format!("{:.2}M", value / 1e6)
+    } else if value >= 1e3 {
+        format!("{:.2}k", value / 1e3)
+    } else {
+        format_count(value)
+    }
+}
+
+pub(crate) fn format_count(count: f64) -> String {
+    // Allocation counts are usually exact integers per iteration; only show a
+    // decimal when they aren't.
+    if (count - count.round()).abs() < 0.05 {
+        format!("{:.0}", count)
+    } else {
+        format!("{:.1}", count)
+    }
+}
+
 mod github_pr_comment;
 mod json;
 mod terminal;
@@ -32,6 +208,31 @@ pub use github_pr_comment::GithubPrCommentReporter;
 pub use json::JsonReporter;
 pub use terminal::TerminalReporter;
 
+#[cfg(test)]
+mod headline_metric_tests {
+    use super::HeadlineMetric;
+
+    #[test]
+    fn parses_mean_median_and_percentiles() {
+        assert_eq!("mean".parse(), Ok(HeadlineMetric::Mean));
+        assert_eq!("median".parse(), Ok(HeadlineMetric::Quantile(0.5)));
+        assert_eq!("p99".parse(), Ok(HeadlineMetric::Quantile(0.99)));
+        for invalid in ["p0", "p100", "99", "pxx", "max"] {
+            assert!(invalid.parse::<HeadlineMetric>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn displays_as_parsed() {
+        for metric in ["mean", "p50", "p90", "p99", "p99.9"] {
+            assert_eq!(
+                metric.parse::<HeadlineMetric>().unwrap().to_string(),
+                metric
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod json_reporter_tests {
     use hypobench_core::stats::{Side, TestResult};
@@ -54,7 +255,18 @@ mod json_reporter_tests {
                     minimum_effect_size: 2.0,
                     sample_size: 50,
                     correct_multiple_comparisons: true,
+                    equivalence_margin: None,
+                    power: None,
+                    method: None,
+                    bootstrap: None,
+                    sampling: None,
+                    change_interval: None,
                 },
+                baseline_harnesses: Vec::new(),
+                candidate_harnesses: Vec::new(),
+                baseline_placement: None,
+                candidate_placement: None,
+                system_checks: Vec::new(),
             },
             comparisons: vec![BenchmarkComparison {
                 name: "bench_foo".to_string(),
@@ -64,6 +276,10 @@ mod json_reporter_tests {
                     min_ns: 900,
                     max_ns: 1100,
                     sample_count: 50,
+                    median_ns: 1000.0,
+                    quantiles: Vec::new(),
+                    fit: None,
+                    modality: None,
                 },
                 candidate_stats: SampleStats {
                     mean_ns: 800.0,
@@ -71,6 +287,10 @@ mod json_reporter_tests {
                     min_ns: 720,
                     max_ns: 880,
                     sample_count: 50,
+                    median_ns: 800.0,
+                    quantiles: Vec::new(),
+                    fit: None,
+                    modality: None,
                 },
                 test_result: TestResult {
                     p_value: 0.001,
@@ -82,8 +302,19 @@ mod json_reporter_tests {
                     winner: Some(Side::Candidate),
                     baseline_mean_ns: 1000.0,
                     candidate_mean_ns: 800.0,
+                    equivalence: None,
+                    power: None,
+                    posterior: None,
+                    change_interval: None,
                 },
+                allocations: None,
+                metrics: Vec::new(),
+                contamination: None,
+                output_check: None,
+                quantiles: Vec::new(),
+                stationarity: None,
             }],
+            suite: None,
         }
     }
 
@@ -104,7 +335,7 @@ mod json_reporter_tests {
 
 #[cfg(test)]
 mod github_pr_comment_tests {
-    use hypobench_core::stats::{Side, TestResult};
+    use hypobench_core::stats::{ChangeInterval, Side, TestResult};
     use hypobench_core::{
         BenchmarkComparison, ConfigSnapshot, Report, ReportMetadata, SampleStats,
     };
@@ -128,6 +359,10 @@ mod github_pr_comment_tests {
                 min_ns: (baseline_ns * 0.9) as u64,
                 max_ns: (baseline_ns * 1.1) as u64,
                 sample_count: 50,
+                median_ns: baseline_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             candidate_stats: SampleStats {
                 mean_ns: candidate_ns,
@@ -135,6 +370,10 @@ mod github_pr_comment_tests {
                 min_ns: (candidate_ns * 0.9) as u64,
                 max_ns: (candidate_ns * 1.1) as u64,
                 sample_count: 50,
+                median_ns: candidate_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             test_result: TestResult {
                 p_value: p,
@@ -146,7 +385,17 @@ mod github_pr_comment_tests {
                 winner,
                 baseline_mean_ns: baseline_ns,
                 candidate_mean_ns: candidate_ns,
+                equivalence: None,
+                power: None,
+                posterior: None,
+                change_interval: None,
             },
+            allocations: None,
+            metrics: Vec::new(),
+            contamination: None,
+            output_check: None,
+            quantiles: Vec::new(),
+            stationarity: None,
         }
     }
 
@@ -163,7 +412,18 @@ mod github_pr_comment_tests {
                     minimum_effect_size: 2.0,
                     sample_size: 50,
                     correct_multiple_comparisons: true,
+                    equivalence_margin: None,
+                    power: None,
+                    method: None,
+                    bootstrap: None,
+                    sampling: None,
+                    change_interval: None,
                 },
+                baseline_harnesses: Vec::new(),
+                candidate_harnesses: Vec::new(),
+                baseline_placement: None,
+                candidate_placement: None,
+                system_checks: Vec::new(),
             },
             comparisons: vec![
                 make_comparison(
@@ -186,6 +446,7 @@ mod github_pr_comment_tests {
                 ),
                 make_comparison("bench_same", 1000.0, 1010.0, -1.0, 0.5, None, false),
             ],
+            suite: None,
         }
     }
 
@@ -271,6 +532,103 @@ mod github_pr_comment_tests {
         );
     }
 
+    #[test]
+    fn marks_equivalent_benchmarks() {
+        let mut report = sample_report();
+        report.metadata.config.equivalence_margin = Some(2.0);
+        report.comparisons[2].test_result.equivalence = Some(hypobench_core::Equivalence {
+            margin: 2.0,
+            p_value: 0.004,
+            equivalent: true,
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("**1 faster, 1 slower, 1 equivalent, 0 inconclusive**"),
+            "missing equivalent count: {out}"
+        );
+        assert!(
+            out.contains("| :heavy_equals_sign: | bench_same"),
+            "missing equivalent emoji: {out}"
+        );
+        assert!(out.contains("- Equivalence margin: ±2%"));
+    }
+
+    #[test]
+    fn shows_minimum_detectable_effect_column() {
+        let mut report = sample_report();
+        report.metadata.config.power = Some(0.8);
+        report.comparisons[2].test_result.power = Some(hypobench_core::PowerAnalysis {
+            power: 0.8,
+            minimum_detectable_effect: 1.83,
+            required_sample_size: Some(160),
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("| p | MDE |"), "missing MDE header: {out}");
+        assert!(out.contains("| 0.5000 | ±1.8% |"), "missing MDE: {out}");
+        assert!(
+            out.contains("| 0.0010 | – |"),
+            "missing MDE placeholder: {out}"
+        );
+        assert!(out.contains("- Power (for MDE): 0.8"));
+    }
+
+    #[test]
+    fn shows_posterior_probabilities_for_bayesian_runs() {
+        let mut report = sample_report();
+        report.metadata.config.method = Some("bayesian".to_string());
+        for (cmp, prob_faster) in report.comparisons.iter_mut().zip([0.999, 0.03, 0.4]) {
+            cmp.test_result.posterior = Some(hypobench_core::Posterior {
+                prob_faster,
+                margin: 2.0,
+                prob_within_margin: 0.1,
+            });
+        }
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("97% probability of regression, 10% within ±2%)"),
+            "missing posterior on pinned regression: {out}"
+        );
+        assert!(out.contains("| P(slower) |\n"), "missing header: {out}");
+        assert!(out.contains("| 60% |\n"), "missing P(slower) cell: {out}");
+        assert!(out.contains("- Test: bayesian"));
+    }
+
+    #[test]
+    fn leads_with_suite_geomean() {
+        let mut report = sample_report();
+        report.suite = Some(hypobench_core::SuiteSummary {
+            benchmarks: 3,
+            change: -3.2,
+            change_ci_low: -5.01,
+            change_ci_high: -1.12,
+            confidence_level: 0.99,
+            weighted: true,
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.starts_with(
+                "## Benchmark Results\n\n:warning: **Suite: +3.20%** (99% CI [+1.12%, +5.01%], geometric mean of 3 weighted benchmarks)\n"
+            ),
+            "missing suite headline: {out}"
+        );
+    }
+
     #[test]
     fn escapes_pipes_in_benchmark_names() {
         let mut report = sample_report();
@@ -282,4 +640,351 @@ mod github_pr_comment_tests {
         let out = String::from_utf8(buf).unwrap();
         assert!(out.contains(r"bench\|weird"), "pipe not escaped: {out}");
     }
+
+    #[test]
+    fn lists_harness_builds_when_known() {
+        let mut report = sample_report();
+        report.metadata.baseline_harnesses = vec![hypobench_core::HarnessInfo {
+            crate_name: Some("char-counter".to_string()),
+            crate_version: Some("0.1.0".to_string()),
+            git_sha: Some("abc123def456".to_string()),
+            rustc_version: "rustc 1.80.0".to_string(),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            profile: "release".to_string(),
+            opt_level: "3".to_string(),
+            features: vec!["default".to_string()],
+            harness_version: "0.5.1".to_string(),
+            pid: 1234,
+        }];
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("- Baseline build: char-counter 0.1.0 @ abc123de (release, opt-level 3)"),
+            "missing baseline build: {out}"
+        );
+        assert!(!out.contains("Candidate build"));
+    }
+
+    #[test]
+    fn lists_failed_system_checks() {
+        let mut report = sample_report();
+        report.metadata.system_checks = vec![
+            hypobench_core::SystemCheck {
+                name: "turbo_boost".to_string(),
+                ok: false,
+                detail: "turbo boost is enabled".to_string(),
+                advice: Some("disable it".to_string()),
+            },
+            hypobench_core::SystemCheck {
+                name: "aslr".to_string(),
+                ok: true,
+                detail: "ASLR is disabled".to_string(),
+                advice: None,
+            },
+        ];
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("### :thermometer: System noise\n\n- turbo boost is enabled\n"),
+            "missing system noise: {out}"
+        );
+        assert!(!out.contains("ASLR"));
+    }
+
+    #[test]
+    fn lists_harness_placement_when_set() {
+        let mut report = sample_report();
+        report.metadata.candidate_placement = Some(hypobench_core::HarnessPlacement {
+            launcher: vec![
+                "numactl".to_string(),
+                "--cpunodebind=0".to_string(),
+                "--".to_string(),
+            ],
+            cpus: vec![3],
+            nice: Some(-5),
+            ..Default::default()
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("- Candidate placement: via numactl --cpunodebind=0 -- · CPU 3 · nice -5"),
+            "missing candidate placement: {out}"
+        );
+        assert!(!out.contains("Baseline placement"));
+    }
+
+    #[test]
+    fn pins_significant_allocation_changes() {
+        use hypobench_core::protocol::AllocationStats;
+        use hypobench_core::{AllocationComparison, WelchTTest};
+
+        let stats = |count, bytes| AllocationStats {
+            count,
+            bytes,
+            peak_live_bytes: 0,
+        };
+        let mut report = sample_report();
+        report.comparisons[0].allocations = Some(AllocationComparison::analyze(
+            &WelchTTest::new(0.95),
+            &[stats(12, 480); 10],
+            &[stats(15, 576); 10],
+            1,
+        ));
+        report.comparisons[1].allocations = Some(AllocationComparison::analyze(
+            &WelchTTest::new(0.95),
+            &[stats(12, 480); 10],
+            &[stats(12, 480); 10],
+            1,
+        ));
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("Allocation changes"), "missing section: {out}");
+        assert!(
+            out.contains(
+                "- `bench_fast` — **+3 allocs/iter** (12 → 15), **+96 B/iter** (480 B → 576 B)"
+            ),
+            "missing allocation row: {out}"
+        );
+        assert_eq!(
+            out.matches("allocs/iter").count(),
+            1,
+            "unchanged bench pinned: {out}"
+        );
+    }
+
+    #[test]
+    fn pins_significant_counter_changes() {
+        use hypobench_core::{MetricComparison, WelchTTest};
+
+        let baseline: Vec<f64> = (0..10).map(|i| 1_000_000.0 + i as f64).collect();
+        let fewer: Vec<f64> = baseline.iter().map(|v| v * 0.959).collect();
+        let test = WelchTTest::new(0.95);
+        let mut report = sample_report();
+        report.comparisons[0].metrics = vec![
+            MetricComparison::analyze(&test, "instructions", &baseline, &fewer),
+            MetricComparison::analyze(&test, "cycles", &baseline, &baseline),
+        ];
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(out.contains("Counter changes"), "missing section: {out}");
+        assert!(
+            out.contains("- `bench_fast` — **instructions/iter -4.1%** (1.00M → 959.00k)"),
+            "missing counter row: {out}"
+        );
+        assert!(
+            !out.contains("cycles/iter"),
+            "unchanged counter pinned: {out}"
+        );
+    }
+
+    #[test]
+    fn lists_contaminated_samples() {
+        let mut report = sample_report();
+        report.comparisons[1].contamination = Some(hypobench_core::Contamination {
+            baseline_flagged: 1,
+            candidate_flagged: 3,
+            rejected: false,
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        let name = &report.comparisons[1].name;
+        assert!(
+            out.contains(&format!("- `{name}` — 1 baseline, 3 candidate\n")),
+            "missing contamination row: {out}"
+        );
+    }
+
+    #[test]
+    fn lists_unreliable_benchmarks() {
+        use hypobench_core::Stationarity;
+
+        // The candidate warmed up: every sample a little faster than the last.
+        let baseline: Vec<f64> = (0..50).map(|i| 1000.0 + (i % 5) as f64).collect();
+        let candidate: Vec<f64> = (0..50).map(|i| 1100.0 - 2.0 * i as f64).collect();
+        let mut report = sample_report();
+        report.comparisons[1].stationarity = Stationarity::analyze(&baseline, &candidate, 0.01);
+        assert!(report.comparisons[1].unreliable());
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        let name = &report.comparisons[1].name;
+        assert!(
+            out.contains(&format!(
+                "- `{name}` — candidate trend (tau -1.00, p=0.0000); "
+            )),
+            "missing unreliable row: {out}"
+        );
+        assert!(out.contains(&format!("{name} :warning:")), "{out}");
+    }
+
+    #[test]
+    fn lists_multimodal_benchmarks() {
+        let samples: Vec<f64> = (0..100)
+            .map(|i| if i % 4 == 0 { 3000.0 } else { 1000.0 } + (i % 10) as f64)
+            .collect();
+        let mut report = sample_report();
+        report.comparisons[0].baseline_stats = SampleStats::from_samples(&samples, &[]);
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        let section = out
+            .find("### :warning: Multimodal benchmarks")
+            .expect("missing section");
+        let row = out[section..]
+            .lines()
+            .find(|line| line.starts_with("- `"))
+            .unwrap();
+        let name = &report.comparisons[0].name;
+        assert!(
+            row.starts_with(&format!("- `{name}` — baseline 1.0")),
+            "{row}"
+        );
+        assert!(
+            row.contains(" (75%), 3.0") && row.ends_with(" µs (25%)"),
+            "{row}"
+        );
+    }
+
+    #[test]
+    fn pins_output_mismatches_first() {
+        use hypobench_core::OutputCheck;
+
+        let mut report = sample_report();
+        report.comparisons[0].output_check = Some(OutputCheck::compare("aaaa", "bbbb"));
+        report.comparisons[1].output_check = Some(OutputCheck::Match);
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        let section = out
+            .find("### :x: Output mismatches")
+            .expect("missing section");
+        assert!(section < out.find("### :rocket: Improvements").unwrap());
+        assert!(
+            out.contains("- `bench_fast` — baseline output `aaaa`, candidate output `bbbb`"),
+            "missing mismatch row: {out}"
+        );
+        assert!(out.contains(":x: output differs"), "{out}");
+        assert_eq!(out.matches("baseline output").count(), 1, "{out}");
+    }
+
+    #[test]
+    fn leads_with_chosen_quantile() {
+        use super::HeadlineMetric;
+        use hypobench_core::QuantileComparison;
+
+        // bench_same's mean barely moved, but its p99 regressed.
+        let mut report = sample_report();
+        report.comparisons[2].quantiles = vec![QuantileComparison {
+            quantile: 0.99,
+            baseline_ns: 1500.0,
+            candidate_ns: 2100.0,
+            change: -40.0,
+            change_ci_low: -55.0,
+            change_ci_high: -25.0,
+            statistically_significant: true,
+        }];
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("- `bench_same` — **p99 +40.00%** (1.500 µs → 2.100 µs)"),
+            "missing quantile change: {out}"
+        );
+
+        report.comparisons.retain(|c| c.name == "bench_same");
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .with_metric(HeadlineMetric::Quantile(0.99))
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("**0 faster, 1 slower, 0 inconclusive**"),
+            "{out}"
+        );
+        assert!(
+            out.contains("- `bench_same` — **+40.00%** (p99, CI [+25.00%, +55.00%])"),
+            "missing p99 regression: {out}"
+        );
+        assert!(out.contains("| Baseline p99 | Candidate p99 |"), "{out}");
+        assert!(
+            out.contains("| :warning: | bench_same | 1.500 µs | 2.100 µs | +40.00% | [+25.00%, +55.00%] | – |"),
+            "missing p99 row: {out}"
+        );
+        assert!(out.contains("- Headline metric: p99"), "{out}");
+    }
+
+    #[test]
+    fn lists_bootstrap_settings() {
+        let mut report = sample_report();
+        report.metadata.config.bootstrap = Some(hypobench_core::Bootstrap {
+            resamples: 2000,
+            seed: 0xC0FFEE,
+            interval: hypobench_core::BootstrapInterval::Bca,
+        });
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("- Bootstrap: 2000 resamples, bca interval, seed 0xc0ffee"),
+            "{out}"
+        );
+        assert!(!out.contains("- Change CI:"), "{out}");
+    }
+
+    #[test]
+    fn lists_analytic_change_interval() {
+        let mut report = sample_report();
+        report.metadata.config.change_interval = Some("fieller".to_string());
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        assert!(
+            out.contains("- Change CI: Fieller interval on the ratio of means\n"),
+            "{out}"
+        );
+        assert!(!out.contains("Bootstrapped instead"), "{out}");
+
+        report.comparisons[0].test_result.change_interval = Some(ChangeInterval::Bootstrap);
+        let mut buf = Vec::new();
+        GithubPrCommentReporter::new()
+            .write(&report, &mut buf)
+            .expect("write");
+        let out = String::from_utf8(buf).unwrap();
+        let fallback = format!(
+            "  - Bootstrapped instead (ratio unbounded): `{}`\n",
+            report.comparisons[0].name
+        );
+        assert!(out.contains(&fallback), "{out}");
+    }
 }
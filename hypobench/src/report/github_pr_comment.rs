@@ -1,57 +1,181 @@
 //! GitHub PR-comment reporter: specializes the output for posting as a PR comment body.
 //!
 //! Layout:
+//! - Suite headline (geometric-mean change), when there are 2+ benchmarks
 //! - Header line with counts
+//! - Pinned list of benchmarks whose output differs between the two sides
+//! - Failed preflight checks (governor, turbo, load, ...), which cast doubt on
+//!   everything below
 //! - Pinned lists of regressions and improvements (above the fold)
-//! - Full per-bench table inside a collapsible `<details>` block
+//! - Pinned list of significant allocation changes, when allocations were counted
+//! - Pinned list of significant perf counter changes, when counters were collected
+//! - Pinned list of significant quantile (p50/p90/p99) changes
+//! - Full per-bench table inside a collapsible `<details>` block, followed by
+//!   any samples flagged as preempted
 //! - Baseline/candidate SHA line
-//! - Collapsible statistical parameters block
+//! - Collapsible statistical parameters block (with harness builds, if known)
 //! - Small footer crediting hypobench
 
 use std::io::Write;
 
-use hypobench_core::stats::Side;
-use hypobench_core::{BenchmarkComparison, Report, SampleStats};
+use hypobench_core::stats::Verdict;
+use hypobench_core::{
+    AllocationComparison, BenchmarkComparison, MetricComparison, OutputCheck, QuantileComparison,
+    Report, SuiteSummary, SystemCheck,
+};
 
-use super::ReportError;
+use super::{
+    change_ci_fallbacks, format_count, format_metric_value, format_modes, HeadlineMetric,
+    ReportError,
+};
 
 #[derive(Debug, Default, Clone)]
-pub struct GithubPrCommentReporter;
+pub struct GithubPrCommentReporter {
+    /// Statistic the verdicts, pinned lists and table lead with.
+    metric: HeadlineMetric,
+}
 
 impl GithubPrCommentReporter {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Lead with `metric` instead of the mean.
+    pub fn with_metric(mut self, metric: HeadlineMetric) -> Self {
+        self.metric = metric;
+        self
     }
 
     pub fn write(&self, report: &Report, writer: &mut impl Write) -> Result<(), ReportError> {
-        let (faster, slower, inconclusive) = tally(&report.comparisons);
+        let (faster, slower, equivalent, inconclusive) = tally(&report.comparisons, self.metric);
         let total = report.comparisons.len();
+        // Equivalence is opt-in, so only mention it when it found something.
+        let equivalent = if equivalent > 0 {
+            format!("{equivalent} equivalent, ")
+        } else {
+            String::new()
+        };
 
         writeln!(writer, "## Benchmark Results")?;
         writeln!(writer)?;
+        if let Some(suite) = &report.suite {
+            writeln!(writer, "{}", format_suite(suite))?;
+            writeln!(writer)?;
+        }
         writeln!(
             writer,
-            "**{faster} faster, {slower} slower, {inconclusive} inconclusive** across {total} benchmarks."
+            "**{faster} faster, {slower} slower, {equivalent}{inconclusive} inconclusive** across {total} benchmarks."
         )?;
         writeln!(writer)?;
 
+        // A speedup from computing something else isn't a speedup, so
+        // output mismatches go first.
+        let mismatches: Vec<(&BenchmarkComparison, &str, &str)> = report
+            .comparisons
+            .iter()
+            .filter_map(|c| match &c.output_check {
+                Some(OutputCheck::Mismatch {
+                    baseline,
+                    candidate,
+                }) => Some((c, baseline.as_str(), candidate.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        if !mismatches.is_empty() {
+            writeln!(writer, "### :x: Output mismatches")?;
+            writeln!(writer)?;
+            for (cmp, baseline, candidate) in &mismatches {
+                writeln!(
+                    writer,
+                    "- `{}` — baseline output `{baseline}`, candidate output `{candidate}`",
+                    escape_backticks(&cmp.name)
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        let noise: Vec<&SystemCheck> = report
+            .metadata
+            .system_checks
+            .iter()
+            .filter(|check| !check.ok)
+            .collect();
+        if !noise.is_empty() {
+            writeln!(writer, "### :thermometer: System noise")?;
+            writeln!(writer)?;
+            for check in &noise {
+                writeln!(writer, "- {}", check.detail)?;
+            }
+            writeln!(writer)?;
+        }
+
+        // Results from drifting samples can't be taken at face value.
+        let unreliable: Vec<&BenchmarkComparison> = report
+            .comparisons
+            .iter()
+            .filter(|c| c.unreliable())
+            .collect();
+        if !unreliable.is_empty() {
+            writeln!(writer, "### :warning: Unreliable benchmarks")?;
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "Samples drifted during the run, so these results may reflect the machine rather than the change:"
+            )?;
+            writeln!(writer)?;
+            for cmp in &unreliable {
+                let findings = cmp
+                    .stationarity
+                    .map(|s| s.findings().join("; "))
+                    .unwrap_or_default();
+                writeln!(writer, "- `{}` — {}", escape_backticks(&cmp.name), findings)?;
+            }
+            writeln!(writer)?;
+        }
+
+        // A mean between two modes describes no actual run.
+        let multimodal: Vec<&BenchmarkComparison> = report
+            .comparisons
+            .iter()
+            .filter(|c| c.multimodal())
+            .collect();
+        if !multimodal.is_empty() {
+            writeln!(writer, "### :warning: Multimodal benchmarks")?;
+            writeln!(writer)?;
+            writeln!(
+                writer,
+                "These switch between performance modes, so their means and tests may mislead; compare the modes instead:"
+            )?;
+            writeln!(writer)?;
+            for cmp in &multimodal {
+                writeln!(
+                    writer,
+                    "- `{}` — {}",
+                    escape_backticks(&cmp.name),
+                    format_modes(cmp, format_time)
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
         // Pinned regressions and improvements above the fold.
         let regressions: Vec<&BenchmarkComparison> = report
             .comparisons
             .iter()
-            .filter(|c| classify(c) == Verdict::Slower)
+            .filter(|c| self.metric.headline(c).verdict == Verdict::Slower)
             .collect();
         let improvements: Vec<&BenchmarkComparison> = report
             .comparisons
             .iter()
-            .filter(|c| classify(c) == Verdict::Faster)
+            .filter(|c| self.metric.headline(c).verdict == Verdict::Faster)
             .collect();
 
         if !regressions.is_empty() {
             writeln!(writer, "### :warning: Regressions")?;
             writeln!(writer)?;
             for cmp in &regressions {
-                writeln!(writer, "{}", format_pinned_row(cmp))?;
+                writeln!(writer, "{}", format_pinned_row(cmp, self.metric))?;
             }
             writeln!(writer)?;
         }
@@ -60,7 +184,88 @@ impl GithubPrCommentReporter {
             writeln!(writer, "### :rocket: Improvements")?;
             writeln!(writer)?;
             for cmp in &improvements {
-                writeln!(writer, "{}", format_pinned_row(cmp))?;
+                writeln!(writer, "{}", format_pinned_row(cmp, self.metric))?;
+            }
+            writeln!(writer)?;
+        }
+
+        let allocation_changes: Vec<(&BenchmarkComparison, &AllocationComparison)> = report
+            .comparisons
+            .iter()
+            .filter_map(|c| c.allocations.as_ref().map(|a| (c, a)))
+            .filter(|(_, a)| a.changed())
+            .collect();
+
+        if !allocation_changes.is_empty() {
+            writeln!(writer, "### :package: Allocation changes")?;
+            writeln!(writer)?;
+            for (cmp, allocations) in &allocation_changes {
+                writeln!(
+                    writer,
+                    "- `{}` — {}",
+                    escape_backticks(&cmp.name),
+                    format_allocation_change(allocations)
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        let counter_changes: Vec<(&BenchmarkComparison, Vec<&MetricComparison>)> = report
+            .comparisons
+            .iter()
+            .filter_map(|c| {
+                let changed: Vec<&MetricComparison> = c
+                    .metrics
+                    .iter()
+                    .filter(|m| m.test_result.statistically_significant)
+                    .collect();
+                (!changed.is_empty()).then_some((c, changed))
+            })
+            .collect();
+
+        if !counter_changes.is_empty() {
+            writeln!(writer, "### :abacus: Counter changes")?;
+            writeln!(writer)?;
+            for (cmp, metrics) in &counter_changes {
+                let changes: Vec<String> =
+                    metrics.iter().map(|m| format_metric_change(m)).collect();
+                writeln!(
+                    writer,
+                    "- `{}` — {}",
+                    escape_backticks(&cmp.name),
+                    changes.join(", ")
+                )?;
+            }
+            writeln!(writer)?;
+        }
+
+        let quantile_changes: Vec<(&BenchmarkComparison, Vec<&QuantileComparison>)> = report
+            .comparisons
+            .iter()
+            .filter_map(|c| {
+                let changed: Vec<&QuantileComparison> = c
+                    .quantiles
+                    .iter()
+                    .filter(|q| q.statistically_significant)
+                    .collect();
+                (!changed.is_empty()).then_some((c, changed))
+            })
+            .collect();
+
+        if !quantile_changes.is_empty() {
+            writeln!(writer, "### :hourglass: Quantile changes")?;
+            writeln!(writer)?;
+            for (cmp, quantiles) in &quantile_changes {
+                let changes: Vec<String> = quantiles
+                    .iter()
+                    .map(|q| format_quantile_change(q))
+                    .collect();
+                writeln!(
+                    writer,
+                    "- `{}` — {}",
+                    escape_backticks(&cmp.name),
+                    changes.join(", ")
+                )?;
             }
             writeln!(writer)?;
         }
@@ -69,25 +274,68 @@ impl GithubPrCommentReporter {
         // sections above the fold already surface the noteworthy rows; the
         // full table is a secondary reference that shouldn't force-scroll on
         // every reviewer viewing the PR.
+        let bayesian = report
+            .comparisons
+            .iter()
+            .any(|c| c.test_result.posterior.is_some());
+        let with_mde = report
+            .comparisons
+            .iter()
+            .any(|c| c.test_result.power.is_some());
         writeln!(writer, "<details>")?;
         writeln!(
             writer,
             "<summary>Full results ({total} benchmarks)</summary>"
         )?;
         writeln!(writer)?;
+        let (baseline, candidate) = match self.metric {
+            HeadlineMetric::Mean => ("Baseline".to_string(), "Candidate".to_string()),
+            metric => (format!("Baseline {metric}"), format!("Candidate {metric}")),
+        };
         writeln!(
             writer,
-            "| | Benchmark | Baseline | Candidate | Change | {ci_pct:.0}% CI | p |",
-            ci_pct = report.metadata.config.confidence_level * 100.0
+            "| | Benchmark | {baseline} | {candidate} | Change | {ci_pct:.0}% CI | {p} |{mde}",
+            ci_pct = report.metadata.config.confidence_level * 100.0,
+            // A Bayesian run reports the probability of a regression instead.
+            p = if bayesian { "P(slower)" } else { "p" },
+            mde = if with_mde { " MDE |" } else { "" },
         )?;
         writeln!(
             writer,
-            "|---|-----------|----------|-----------|--------|--------|---|"
+            "|---|-----------|----------|-----------|--------|--------|---|{}",
+            if with_mde { "-----|" } else { "" }
         )?;
         for cmp in &report.comparisons {
-            write_row(writer, cmp)?;
+            write_row(writer, cmp, self.metric, with_mde)?;
         }
         writeln!(writer)?;
+        let contaminated: Vec<_> = report
+            .comparisons
+            .iter()
+            .filter_map(|c| c.contamination.map(|n| (c, n)))
+            .collect();
+        if !contaminated.is_empty() {
+            writeln!(
+                writer,
+                "Samples with context-switch spikes (likely preempted):"
+            )?;
+            writeln!(writer)?;
+            for (cmp, contamination) in &contaminated {
+                writeln!(
+                    writer,
+                    "- `{}` — {} baseline, {} candidate{}",
+                    escape_backticks(&cmp.name),
+                    contamination.baseline_flagged,
+                    contamination.candidate_flagged,
+                    if contamination.rejected {
+                        " (rejected)"
+                    } else {
+                        ""
+                    }
+                )?;
+            }
+            writeln!(writer)?;
+        }
         writeln!(writer, "</details>")?;
         writeln!(writer)?;
 
@@ -102,6 +350,12 @@ impl GithubPrCommentReporter {
         writeln!(writer, "<summary>Statistical parameters</summary>")?;
         writeln!(writer)?;
         let cfg = &report.metadata.config;
+        if let Some(method) = &cfg.method {
+            writeln!(writer, "- Test: {}", method)?;
+        }
+        if self.metric != HeadlineMetric::Mean {
+            writeln!(writer, "- Headline metric: {}", self.metric)?;
+        }
         writeln!(writer, "- Confidence level: {}", cfg.confidence_level)?;
         writeln!(
             writer,
@@ -118,6 +372,78 @@ impl GithubPrCommentReporter {
                 "none"
             }
         )?;
+        if let Some(power) = cfg.power {
+            writeln!(writer, "- Power (for MDE): {}", power)?;
+        }
+        if let Some(margin) = cfg.equivalence_margin {
+            writeln!(writer, "- Equivalence margin: ±{}%", margin)?;
+        }
+        if cfg.sampling.as_deref() == Some("linear") {
+            // Only Welch compares slopes; the Bayesian test keeps per-sample means.
+            if cfg.method.as_deref().unwrap_or("welch") == "welch" {
+                writeln!(
+                    writer,
+                    "- Sampling: linear (times are slopes of elapsed time against iterations)"
+                )?;
+            } else {
+                writeln!(writer, "- Sampling: linear")?;
+            }
+        }
+        match cfg.change_interval.as_deref() {
+            Some("fieller") => writeln!(
+                writer,
+                "- Change CI: Fieller interval on the ratio of means"
+            )?,
+            Some("delta") => writeln!(
+                writer,
+                "- Change CI: delta-method interval on the log ratio of means"
+            )?,
+            _ => {}
+        }
+        let fallbacks = change_ci_fallbacks(report);
+        if !fallbacks.is_empty() {
+            let names: Vec<String> = fallbacks
+                .iter()
+                .map(|c| format!("`{}`", escape_backticks(&c.name)))
+                .collect();
+            writeln!(
+                writer,
+                "  - Bootstrapped instead (ratio unbounded): {}",
+                names.join(", ")
+            )?;
+        }
+        if let Some(bootstrap) = &cfg.bootstrap {
+            writeln!(
+                writer,
+                "- Bootstrap: {} resamples, {} interval, seed {:#x}",
+                bootstrap.resamples,
+                bootstrap.interval.as_str(),
+                bootstrap.seed
+            )?;
+        }
+        for (label, harnesses) in [
+            ("Baseline", &report.metadata.baseline_harnesses),
+            ("Candidate", &report.metadata.candidate_harnesses),
+        ] {
+            for info in harnesses {
+                writeln!(
+                    writer,
+                    "- {} build: {} · {} · {}",
+                    label,
+                    info.summary(),
+                    info.rustc_version,
+                    info.target
+                )?;
+            }
+        }
+        for (label, placement) in [
+            ("Baseline", &report.metadata.baseline_placement),
+            ("Candidate", &report.metadata.candidate_placement),
+        ] {
+            if let Some(placement) = placement {
+                writeln!(writer, "- {} placement: {}", label, placement.summary())?;
+            }
+        }
         writeln!(writer, "- Generated at: {}", report.metadata.generated_at)?;
         writeln!(writer)?;
         writeln!(writer, "</details>")?;
@@ -132,81 +458,213 @@ impl GithubPrCommentReporter {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Verdict {
-    Faster,
-    Slower,
-    Inconclusive,
-}
-
-fn classify(cmp: &BenchmarkComparison) -> Verdict {
-    if !cmp.test_result.statistically_significant {
-        return Verdict::Inconclusive;
-    }
-    match cmp.test_result.winner {
-        Some(Side::Candidate) => Verdict::Faster,
-        Some(Side::Baseline) => Verdict::Slower,
-        None => Verdict::Inconclusive,
-    }
-}
-
 /// GitHub-flavoured emoji shortcode matching the Regressions/Improvements
 /// section headers, so the full table visually lines up with the above-the-fold
-/// summary. `:heavy_minus_sign:` is a neutral tick for inconclusive rows.
+/// summary. `:heavy_equals_sign:` marks rows shown to be within the
+/// equivalence margin; `:heavy_minus_sign:` is a neutral tick for inconclusive
+/// rows.
 fn verdict_emoji(v: Verdict) -> &'static str {
     match v {
         Verdict::Faster => ":rocket:",
         Verdict::Slower => ":warning:",
+        Verdict::Equivalent => ":heavy_equals_sign:",
         Verdict::Inconclusive => ":heavy_minus_sign:",
     }
 }
 
-fn tally(comparisons: &[BenchmarkComparison]) -> (usize, usize, usize) {
+/// Counts of faster, slower, equivalent and inconclusive benchmarks under `metric`.
+fn tally(
+    comparisons: &[BenchmarkComparison],
+    metric: HeadlineMetric,
+) -> (usize, usize, usize, usize) {
     let mut faster = 0;
     let mut slower = 0;
+    let mut equivalent = 0;
     let mut inconclusive = 0;
     for c in comparisons {
-        match classify(c) {
+        match metric.headline(c).verdict {
             Verdict::Faster => faster += 1,
             Verdict::Slower => slower += 1,
+            Verdict::Equivalent => equivalent += 1,
             Verdict::Inconclusive => inconclusive += 1,
         }
     }
-    (faster, slower, inconclusive)
+    (faster, slower, equivalent, inconclusive)
 }
 
-fn format_pinned_row(cmp: &BenchmarkComparison) -> String {
-    let change = format_change(cmp.test_result.effect_size);
-    let p = format!("{:.4}", cmp.test_result.p_value);
+/// e.g. `:rocket: **Suite: -3.20%** (95% CI [-5.01%, -1.12%], geometric mean
+/// of 12 benchmarks)`. The emoji only appears when the CI excludes zero.
+fn format_suite(suite: &SuiteSummary) -> String {
+    let emoji = match (suite.changed(), suite.change > 0.0) {
+        (false, _) => "",
+        (true, true) => ":rocket: ",
+        (true, false) => ":warning: ",
+    };
+    format!(
+        "{emoji}**Suite: {}** ({:.0}% CI [{:+.2}%, {:+.2}%], geometric mean of {} {}benchmarks)",
+        format_change(suite.change),
+        suite.confidence_level * 100.0,
+        -suite.change_ci_high,
+        -suite.change_ci_low,
+        suite.benchmarks,
+        if suite.weighted { "weighted " } else { "" },
+    )
+}
+
+fn format_pinned_row(cmp: &BenchmarkComparison, metric: HeadlineMetric) -> String {
+    let headline = metric.headline(cmp);
+    let change = format_change(headline.change);
+    let p = if headline.is_mean {
+        format!("p={:.4}, ", cmp.test_result.p_value)
+    } else {
+        format!("{metric}, ")
+    };
     let ci = format!(
         "[{:+.2}%, {:+.2}%]",
-        -cmp.test_result.change_ci_high, -cmp.test_result.change_ci_low
+        -headline.change_ci_high, -headline.change_ci_low
     );
+    let mismatch = if cmp
+        .output_check
+        .as_ref()
+        .is_some_and(OutputCheck::is_mismatch)
+    {
+        " :x: output differs"
+    } else if cmp.unreliable() {
+        " :warning: unreliable"
+    } else {
+        ""
+    };
+    let posterior = cmp
+        .test_result
+        .posterior
+        .map(|posterior| format!(", {}", posterior.summary()))
+        .unwrap_or_default();
     format!(
-        "- `{}` — **{change}** (p={p}, CI {ci})",
+        "- `{}` — **{change}** ({p}CI {ci}{posterior}){mismatch}",
         escape_backticks(&cmp.name)
     )
 }
 
-fn write_row(writer: &mut impl Write, cmp: &BenchmarkComparison) -> Result<(), ReportError> {
-    let name = escape_pipes(&cmp.name);
-    let baseline = format_stats(&cmp.baseline_stats);
-    let candidate = format_stats(&cmp.candidate_stats);
-    let change = format_change(cmp.test_result.effect_size);
+fn write_row(
+    writer: &mut impl Write,
+    cmp: &BenchmarkComparison,
+    metric: HeadlineMetric,
+    with_mde: bool,
+) -> Result<(), ReportError> {
+    let name = if cmp.unreliable() {
+        format!("{} :warning:", escape_pipes(&cmp.name))
+    } else {
+        escape_pipes(&cmp.name)
+    };
+    let headline = metric.headline(cmp);
+    // Only the point estimate for the PR comment. The per-side ± σ adds noise
+    // to the table without being directly actionable — the Change CI column
+    // already tells the reader whether the observed difference is trustworthy.
+    // Std-dev is still preserved in the JSON report so a richer renderer (e.g.
+    // an HTML dashboard) can surface it if needed.
+    let baseline = format_time(headline.baseline_ns);
+    let candidate = format_time(headline.candidate_ns);
+    let change = format_change(headline.change);
     let ci = format!(
         "[{:+.2}%, {:+.2}%]",
-        -cmp.test_result.change_ci_high, -cmp.test_result.change_ci_low
+        -headline.change_ci_high, -headline.change_ci_low
     );
-    let p = format!("{:.4}", cmp.test_result.p_value);
-    let emoji = verdict_emoji(classify(cmp));
+    let p = match &cmp.test_result.posterior {
+        _ if !headline.is_mean => "–".to_string(),
+        Some(posterior) => format!("{:.0}%", posterior.prob_slower() * 100.0),
+        None => format!("{:.4}", cmp.test_result.p_value),
+    };
+    let emoji = verdict_emoji(headline.verdict);
+    let mde = match cmp.test_result.power {
+        Some(power) => format!(" ±{:.1}% |", power.minimum_detectable_effect),
+        None if with_mde => " – |".to_string(),
+        None => String::new(),
+    };
 
     writeln!(
         writer,
-        "| {emoji} | {name} | {baseline} | {candidate} | {change} | {ci} | {p} |"
+        "| {emoji} | {name} | {baseline} | {candidate} | {change} | {ci} | {p} |{mde}"
     )?;
     Ok(())
 }
 
+/// e.g. `**+3 allocs/iter** (12 → 15), **+96 B/iter** (480 B → 576 B)`.
+/// Only the statistically significant parts are bolded.
+fn format_allocation_change(allocations: &AllocationComparison) -> String {
+    let (base, cand) = (&allocations.baseline, &allocations.candidate);
+    let emphasize = |text: String, significant: bool| {
+        if significant {
+            format!("**{text}**")
+        } else {
+            text
+        }
+    };
+    let count = emphasize(
+        format!(
+            "{} allocs/iter",
+            format_signed(cand.count_per_iter - base.count_per_iter)
+        ),
+        allocations.count_test.statistically_significant,
+    );
+    let bytes = emphasize(
+        format!(
+            "{}/iter",
+            format_bytes_signed(cand.bytes_per_iter - base.bytes_per_iter)
+        ),
+        allocations.bytes_test.statistically_significant,
+    );
+    format!(
+        "{count} ({} → {}), {bytes} ({} → {})",
+        format_count(base.count_per_iter),
+        format_count(cand.count_per_iter),
+        format_bytes(base.bytes_per_iter),
+        format_bytes(cand.bytes_per_iter),
+    )
+}
+
+/// e.g. `**instructions/iter -4.1%** (1.00M → 959.00k)`.
+fn format_metric_change(metric: &MetricComparison) -> String {
+    let result = &metric.test_result;
+    format!(
+        "**{}/iter {:+.1}%** ({} → {})",
+        metric.metric,
+        metric.change_percent(),
+        format_metric_value(&metric.metric, result.baseline_mean_ns, format_time),
+        format_metric_value(&metric.metric, result.candidate_mean_ns, format_time),
+    )
+}
+
+/// e.g. `**p99 +35.10%** (1.200 ms → 1.621 ms)`.
+fn format_quantile_change(quantile: &QuantileComparison) -> String {
+    format!(
+        "**{} {}** ({} → {})",
+        quantile.label(),
+        format_change(quantile.change),
+        format_time(quantile.baseline_ns),
+        format_time(quantile.candidate_ns),
+    )
+}
+
+fn format_signed(delta: f64) -> String {
+    let sign = if delta < 0.0 { "-" } else { "+" };
+    format!("{sign}{}", format_count(delta.abs()))
+}
+
+fn format_bytes(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 {
+        format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+    } else if bytes >= 1024.0 {
+        format!("{:.1} KiB", bytes / 1024.0)
+    } else {
+        format!("{} B", format_count(bytes))
+    }
+}
+
+fn format_bytes_signed(delta: f64) -> String {
+    let sign = if delta < 0.0 { "-" } else { "+" };
+    format!("{sign}{}", format_bytes(delta.abs()))
+}
+
 fn escape_pipes(s: &str) -> String {
     s.replace('|', r"\|")
 }
@@ -218,15 +676,6 @@ fn escape_backticks(s: &str) -> String {
     s.replace('`', "'")
 }
 
-fn format_stats(stats: &SampleStats) -> String {
-    // Only the point estimate for the PR comment. The per-side ± σ adds noise
-    // to the table without being directly actionable — the Change CI column
-    // already tells the reader whether the observed difference is trustworthy.
-    // Std-dev is still preserved in the JSON report so a richer renderer (e.g.
-    // an HTML dashboard) can surface it if needed.
-    format_time(stats.mean_ns)
-}
-
 fn format_time(ns: f64) -> String {
     if ns >= 1_000_000_000.0 {
         format!("{:.3} s", ns / 1_000_000_000.0)
@@ -2,27 +2,99 @@ use std::io::{self, Write};
 
 use colored::Colorize;
 
-use hypobench_core::stats::Side;
-use hypobench_core::{BenchmarkComparison, SampleStats};
+use hypobench_core::stats::{PowerAnalysis, Verdict};
+use hypobench_core::{
+    AllocationComparison, BenchmarkComparison, MetricComparison, OutputCheck, QuantileComparison,
+    Report, SampleStats, SuiteSummary,
+};
 
-use super::{ReportError, Reporter};
+use super::{
+    change_ci_fallbacks, format_count, format_metric_value, format_modes, Headline, HeadlineMetric,
+    ReportError, Reporter,
+};
 
 /// A reporter that outputs benchmark comparison results to the terminal.
 #[derive(Debug, Clone, Default)]
 pub struct TerminalReporter {
     /// Whether to use colors in output (defaults to true).
     use_colors: bool,
+    /// Statistic shown in the Baseline, Candidate, Change and Result columns.
+    metric: HeadlineMetric,
 }
 
 impl TerminalReporter {
     /// Create a new terminal reporter with default settings.
     pub fn new() -> Self {
-        Self { use_colors: true }
+        Self {
+            use_colors: true,
+            metric: HeadlineMetric::Mean,
+        }
     }
 
     /// Create a terminal reporter with color output disabled.
     pub fn without_colors() -> Self {
-        Self { use_colors: false }
+        Self {
+            use_colors: false,
+            metric: HeadlineMetric::Mean,
+        }
+    }
+
+    /// Lead each row with `metric` instead of the mean.
+    pub fn with_metric(mut self, metric: HeadlineMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Write a whole report: the suite headline, if any, then the table.
+    pub fn write_report(&self, report: &Report, writer: &mut impl Write) -> io::Result<()> {
+        if let Some(suite) = &report.suite {
+            writeln!(writer)?;
+            self.print_suite(writer, suite)?;
+        }
+        self.print_header(writer)?;
+        for comparison in &report.comparisons {
+            self.print_row(writer, comparison)?;
+        }
+        let fallbacks = change_ci_fallbacks(report);
+        if !fallbacks.is_empty() {
+            let names: Vec<&str> = fallbacks.iter().map(|c| c.name.as_str()).collect();
+            writeln!(
+                writer,
+                "Change CI bootstrapped instead (ratio unbounded): {}",
+                names.join(", ")
+            )?;
+        }
+        self.print_summary(writer, &report.comparisons)
+    }
+
+    /// Print the suite headline, e.g. `Suite: -3.20% [-5.01%, -1.12%]
+    /// (geometric mean of 12 benchmarks, 95% CI)`.
+    fn print_suite(&self, writer: &mut impl Write, suite: &SuiteSummary) -> io::Result<()> {
+        let change = format!(
+            "{} {}",
+            Self::format_change(suite.change),
+            Self::format_change_ci(suite.change_ci_low, suite.change_ci_high)
+        );
+        let change = match (self.use_colors, suite.changed()) {
+            (false, _) => change,
+            (true, false) => change.yellow().to_string(),
+            (true, true) if suite.change > 0.0 => change.green().bold().to_string(),
+            (true, true) => change.red().bold().to_string(),
+        };
+        let label = if self.use_colors {
+            "Suite:".bold().to_string()
+        } else {
+            "Suite:".to_string()
+        };
+        writeln!(
+            writer,
+            "{} {} (geometric mean of {} {}benchmarks, {:.0}% CI)",
+            label,
+            change,
+            suite.benchmarks,
+            if suite.weighted { "weighted " } else { "" },
+            suite.confidence_level * 100.0
+        )
     }
 
     /// Format a duration in nanoseconds to a human-readable string.
@@ -38,11 +110,18 @@ impl TerminalReporter {
         }
     }
 
-    /// Format a duration with standard deviation.
+    /// Format a duration with standard deviation, or under linear sampling
+    /// the fitted slope with its standard error.
     fn format_time_with_stddev(stats: &SampleStats) -> String {
-        let mean = Self::format_time(stats.mean_ns);
-        let stddev = Self::format_time(stats.std_dev_ns);
-        format!("{} (+/- {})", mean, stddev)
+        let (mean, stddev) = match &stats.fit {
+            Some(fit) => (fit.slope_ns, fit.slope_std_err),
+            None => (stats.mean_ns, stats.std_dev_ns),
+        };
+        format!(
+            "{} (+/- {})",
+            Self::format_time(mean),
+            Self::format_time(stddev)
+        )
     }
 
     /// Format the percent change between baseline and candidate.
@@ -57,90 +136,52 @@ impl TerminalReporter {
     }
 
     /// Format the result column with appropriate coloring.
-    fn format_result(&self, comparison: &BenchmarkComparison) -> String {
-        let result = &comparison.test_result;
+    fn format_result(&self, verdict: Verdict) -> String {
+        let text = Self::result_text(verdict);
+        if !self.use_colors {
+            return text.to_string();
+        }
+        match verdict {
+            Verdict::Faster => text.green().bold().to_string(),
+            Verdict::Slower => text.red().bold().to_string(),
+            Verdict::Equivalent => text.cyan().to_string(),
+            Verdict::Inconclusive => text.yellow().to_string(),
+        }
+    }
 
-        if !result.statistically_significant {
-            let text = "inconclusive";
-            if self.use_colors {
-                text.yellow().to_string()
-            } else {
-                text.to_string()
-            }
-        } else {
-            match result.winner {
-                Some(Side::Candidate) => {
-                    let text = "faster";
-                    if self.use_colors {
-                        text.green().bold().to_string()
-                    } else {
-                        text.to_string()
-                    }
-                }
-                Some(Side::Baseline) => {
-                    let text = "slower";
-                    if self.use_colors {
-                        text.red().bold().to_string()
-                    } else {
-                        text.to_string()
-                    }
-                }
-                None => {
-                    let text = "inconclusive";
-                    if self.use_colors {
-                        text.yellow().to_string()
-                    } else {
-                        text.to_string()
-                    }
-                }
-            }
+    /// Uncolored text of the result column.
+    fn result_text(verdict: Verdict) -> &'static str {
+        match verdict {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "slower",
+            Verdict::Equivalent => "equivalent",
+            Verdict::Inconclusive => "inconclusive",
         }
     }
 
     /// Format the change column with appropriate coloring.
-    fn format_change_colored(&self, comparison: &BenchmarkComparison) -> String {
-        let change = Self::format_change(comparison.test_result.effect_size);
-        let result = &comparison.test_result;
-
-        if !result.statistically_significant {
-            if self.use_colors {
-                change.yellow().to_string()
-            } else {
-                change
-            }
-        } else {
-            match result.winner {
-                Some(Side::Candidate) => {
-                    if self.use_colors {
-                        change.green().to_string()
-                    } else {
-                        change
-                    }
-                }
-                Some(Side::Baseline) => {
-                    if self.use_colors {
-                        change.red().to_string()
-                    } else {
-                        change
-                    }
-                }
-                None => {
-                    if self.use_colors {
-                        change.yellow().to_string()
-                    } else {
-                        change
-                    }
-                }
-            }
+    fn format_change_colored(&self, headline: &Headline) -> String {
+        let change = Self::format_change(headline.change);
+        if !self.use_colors {
+            return change;
+        }
+        match headline.verdict {
+            Verdict::Faster => change.green().to_string(),
+            Verdict::Slower => change.red().to_string(),
+            Verdict::Equivalent | Verdict::Inconclusive => change.yellow().to_string(),
         }
     }
 
     /// Print the table header.
     fn print_header(&self, writer: &mut impl Write) -> io::Result<()> {
         writeln!(writer)?;
+        let (baseline, candidate) = match self.metric {
+            HeadlineMetric::Mean => ("Baseline".to_string(), "Candidate".to_string()),
+            metric => (format!("Baseline {metric}"), format!("Candidate {metric}")),
+        };
         let header = format!(
             "{:<40} {:>24} {:>24} {:>12} {:>22} {:>10} {:>14}",
-            "Benchmark", "Baseline", "Candidate", "Change", "Change CI", "p-value", "Result"
+            "Benchmark", baseline, candidate, "Change", "Change CI", "p-value", "Result"
         );
         if self.use_colors {
             writeln!(writer, "{}", header.bold())?;
@@ -171,27 +212,28 @@ impl TerminalReporter {
             comparison.name.clone()
         };
 
-        let baseline = Self::format_time_with_stddev(&comparison.baseline_stats);
-        let candidate = Self::format_time_with_stddev(&comparison.candidate_stats);
-        let change = self.format_change_colored(comparison);
-        let change_ci = Self::format_change_ci(
-            comparison.test_result.change_ci_low,
-            comparison.test_result.change_ci_high,
-        );
-        let p_value = format!("{:.4}", comparison.test_result.p_value);
-        let result = self.format_result(comparison);
-
-        // Calculate visible widths accounting for ANSI escape codes
-        let change_visible_len = Self::format_change(comparison.test_result.effect_size).len();
-        let result_visible_len = if comparison.test_result.statistically_significant {
-            match comparison.test_result.winner {
-                Some(Side::Candidate) => 6, // "faster"
-                Some(Side::Baseline) => 6,  // "slower"
-                None => 12,                 // "inconclusive"
-            }
+        let headline = self.metric.headline(comparison);
+        let (baseline, candidate, p_value) = if headline.is_mean {
+            (
+                Self::format_time_with_stddev(&comparison.baseline_stats),
+                Self::format_time_with_stddev(&comparison.candidate_stats),
+                format!("{:.4}", comparison.test_result.p_value),
+            )
         } else {
-            12 // "inconclusive"
+            // The bootstrap gives no p-value for a quantile.
+            (
+                Self::format_time(headline.baseline_ns),
+                Self::format_time(headline.candidate_ns),
+                "-".to_string(),
+            )
         };
+        let change = self.format_change_colored(&headline);
+        let change_ci = Self::format_change_ci(headline.change_ci_low, headline.change_ci_high);
+        let result = self.format_result(headline.verdict);
+
+        // Calculate visible widths accounting for ANSI escape codes
+        let change_visible_len = Self::format_change(headline.change).len();
+        let result_visible_len = Self::result_text(headline.verdict).len();
 
         // Pad the colored strings to achieve proper alignment
         let change_padding = 12_usize.saturating_sub(change_visible_len);
@@ -212,9 +254,243 @@ impl TerminalReporter {
             width_change = change_padding,
             width_result = result_padding,
         )?;
+
+        if let Some(OutputCheck::Mismatch {
+            baseline,
+            candidate,
+        }) = &comparison.output_check
+        {
+            let line = format!(
+                "  OUTPUT MISMATCH: baseline {}, candidate {}",
+                baseline, candidate
+            );
+            if self.use_colors {
+                writeln!(writer, "{}", line.red().bold())?;
+            } else {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        if let (Some(baseline), Some(candidate)) = (
+            &comparison.baseline_stats.fit,
+            &comparison.candidate_stats.fit,
+        ) {
+            writeln!(
+                writer,
+                "  fit: overhead {} -> {} per sample, R^2 {:.3} / {:.3}",
+                Self::format_time(baseline.intercept_ns),
+                Self::format_time(candidate.intercept_ns),
+                baseline.r_squared,
+                candidate.r_squared
+            )?;
+        }
+        if let Some(posterior) = &comparison.test_result.posterior {
+            writeln!(writer, "  {}", posterior.summary())?;
+        }
+        // The power analysis is for the mean.
+        if headline.is_mean && headline.verdict == Verdict::Inconclusive {
+            if let Some(power) = &comparison.test_result.power {
+                writeln!(writer, "{}", Self::format_power(comparison, power))?;
+            }
+        }
+        if comparison
+            .quantiles
+            .iter()
+            .any(|q| q.statistically_significant)
+        {
+            self.print_quantiles(writer, &comparison.quantiles)?;
+        }
+        if let Some(allocations) = &comparison.allocations {
+            self.print_allocations(writer, allocations)?;
+        }
+        if !comparison.metrics.is_empty() {
+            self.print_metrics(writer, &comparison.metrics)?;
+        }
+        if comparison.multimodal() {
+            let line = format!(
+                "  multimodal: {}",
+                format_modes(comparison, Self::format_time)
+            );
+            if self.use_colors {
+                writeln!(writer, "{}", line.yellow())?;
+            } else {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        if let Some(stationarity) = comparison.stationarity.filter(|s| !s.stationary()) {
+            let line = format!(
+                "  unreliable, samples drifted: {}",
+                stationarity.findings().join("; ")
+            );
+            if self.use_colors {
+                writeln!(writer, "{}", line.yellow())?;
+            } else {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        if let Some(contamination) = &comparison.contamination {
+            let line = format!(
+                "  context-switch spikes: {} baseline, {} candidate sample(s){}",
+                contamination.baseline_flagged,
+                contamination.candidate_flagged,
+                if contamination.rejected {
+                    ", rejected"
+                } else {
+                    ""
+                }
+            );
+            if self.use_colors {
+                writeln!(writer, "{}", line.yellow())?;
+            } else {
+                writeln!(writer, "{}", line)?;
+            }
+        }
         Ok(())
     }
 
+    /// e.g. `  MDE ±3.5% at 80% power; ~420 samples per side needed for the
+    /// minimum effect size`.
+    /// The sample-size hint only appears when more samples would help.
+    fn format_power(comparison: &BenchmarkComparison, power: &PowerAnalysis) -> String {
+        let mut line = format!(
+            "  MDE ±{:.1}% at {:.0}% power",
+            power.minimum_detectable_effect,
+            power.power * 100.0
+        );
+        let taken = comparison
+            .baseline_stats
+            .sample_count
+            .min(comparison.candidate_stats.sample_count);
+        if let Some(required) = power.required_sample_size.filter(|&n| n > taken as u64) {
+            line.push_str(&format!(
+                "; ~{} samples per side needed for the minimum effect size",
+                required
+            ));
+        }
+        line
+    }
+
+    /// Print the allocation comparison under a benchmark row.
+    fn print_allocations(
+        &self,
+        writer: &mut impl Write,
+        allocations: &AllocationComparison,
+    ) -> io::Result<()> {
+        let (base, cand) = (&allocations.baseline, &allocations.candidate);
+        let count_delta = self.format_cost_delta(
+            Self::format_signed_count(cand.count_per_iter - base.count_per_iter),
+            cand.count_per_iter - base.count_per_iter,
+            allocations.count_test.statistically_significant,
+        );
+        let bytes_delta = self.format_cost_delta(
+            Self::format_signed_bytes(cand.bytes_per_iter - base.bytes_per_iter),
+            cand.bytes_per_iter - base.bytes_per_iter,
+            allocations.bytes_test.statistically_significant,
+        );
+        writeln!(
+            writer,
+            "  allocs/iter: {} -> {} ({})   bytes/iter: {} -> {} ({})   peak live: {} -> {}",
+            format_count(base.count_per_iter),
+            format_count(cand.count_per_iter),
+            count_delta,
+            Self::format_bytes(base.bytes_per_iter),
+            Self::format_bytes(cand.bytes_per_iter),
+            bytes_delta,
+            Self::format_bytes(base.peak_live_bytes as f64),
+            Self::format_bytes(cand.peak_live_bytes as f64),
+        )
+    }
+
+    /// Print quantile changes under a benchmark row, e.g.
+    /// `  p50: -0.20% [-0.51%, +0.10%]   p99: +35.10% [+20.02%, +51.30%]`.
+    fn print_quantiles(
+        &self,
+        writer: &mut impl Write,
+        quantiles: &[QuantileComparison],
+    ) -> io::Result<()> {
+        let parts: Vec<String> = quantiles
+            .iter()
+            .map(|quantile| {
+                // Lower is better; format_cost_delta wants the increase.
+                let change = self.format_cost_delta(
+                    format!(
+                        "{} {}",
+                        Self::format_change(quantile.change),
+                        Self::format_change_ci(quantile.change_ci_low, quantile.change_ci_high)
+                    ),
+                    -quantile.change,
+                    quantile.statistically_significant,
+                );
+                format!("{}: {}", quantile.label(), change)
+            })
+            .collect();
+        writeln!(writer, "  {}", parts.join("   "))
+    }
+
+    /// Print secondary metrics (perf counters) under a benchmark row.
+    fn print_metrics(
+        &self,
+        writer: &mut impl Write,
+        metrics: &[MetricComparison],
+    ) -> io::Result<()> {
+        let parts: Vec<String> = metrics
+            .iter()
+            .map(|metric| {
+                let result = &metric.test_result;
+                let change = self.format_cost_delta(
+                    format!("{:+.1}%", metric.change_percent()),
+                    metric.change_percent(),
+                    result.statistically_significant,
+                );
+                format!(
+                    "{}/iter: {} -> {} ({})",
+                    metric.metric,
+                    format_metric_value(&metric.metric, result.baseline_mean_ns, Self::format_time),
+                    format_metric_value(
+                        &metric.metric,
+                        result.candidate_mean_ns,
+                        Self::format_time
+                    ),
+                    change,
+                )
+            })
+            .collect();
+        writeln!(writer, "  {}", parts.join("   "))
+    }
+
+    /// Color a significant change in a lower-is-better metric: red if the
+    /// candidate needs more.
+    fn format_cost_delta(&self, text: String, delta: f64, significant: bool) -> String {
+        if !self.use_colors || !significant {
+            text
+        } else if delta > 0.0 {
+            text.red().to_string()
+        } else {
+            text.green().to_string()
+        }
+    }
+
+    /// Format an allocation count, with a decimal only when it isn't whole.
+    fn format_signed_count(delta: f64) -> String {
+        let sign = if delta < 0.0 { "-" } else { "+" };
+        format!("{}{}", sign, format_count(delta.abs()))
+    }
+
+    /// Format a byte count to a human-readable string.
+    fn format_bytes(bytes: f64) -> String {
+        if bytes >= 1024.0 * 1024.0 {
+            format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+        } else if bytes >= 1024.0 {
+            format!("{:.1} KiB", bytes / 1024.0)
+        } else {
+            format!("{} B", format_count(bytes))
+        }
+    }
+
+    fn format_signed_bytes(delta: f64) -> String {
+        let sign = if delta < 0.0 { "-" } else { "+" };
+        format!("{}{}", sign, Self::format_bytes(delta.abs()))
+    }
+
     /// Print the summary footer.
     fn print_summary(
         &self,
@@ -223,17 +499,15 @@ impl TerminalReporter {
     ) -> io::Result<()> {
         let mut faster = 0;
         let mut slower = 0;
+        let mut equivalent = 0;
         let mut inconclusive = 0;
 
         for comparison in results {
-            if !comparison.test_result.statistically_significant {
-                inconclusive += 1;
-            } else {
-                match comparison.test_result.winner {
-                    Some(Side::Candidate) => faster += 1,
-                    Some(Side::Baseline) => slower += 1,
-                    None => inconclusive += 1,
-                }
+            match self.metric.headline(comparison).verdict {
+                Verdict::Faster => faster += 1,
+                Verdict::Slower => slower += 1,
+                Verdict::Equivalent => equivalent += 1,
+                Verdict::Inconclusive => inconclusive += 1,
             }
         }
 
@@ -250,21 +524,21 @@ impl TerminalReporter {
         let faster_text = format!("{} faster", faster);
         let slower_text = format!("{} slower", slower);
         let inconclusive_text = format!("{} inconclusive", inconclusive);
+        // Equivalence is opt-in, so only mention it when it found something.
+        let equivalent_text = (equivalent > 0).then(|| format!("{} equivalent", equivalent));
 
         if self.use_colors {
-            writeln!(
-                writer,
-                "{}, {}, {}",
-                faster_text.green(),
-                slower_text.red(),
-                inconclusive_text.yellow()
-            )?;
+            write!(writer, "{}, {}, ", faster_text.green(), slower_text.red())?;
+            if let Some(text) = &equivalent_text {
+                write!(writer, "{}, ", text.cyan())?;
+            }
+            writeln!(writer, "{}", inconclusive_text.yellow())?;
         } else {
-            writeln!(
-                writer,
-                "{}, {}, {}",
-                faster_text, slower_text, inconclusive_text
-            )?;
+            write!(writer, "{}, {}, ", faster_text, slower_text)?;
+            if let Some(text) = &equivalent_text {
+                write!(writer, "{}, ", text)?;
+            }
+            writeln!(writer, "{}", inconclusive_text)?;
         }
 
         writeln!(writer)?;
@@ -292,7 +566,7 @@ impl Reporter for TerminalReporter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use hypobench_core::stats::TestResult;
+    use hypobench_core::stats::{Bootstrap, LinearFit, Side, TestResult};
 
     fn make_comparison(
         name: &str,
@@ -310,6 +584,10 @@ mod tests {
                 min_ns: (baseline_mean_ns * 0.9) as u64,
                 max_ns: (baseline_mean_ns * 1.1) as u64,
                 sample_count: 100,
+                median_ns: baseline_mean_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             candidate_stats: SampleStats {
                 mean_ns: candidate_mean_ns,
@@ -317,6 +595,10 @@ mod tests {
                 min_ns: (candidate_mean_ns * 0.9) as u64,
                 max_ns: (candidate_mean_ns * 1.1) as u64,
                 sample_count: 100,
+                median_ns: candidate_mean_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             test_result: TestResult {
                 p_value,
@@ -328,7 +610,17 @@ mod tests {
                 winner,
                 baseline_mean_ns,
                 candidate_mean_ns,
+                equivalence: None,
+                power: None,
+                posterior: None,
+                change_interval: None,
             },
+            allocations: None,
+            metrics: Vec::new(),
+            contamination: None,
+            output_check: None,
+            quantiles: Vec::new(),
+            stationarity: None,
         }
     }
 
@@ -372,6 +664,222 @@ mod tests {
         assert_eq!(TerminalReporter::format_change(0.0), "0.00%");
     }
 
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(TerminalReporter::format_bytes(96.0), "96 B");
+        assert_eq!(TerminalReporter::format_bytes(2560.0), "2.5 KiB");
+        assert_eq!(TerminalReporter::format_signed_bytes(-96.0), "-96 B");
+        assert_eq!(TerminalReporter::format_signed_count(2.5), "+2.5");
+    }
+
+    #[test]
+    fn test_row_includes_allocations() {
+        use hypobench_core::protocol::AllocationStats;
+        use hypobench_core::WelchTTest;
+
+        let stats = |count, bytes| AllocationStats {
+            count,
+            bytes,
+            peak_live_bytes: 2048,
+        };
+        let mut comparison = make_comparison("bench_alloc", 1000.0, 1000.0, 0.0, 1.0, None);
+        comparison.allocations = Some(AllocationComparison::analyze(
+            &WelchTTest::new(0.95),
+            &[stats(24, 960); 10],
+            &[stats(30, 1152); 10],
+            2,
+        ));
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains("allocs/iter: 12 -> 15 (+3)   bytes/iter: 480 B -> 576 B (+96 B)"),
+            "{output}"
+        );
+        assert!(output.contains("peak live: 2.0 KiB -> 2.0 KiB"), "{output}");
+    }
+
+    #[test]
+    fn test_row_includes_metrics() {
+        use hypobench_core::WelchTTest;
+
+        let baseline: Vec<f64> = (0..10).map(|i| 1_000_000.0 + i as f64).collect();
+        let candidate: Vec<f64> = baseline.iter().map(|v| v * 0.959).collect();
+        let mut comparison = make_comparison("bench_perf", 1000.0, 1000.0, 0.0, 1.0, None);
+        comparison.metrics = vec![MetricComparison::analyze(
+            &WelchTTest::new(0.95),
+            "instructions",
+            &baseline,
+            &candidate,
+        )];
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains("instructions/iter: 1.00M -> 959.00k (-4.1%)"),
+            "{output}"
+        );
+        assert_eq!(
+            format_metric_value("task_clock_ns", 1500.0, TerminalReporter::format_time),
+            "1.500 us"
+        );
+    }
+
+    #[test]
+    fn test_row_shows_linear_fit() {
+        let mut comparison = make_comparison("bench_linear", 1000.0, 1000.0, 0.0, 1.0, None);
+        let fit = |slope_ns, intercept_ns| LinearFit {
+            slope_ns,
+            intercept_ns,
+            slope_std_err: 2.0,
+            r_squared: 0.999,
+            samples: 100,
+        };
+        comparison.baseline_stats.fit = Some(fit(900.0, 10_000.0));
+        comparison.candidate_stats.fit = Some(fit(850.0, 40_000.0));
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("900.000 ns (+/- 2.000 ns)"), "{output}");
+        assert!(
+            output.contains("  fit: overhead 10.000 us -> 40.000 us per sample, R^2 0.999 / 0.999"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_row_leads_with_quantile() {
+        let baseline: Vec<f64> = (0..100).map(|i| 1000.0 + (i % 10) as f64).collect();
+        let candidate: Vec<f64> = baseline
+            .iter()
+            .enumerate()
+            .map(|(i, v)| if i % 10 == 9 { v * 3.0 } else { *v })
+            .collect();
+        let mut comparison = make_comparison("bench_tail", 1004.5, 1204.4, -19.9, 0.2, None);
+        comparison.quantiles = [0.5, 0.99]
+            .iter()
+            .map(|&q| {
+                QuantileComparison::analyze(
+                    &baseline,
+                    &candidate,
+                    q,
+                    0.95,
+                    1.0,
+                    &Bootstrap::default(),
+                )
+            })
+            .collect();
+
+        // Under the mean, a significant quantile change gets its own line.
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains("  p50: 0.00% [-0.15%, +0.10%]   p99: +200.00% [+200.00%, +200.00%]"),
+            "{output}"
+        );
+
+        let reporter =
+            TerminalReporter::without_colors().with_metric(HeadlineMetric::Quantile(0.99));
+        let mut buffer = Vec::new();
+        reporter.print_header(&mut buffer).unwrap();
+        reporter.print_row(&mut buffer, &comparison).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("Baseline p99"), "{output}");
+        assert!(output.contains("1.009 us"), "{output}");
+        assert!(output.contains("3.027 us"), "{output}");
+        assert!(output.contains("+200.00%"), "{output}");
+        assert!(output.contains("  -         slower\n"), "{output}");
+    }
+
+    #[test]
+    fn test_row_flags_contamination() {
+        let mut comparison = make_comparison("bench_noisy", 1000.0, 1000.0, 0.0, 1.0, None);
+        comparison.contamination = Some(hypobench_core::Contamination {
+            baseline_flagged: 2,
+            candidate_flagged: 0,
+            rejected: true,
+        });
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains("context-switch spikes: 2 baseline, 0 candidate sample(s), rejected"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_row_flags_drift() {
+        let baseline: Vec<f64> = (0..50).map(|i| 1000.0 + (i % 5) as f64).collect();
+        let candidate: Vec<f64> = (0..50)
+            .map(|i| 1000.0 + (i % 5) as f64 + i as f64)
+            .collect();
+        let mut comparison = make_comparison("bench_drift", 1000.0, 1025.0, -2.5, 0.001, None);
+        comparison.stationarity =
+            hypobench_core::Stationarity::analyze(&baseline, &candidate, 0.05);
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains("unreliable, samples drifted: candidate trend (tau +"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_row_flags_multimodal_samples() {
+        let mut comparison = make_comparison("bench_modes", 1000.0, 1300.0, -30.0, 0.001, None);
+        let samples: Vec<f64> = (0..100)
+            .map(|i| if i % 2 == 0 { 1000.0 } else { 1600.0 } + (i % 10) as f64)
+            .collect();
+        comparison.candidate_stats = SampleStats::from_samples(&samples, &[]);
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("  multimodal: candidate 1.00"), "{output}");
+        assert!(output.contains(" (50%), 1.60"), "{output}");
+        assert!(!output.contains("baseline 1."), "{output}");
+    }
+
+    #[test]
+    fn test_row_flags_output_mismatch() {
+        let mut comparison = make_comparison("bench_wrong", 1000.0, 700.0, 30.0, 0.001, None);
+        comparison.output_check =
+            Some(OutputCheck::compare("00000000000000aa", "00000000000000bb"));
+
+        let mut buffer = Vec::new();
+        TerminalReporter::without_colors()
+            .print_row(&mut buffer, &comparison)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output
+                .contains("OUTPUT MISMATCH: baseline 00000000000000aa, candidate 00000000000000bb"),
+            "{output}"
+        );
+    }
+
     #[test]
     fn test_report_to_buffer() {
         let reporter = TerminalReporter::without_colors();
@@ -414,4 +922,101 @@ mod tests {
         assert!(output.contains("1 slower"));
         assert!(output.contains("1 inconclusive"));
     }
+
+    #[test]
+    fn test_equivalent_result_is_shown_apart_from_inconclusive() {
+        let reporter = TerminalReporter::without_colors();
+        let mut equivalent = make_comparison("bench_same", 1000.0, 1001.0, -0.1, 0.6, None);
+        equivalent.test_result.equivalence = Some(hypobench_core::stats::Equivalence {
+            margin: 2.0,
+            p_value: 0.001,
+            equivalent: true,
+        });
+        let results = vec![
+            equivalent,
+            make_comparison("bench_noisy", 1000.0, 1010.0, -1.0, 0.5, None),
+        ];
+
+        let mut buffer = Vec::new();
+        for comparison in &results {
+            reporter.print_row(&mut buffer, comparison).unwrap();
+        }
+        reporter.print_summary(&mut buffer, &results).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("equivalent"));
+        assert!(output.contains("0 faster, 0 slower, 1 equivalent, 1 inconclusive"));
+    }
+
+    #[test]
+    fn test_inconclusive_row_shows_minimum_detectable_effect() {
+        let reporter = TerminalReporter::without_colors();
+        let mut noisy = make_comparison("bench_noisy", 1000.0, 1010.0, -1.0, 0.5, None);
+        noisy.test_result.power = Some(hypobench_core::stats::PowerAnalysis {
+            power: 0.8,
+            minimum_detectable_effect: 3.52,
+            required_sample_size: Some(1240),
+        });
+        let mut fast = make_comparison(
+            "bench_fast",
+            1000.0,
+            800.0,
+            20.0,
+            0.001,
+            Some(Side::Candidate),
+        );
+        fast.test_result.power = noisy.test_result.power;
+
+        let mut buffer = Vec::new();
+        reporter.print_row(&mut buffer, &noisy).unwrap();
+        reporter.print_row(&mut buffer, &fast).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.contains(
+                "  MDE ±3.5% at 80% power; ~1240 samples per side needed for the minimum effect size\n"
+            ),
+            "{output}"
+        );
+        assert_eq!(output.matches("MDE").count(), 1);
+    }
+
+    #[test]
+    fn test_write_report_leads_with_suite() {
+        let reporter = TerminalReporter::without_colors();
+        let report: Report = serde_json::from_value(serde_json::json!({
+            "schema_version": "1",
+            "metadata": {
+                "hypobench_version": "0.5.0",
+                "generated_at": "2026-04-18T10:00:00Z",
+                "baseline_ref": "main",
+                "candidate_ref": "HEAD",
+                "config": {
+                    "confidence_level": 0.95,
+                    "minimum_effect_size": 1.0,
+                    "sample_size": 100,
+                    "correct_multiple_comparisons": true
+                }
+            },
+            "comparisons": [],
+            "suite": {
+                "benchmarks": 12,
+                "change": 3.2,
+                "change_ci_low": 1.12,
+                "change_ci_high": 5.01,
+                "confidence_level": 0.95
+            }
+        }))
+        .unwrap();
+
+        let mut buffer = Vec::new();
+        reporter.write_report(&report, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.starts_with(
+                "\nSuite: -3.20% [-5.01%, -1.12%] (geometric mean of 12 benchmarks, 95% CI)\n"
+            ),
+            "{output}"
+        );
+        assert!(output.contains("Summary:"));
+    }
 }
@@ -0,0 +1,311 @@
+//! CPU pinning and scheduling priority for spawned harnesses.
+//!
+//! Left alone, the kernel migrates harnesses freely across cores and SMT
+//! siblings, which adds a lot of variance. Placement is applied in the child
+//! between `fork` and `exec`, so every thread the harness starts inherits it.
+//! It relies on `sched_setaffinity`/`setpriority`/`sched_setscheduler` and is
+//! Linux-only; elsewhere configured placement is ignored with a warning.
+//! [`resolve`] also fills in each side's launcher and extra environment,
+//! which work everywhere.
+
+use std::collections::BTreeMap;
+
+use hypobench_core::report::HarnessPlacement;
+use thiserror::Error;
+use tokio::process::Command;
+
+use crate::config::OrchestrationConfig;
+
+/// Errors in the configured placement, caught before anything is spawned.
+#[derive(Debug, Error)]
+pub enum PlacementError {
+    /// A configured CPU is outside this process's affinity mask (or offline).
+    #[error("CPU {cpu} is not available to this process (allowed: {allowed})")]
+    UnavailableCpu { cpu: usize, allowed: String },
+
+    /// Nice value outside what the kernel accepts.
+    #[error("nice value {0} is out of range (-20..=19)")]
+    InvalidNice(i32),
+
+    /// Real-time priority outside what `SCHED_FIFO` accepts.
+    #[error("SCHED_FIFO priority {0} is out of range (1..=99)")]
+    InvalidFifoPriority(u32),
+}
+
+/// Resolve the configured placement for the baseline and candidate harnesses.
+///
+/// `{side}` in the launcher becomes `baseline` or `candidate`.
+/// `baseline_cpus` / `candidate_cpus` override `cpus` for their side. With
+/// only `cpus` set, both harnesses share those CPUs; samples are interleaved,
+/// so they take turns rather than compete. On platforms other than Linux this
+/// warns if CPUs or priorities were configured and leaves them out.
+///
+/// # Errors
+///
+/// Returns an error if a CPU isn't available to this process or a priority is
+/// out of range.
+pub fn resolve(
+    config: &OrchestrationConfig,
+) -> Result<(HarnessPlacement, HarnessPlacement), PlacementError> {
+    let side = |name: &str, cpus: &[usize], env: &BTreeMap<String, String>| {
+        let mut cpus = if cpus.is_empty() {
+            config.cpus.clone()
+        } else {
+            cpus.to_vec()
+        };
+        cpus.sort_unstable();
+        cpus.dedup();
+        HarnessPlacement {
+            launcher: config
+                .launcher
+                .iter()
+                .map(|arg| arg.replace("{side}", name))
+                .collect(),
+            env: env.clone(),
+            cpus,
+            nice: config.nice,
+            fifo_priority: config.fifo_priority,
+        }
+    };
+    let mut baseline = side("baseline", &config.baseline_cpus, &config.baseline_env);
+    let mut candidate = side("candidate", &config.candidate_cpus, &config.candidate_env);
+    if !schedules(&baseline) && !schedules(&candidate) {
+        return Ok((baseline, candidate));
+    }
+    if !cfg!(target_os = "linux") {
+        eprintln!(
+            "Warning: CPU pinning and scheduling options are only supported on Linux; ignoring them"
+        );
+        for placement in [&mut baseline, &mut candidate] {
+            placement.cpus.clear();
+            placement.nice = None;
+            placement.fifo_priority = None;
+        }
+        return Ok((baseline, candidate));
+    }
+
+    if let Some(nice) = config.nice.filter(|nice| !(-20..=19).contains(nice)) {
+        return Err(PlacementError::InvalidNice(nice));
+    }
+    if let Some(priority) = config
+        .fifo_priority
+        .filter(|priority| !(1..=99).contains(priority))
+    {
+        return Err(PlacementError::InvalidFifoPriority(priority));
+    }
+    let allowed = sys::allowed_cpus();
+    for &cpu in baseline.cpus.iter().chain(&candidate.cpus) {
+        if !allowed.contains(&cpu) {
+            let allowed: Vec<String> = allowed.iter().map(usize::to_string).collect();
+            return Err(PlacementError::UnavailableCpu {
+                cpu,
+                allowed: allowed.join(","),
+            });
+        }
+    }
+    Ok((baseline, candidate))
+}
+
+/// Arrange for `command`'s child to run with `placement`.
+///
+/// A failure in the child (typically `EPERM` for a negative nice value or
+/// `SCHED_FIFO` without `CAP_SYS_NICE`) makes the spawn itself fail.
+pub(crate) fn apply(command: &mut Command, placement: &HarnessPlacement) {
+    if schedules(placement) {
+        sys::apply(command, placement);
+    }
+}
+
+/// Whether `placement` pins CPUs or changes scheduling, as opposed to only
+/// wrapping or adding environment.
+fn schedules(placement: &HarnessPlacement) -> bool {
+    !placement.cpus.is_empty() || placement.nice.is_some() || placement.fifo_priority.is_some()
+}
+
+/// Whether a spawn failing with permission denied is likely down to `placement`.
+pub(crate) fn needs_privilege(placement: &HarnessPlacement) -> bool {
+    placement.nice.is_some_and(|nice| nice < 0) || placement.fifo_priority.is_some()
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use hypobench_core::report::HarnessPlacement;
+    use std::io;
+    use std::mem;
+    use tokio::process::Command;
+
+    /// CPUs this process may run on, in ascending order.
+    pub(super) fn allowed_cpus() -> Vec<usize> {
+        // SAFETY: `cpu_set_t` is plain bits, so all-zero is a valid value.
+        let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+        // SAFETY: `set` is a valid, correctly sized out-pointer.
+        let rc = unsafe { libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) };
+        if rc != 0 {
+            return Vec::new();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            // SAFETY: `cpu` is below `CPU_SETSIZE`, so it is within `set`.
+            .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+            .collect()
+    }
+
+    pub(super) fn apply(command: &mut Command, placement: &HarnessPlacement) {
+        // Everything the hook needs is prepared here, in the parent.
+        let cpus = (!placement.cpus.is_empty()).then(|| {
+            // SAFETY: as above, all-zero is a valid `cpu_set_t`.
+            let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+            for &cpu in &placement.cpus {
+                // SAFETY: only touches `set`; an out-of-range CPU panics on
+                // the bounds check rather than writing past it.
+                unsafe { libc::CPU_SET(cpu, &mut set) };
+            }
+            set
+        });
+        let nice = placement.nice;
+        let fifo = placement.fifo_priority.map(|priority| libc::sched_param {
+            sched_priority: priority as libc::c_int,
+        });
+
+        let check = |rc: libc::c_int| {
+            if rc == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        };
+        // SAFETY: the hook only makes raw syscalls on values captured by
+        // copy; it neither allocates nor takes locks, so it is safe to run
+        // between fork and exec.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(set) = &cpus {
+                    check(libc::sched_setaffinity(
+                        0,
+                        mem::size_of::<libc::cpu_set_t>(),
+                        set,
+                    ))?;
+                }
+                if let Some(nice) = nice {
+                    check(libc::setpriority(libc::PRIO_PROCESS, 0, nice))?;
+                }
+                if let Some(param) = &fifo {
+                    check(libc::sched_setscheduler(0, libc::SCHED_FIFO, param))?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use hypobench_core::report::HarnessPlacement;
+    use tokio::process::Command;
+
+    pub(super) fn allowed_cpus() -> Vec<usize> {
+        Vec::new()
+    }
+
+    pub(super) fn apply(_command: &mut Command, _placement: &HarnessPlacement) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_shares_cpus_unless_overridden() {
+        let config = OrchestrationConfig::default();
+        let (baseline, candidate) = resolve(&config).unwrap();
+        assert!(baseline.is_default() && candidate.is_default());
+
+        if !cfg!(target_os = "linux") {
+            return;
+        }
+        let first = sys::allowed_cpus()[0];
+        let config = OrchestrationConfig {
+            cpus: vec![first, first],
+            nice: Some(5),
+            ..Default::default()
+        };
+        let (baseline, candidate) = resolve(&config).unwrap();
+        assert_eq!(baseline.cpus, vec![first]);
+        assert_eq!(baseline, candidate);
+        assert_eq!(baseline.nice, Some(5));
+
+        let config = OrchestrationConfig {
+            cpus: vec![first],
+            candidate_cpus: vec![usize::MAX],
+            ..Default::default()
+        };
+        let err = resolve(&config).unwrap_err();
+        assert!(matches!(err, PlacementError::UnavailableCpu { cpu, .. } if cpu == usize::MAX));
+
+        let config = OrchestrationConfig {
+            fifo_priority: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            resolve(&config),
+            Err(PlacementError::InvalidFifoPriority(0))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_templates_launcher_per_side() {
+        let config = OrchestrationConfig {
+            launcher: vec![
+                "valgrind".to_string(),
+                "--cachegrind-out-file=cg.{side}.out".to_string(),
+            ],
+            candidate_env: [("RAYON_NUM_THREADS".to_string(), "1".to_string())].into(),
+            ..Default::default()
+        };
+        let (baseline, candidate) = resolve(&config).unwrap();
+        assert_eq!(
+            baseline.launcher[1],
+            "--cachegrind-out-file=cg.baseline.out"
+        );
+        assert_eq!(
+            candidate.launcher[1],
+            "--cachegrind-out-file=cg.candidate.out"
+        );
+        assert!(baseline.env.is_empty());
+        assert_eq!(candidate.env["RAYON_NUM_THREADS"], "1");
+        assert!(!schedules(&candidate));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_apply_pins_and_renices_child() {
+        let cpu = *sys::allowed_cpus().last().unwrap();
+        let placement = HarnessPlacement {
+            cpus: vec![cpu],
+            // Raising the nice value needs no privilege.
+            nice: Some(7),
+            ..Default::default()
+        };
+        let mut command = Command::new("sleep");
+        command.arg("5").kill_on_drop(true);
+        apply(&mut command, &placement);
+        let child = command.spawn().unwrap();
+        let pid = child.id().unwrap();
+
+        let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).unwrap();
+        let allowed = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+            .unwrap();
+        assert_eq!(allowed.trim(), cpu.to_string());
+
+        // Field 19 of /proc/<pid>/stat, counted after the parenthesised name.
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap();
+        let fields: Vec<&str> = stat
+            .rsplit(')')
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        assert_eq!(fields[16], "7");
+    }
+}
@@ -155,6 +155,14 @@ impl SourceProvider for GitWorktreeProvider {
         Ok((baseline_path, candidate_path))
     }
 
+    fn prepare_source(&self, commit: &str) -> Result<PathBuf, SourceError> {
+        self.cleanup_existing()?;
+        let path = self.baseline_path();
+        self.create_worktree(&path, commit)
+            .map_err(|e| SourceError::Checkout(commit.to_string(), format!("{}", e)))?;
+        Ok(path)
+    }
+
     fn cleanup(&self) -> Result<(), SourceError> {
         self.remove_worktree(&self.baseline_path())?;
         self.remove_worktree(&self.candidate_path())?;
@@ -194,4 +202,31 @@ mod tests {
             PathBuf::from("/test/repo/.hypobench/candidate")
         );
     }
+
+    #[test]
+    fn test_prepare_source_checks_out_one_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .current_dir(dir.path())
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("file"), "contents").unwrap();
+        git(&["add", "file"]);
+        git(&["commit", "-q", "-m", "initial"]);
+
+        let provider = GitWorktreeProvider::with_repo_root(dir.path().to_path_buf());
+        let path = provider.prepare_source("HEAD").unwrap();
+        assert_eq!(path, provider.baseline_path());
+        assert!(path.join("file").exists());
+        assert!(!provider.candidate_path().exists());
+
+        provider.cleanup().unwrap();
+        assert!(!provider.worktree_base().exists());
+    }
 }
@@ -19,6 +19,8 @@ pub trait SourceProvider: Send + Sync {
         baseline: &str,
         candidate: &str,
     ) -> Result<(PathBuf, PathBuf), SourceError>;
+    /// Prepare a single source, for runs that build one commit.
+    fn prepare_source(&self, commit: &str) -> Result<PathBuf, SourceError>;
     fn cleanup(&self) -> Result<(), SourceError>;
 }
 
@@ -5,29 +5,33 @@
 
 use hypobench::{HarnessHandle, OrchestratorError};
 
+// Lets the in-process harnesses below report allocations.
+#[global_allocator]
+static ALLOC: hypobench_harness::CountingAllocator = hypobench_harness::CountingAllocator::system();
+
 /// Test that HarnessHandle::connect validates URLs correctly.
 #[test]
 fn test_harness_handle_connect_validation() {
     // Valid URLs should work
-    assert!(HarnessHandle::connect("http://localhost:9100").is_ok());
-    assert!(HarnessHandle::connect("https://localhost:9100").is_ok());
-    assert!(HarnessHandle::connect("http://127.0.0.1:9100").is_ok());
+    assert!(HarnessHandle::connect("http://localhost:9100", None).is_ok());
+    assert!(HarnessHandle::connect("https://localhost:9100", None).is_ok());
+    assert!(HarnessHandle::connect("http://127.0.0.1:9100", None).is_ok());
 
     // Invalid URLs should fail
-    let result = HarnessHandle::connect("localhost:9100");
+    let result = HarnessHandle::connect("localhost:9100", None);
     assert!(matches!(result, Err(OrchestratorError::InvalidUrl(_))));
 
-    let result = HarnessHandle::connect("not-a-url");
+    let result = HarnessHandle::connect("not-a-url", None);
     assert!(matches!(result, Err(OrchestratorError::InvalidUrl(_))));
 
-    let result = HarnessHandle::connect("ftp://localhost:9100");
+    let result = HarnessHandle::connect("ftp://localhost:9100", None);
     assert!(matches!(result, Err(OrchestratorError::InvalidUrl(_))));
 }
 
 /// Test that trailing slashes are handled correctly.
 #[test]
 fn test_harness_handle_trailing_slash() {
-    let handle = HarnessHandle::connect("http://localhost:9100/").unwrap();
+    let handle = HarnessHandle::connect("http://localhost:9100/", None).unwrap();
     // The handle should normalize the URL
     assert!(!handle.is_managed());
 }
@@ -35,7 +39,7 @@ fn test_harness_handle_trailing_slash() {
 /// Test that remote handles are not marked as managed.
 #[test]
 fn test_remote_handle_not_managed() {
-    let handle = HarnessHandle::connect("http://localhost:9100").unwrap();
+    let handle = HarnessHandle::connect("http://localhost:9100", None).unwrap();
     assert!(!handle.is_managed());
     // pid() should return None for remote handles
     assert!(handle.pid().is_none());
@@ -239,6 +243,10 @@ mod report_tests {
                 min_ns: (baseline_mean_ns * 0.9) as u64,
                 max_ns: (baseline_mean_ns * 1.1) as u64,
                 sample_count: 100,
+                median_ns: baseline_mean_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             candidate_stats: SampleStats {
                 mean_ns: candidate_mean_ns,
@@ -246,6 +254,10 @@ mod report_tests {
                 min_ns: (candidate_mean_ns * 0.9) as u64,
                 max_ns: (candidate_mean_ns * 1.1) as u64,
                 sample_count: 100,
+                median_ns: candidate_mean_ns,
+                quantiles: Vec::new(),
+                fit: None,
+                modality: None,
             },
             test_result: TestResult {
                 p_value,
@@ -257,7 +269,17 @@ mod report_tests {
                 winner,
                 baseline_mean_ns,
                 candidate_mean_ns,
+                equivalence: None,
+                power: None,
+                posterior: None,
+                change_interval: None,
             },
+            allocations: None,
+            metrics: Vec::new(),
+            contamination: None,
+            output_check: None,
+            quantiles: Vec::new(),
+            stationarity: None,
         }
     }
 
@@ -307,8 +329,13 @@ mod report_tests {
 /// without requiring git worktrees or cargo builds.
 #[cfg(test)]
 mod harness_integration_tests {
-    use hypobench::{run_with_urls, wait_for_health, HarnessHandle};
-    use hypobench_harness::{run_harness_async, BenchmarkRegistry};
+    use hypobench::{
+        run_with_urls, wait_for_health, HarnessHandle, OrchestratorError, OutputCheck, Sampling,
+        Side, SlopeTest,
+    };
+    use hypobench_harness::{
+        run_harness_async, run_harness_with_config_async, BenchmarkRegistry, HarnessConfig,
+    };
     use std::time::{Duration, Instant};
 
     /// Find a free port for testing.
@@ -345,10 +372,11 @@ mod harness_integration_tests {
         });
 
         // Connect and check health
-        let mut handle = HarnessHandle::connect(&format!("http://127.0.0.1:{}", port)).unwrap();
+        let mut handle =
+            HarnessHandle::connect(&format!("http://127.0.0.1:{}", port), None).unwrap();
 
         // Wait for harness to be ready
-        let result = wait_for_health(&handle, Duration::from_secs(5)).await;
+        let result = wait_for_health(&mut handle, Duration::from_secs(5)).await;
         assert!(result.is_ok(), "Health check failed: {:?}", result);
 
         // Shutdown
@@ -366,8 +394,9 @@ mod harness_integration_tests {
             run_harness_async(registry, port).await.unwrap();
         });
 
-        let mut handle = HarnessHandle::connect(&format!("http://127.0.0.1:{}", port)).unwrap();
-        wait_for_health(&handle, Duration::from_secs(5))
+        let mut handle =
+            HarnessHandle::connect(&format!("http://127.0.0.1:{}", port), None).unwrap();
+        wait_for_health(&mut handle, Duration::from_secs(5))
             .await
             .unwrap();
 
@@ -379,6 +408,92 @@ mod harness_integration_tests {
         harness_task.abort();
     }
 
+    /// Test that a harness in a binary with the counting allocator reports
+    /// the allocations made by each run.
+    #[tokio::test]
+    async fn test_harness_reports_allocations() {
+        let port = find_free_port();
+        let mut registry = BenchmarkRegistry::new();
+        registry.register("alloc_bench", |n| {
+            let start = Instant::now();
+            for _ in 0..n {
+                std::hint::black_box(vec![0u8; 100]);
+            }
+            start.elapsed()
+        });
+
+        let harness_task = tokio::spawn(async move {
+            run_harness_async(registry, port).await.unwrap();
+        });
+
+        let mut handle =
+            HarnessHandle::connect(&format!("http://127.0.0.1:{}", port), None).unwrap();
+        wait_for_health(&mut handle, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let sample = handle.run_sample("alloc_bench", 8).await.unwrap();
+        let allocations = sample.allocations.expect("allocations are counted");
+        assert_eq!(allocations.count, 8);
+        assert_eq!(allocations.bytes, 800);
+        assert_eq!(allocations.peak_live_bytes, 100);
+        assert_eq!(sample.rusage.is_some(), cfg!(target_os = "linux"));
+
+        let batch = handle.run_batch("alloc_bench", 2, 3).await.unwrap();
+        assert!(batch
+            .iter()
+            .all(|sample| sample.allocations.map(|a| a.count) == Some(2)));
+
+        let _ = handle.shutdown().await;
+        harness_task.abort();
+    }
+
+    /// Test that perf counters are only collected when asked for, and are
+    /// reported under the documented names when the host allows them.
+    #[tokio::test]
+    async fn test_harness_reports_perf_counters_on_request() {
+        let port = find_free_port();
+        let registry = create_test_registry(50);
+        let harness_task = tokio::spawn(async move {
+            run_harness_async(registry, port).await.unwrap();
+        });
+
+        let mut handle =
+            HarnessHandle::connect(&format!("http://127.0.0.1:{}", port), None).unwrap();
+        wait_for_health(&mut handle, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let sample = handle.run_sample("test_bench", 4).await.unwrap();
+        assert!(sample.counters.is_empty());
+
+        handle.set_perf_counters(true);
+        let batch = handle.run_batch("test_bench", 4, 2).await.unwrap();
+        // Perf events may be unavailable here; then the maps stay empty.
+        const KNOWN: [&str; 7] = [
+            "instructions",
+            "cycles",
+            "branch_misses",
+            "cache_misses",
+            "task_clock_ns",
+            "page_faults",
+            "context_switches",
+        ];
+        for sample in &batch {
+            assert!(
+                sample
+                    .counters
+                    .keys()
+                    .all(|name| KNOWN.contains(&name.as_str())),
+                "{:?}",
+                sample.counters
+            );
+        }
+
+        let _ = handle.shutdown().await;
+        harness_task.abort();
+    }
+
     /// Test that we can run a benchmark iteration.
     #[tokio::test]
     async fn test_harness_run_iteration() {
@@ -389,8 +504,9 @@ mod harness_integration_tests {
             run_harness_async(registry, port).await.unwrap();
         });
 
-        let mut handle = HarnessHandle::connect(&format!("http://127.0.0.1:{}", port)).unwrap();
-        wait_for_health(&handle, Duration::from_secs(5))
+        let mut handle =
+            HarnessHandle::connect(&format!("http://127.0.0.1:{}", port), None).unwrap();
+        wait_for_health(&mut handle, Duration::from_secs(5))
             .await
             .unwrap();
 
@@ -441,13 +557,13 @@ mod harness_integration_tests {
         let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
         let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
 
-        let mut baseline_handle = HarnessHandle::connect(&baseline_url).unwrap();
-        let mut candidate_handle = HarnessHandle::connect(&candidate_url).unwrap();
+        let mut baseline_handle = HarnessHandle::connect(&baseline_url, None).unwrap();
+        let mut candidate_handle = HarnessHandle::connect(&candidate_url, None).unwrap();
 
-        wait_for_health(&baseline_handle, Duration::from_secs(5))
+        wait_for_health(&mut baseline_handle, Duration::from_secs(5))
             .await
             .unwrap();
-        wait_for_health(&candidate_handle, Duration::from_secs(5))
+        wait_for_health(&mut candidate_handle, Duration::from_secs(5))
             .await
             .unwrap();
 
@@ -459,8 +575,14 @@ mod harness_integration_tests {
             Duration::from_secs(5),
             10,                        // sample size
             Duration::from_millis(10), // interleave interval
+            3,                         // block size
             Duration::from_millis(0),  // target_sample: n=1 is enough
             1_000,                     // max calibration iters
+            Duration::from_secs(30),   // claim ttl
+            false,                     // force claim
+            None,                      // auth token
+            false,                     // perf counters
+            Sampling::Flat,            // sampling
         )
         .await
         .unwrap();
@@ -471,6 +593,17 @@ mod harness_integration_tests {
         assert_eq!(samples[0].baseline_samples.len(), 10);
         assert_eq!(samples[0].candidate_samples.len(), 10);
 
+        // This test binary installs the counting allocator, so both sides count.
+        assert!(samples[0].has_allocations());
+
+        // Both harnesses report their provenance; in-process, that's this test binary.
+        let baseline_info = samples[0].baseline_info.as_ref().unwrap();
+        assert_eq!(baseline_info.pid, std::process::id());
+        assert_eq!(
+            samples[0].candidate_info.as_ref().unwrap().rustc_version,
+            baseline_info.rustc_version
+        );
+
         // Baseline should be slower on average
         let baseline_mean: f64 = samples[0]
             .baseline_samples
@@ -499,6 +632,139 @@ mod harness_integration_tests {
         candidate_task.abort();
     }
 
+    /// Test that the preflight compares output digests: a candidate that
+    /// returns a different answer is flagged before sampling.
+    #[tokio::test]
+    async fn test_output_mismatch_detection() {
+        let baseline_port = find_free_port();
+        let candidate_port = find_free_port();
+
+        let registry = |answer: u64| {
+            let mut registry = BenchmarkRegistry::new();
+            registry.register_with_output("answer", move |_n| (Duration::from_micros(50), answer));
+            registry.register_with_output("same", |_n| (Duration::from_micros(50), "ok"));
+            registry
+        };
+        let (baseline_registry, candidate_registry) = (registry(42), registry(41));
+
+        let baseline_task = tokio::spawn(async move {
+            run_harness_async(baseline_registry, baseline_port)
+                .await
+                .unwrap();
+        });
+        let candidate_task = tokio::spawn(async move {
+            run_harness_async(candidate_registry, candidate_port)
+                .await
+                .unwrap();
+        });
+
+        let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
+        let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
+
+        let mut samples = run_with_urls(
+            &baseline_url,
+            &candidate_url,
+            Duration::from_secs(5),
+            2,
+            Duration::from_millis(0),
+            1,
+            Duration::from_millis(0),
+            1,
+            Duration::from_secs(30),
+            false,
+            None,
+            false,
+            Sampling::Flat,
+        )
+        .await
+        .unwrap();
+        samples.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(samples[0].name, "answer");
+        assert!(samples[0].output_check.as_ref().unwrap().is_mismatch());
+        assert_eq!(samples[1].output_check, Some(OutputCheck::Match));
+
+        let mut baseline_handle = HarnessHandle::connect(&baseline_url, None).unwrap();
+        let mut candidate_handle = HarnessHandle::connect(&candidate_url, None).unwrap();
+        let _ = baseline_handle.shutdown().await;
+        let _ = candidate_handle.shutdown().await;
+        baseline_task.abort();
+        candidate_task.abort();
+    }
+
+    /// Test that linear sampling runs a range of iteration counts and that
+    /// fitting their totals separates per-iteration time from overhead.
+    #[tokio::test]
+    async fn test_linear_sampling_fits_slopes() {
+        let baseline_port = find_free_port();
+        let candidate_port = find_free_port();
+
+        // Synthetic timings: the candidate pays more fixed overhead per
+        // sample but each iteration is 20% cheaper.
+        let registry = |overhead_ns: u64, per_iter_ns: u64| {
+            let mut registry = BenchmarkRegistry::new();
+            registry.register("linear", move |n| {
+                Duration::from_nanos(overhead_ns + per_iter_ns * n)
+            });
+            registry
+        };
+        let (baseline_registry, candidate_registry) =
+            (registry(10_000, 1_000), registry(50_000, 800));
+
+        let baseline_task = tokio::spawn(async move {
+            run_harness_async(baseline_registry, baseline_port)
+                .await
+                .unwrap();
+        });
+        let candidate_task = tokio::spawn(async move {
+            run_harness_async(candidate_registry, candidate_port)
+                .await
+                .unwrap();
+        });
+
+        let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
+        let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
+
+        let samples = run_with_urls(
+            &baseline_url,
+            &candidate_url,
+            Duration::from_secs(5),
+            20,
+            Duration::from_millis(0),
+            4,
+            Duration::from_millis(0), // calibration stops at n=1
+            1_000,
+            Duration::from_secs(30),
+            false,
+            None,
+            false,
+            Sampling::Linear,
+        )
+        .await
+        .unwrap();
+
+        let sample = &samples[0];
+        let mut counts: Vec<u64> = sample.baseline_totals.iter().map(|&(n, _)| n).collect();
+        counts.sort();
+        assert_eq!(counts, (1..=20).collect::<Vec<u64>>());
+        assert_eq!(sample.candidate_totals.len(), 20);
+        assert!(!sample.has_allocations());
+
+        let (baseline_runs, candidate_runs) = sample.linear_runs().unwrap();
+        let result = SlopeTest::new(0.95).analyze_runs(&baseline_runs, &candidate_runs);
+        assert!((result.baseline_mean_ns - 1_000.0).abs() < 1e-6);
+        assert!((result.candidate_mean_ns - 800.0).abs() < 1e-6);
+        assert_eq!(result.winner, Some(Side::Candidate));
+        assert!((result.effect_size - 20.0).abs() < 1e-6);
+
+        let mut baseline_handle = HarnessHandle::connect(&baseline_url, None).unwrap();
+        let mut candidate_handle = HarnessHandle::connect(&candidate_url, None).unwrap();
+        let _ = baseline_handle.shutdown().await;
+        let _ = candidate_handle.shutdown().await;
+        baseline_task.abort();
+        candidate_task.abort();
+    }
+
     /// Test that benchmark mismatch is detected correctly.
     #[tokio::test]
     async fn test_benchmark_mismatch_detection() {
@@ -527,13 +793,13 @@ mod harness_integration_tests {
         let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
         let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
 
-        let baseline_handle = HarnessHandle::connect(&baseline_url).unwrap();
-        let candidate_handle = HarnessHandle::connect(&candidate_url).unwrap();
+        let mut baseline_handle = HarnessHandle::connect(&baseline_url, None).unwrap();
+        let mut candidate_handle = HarnessHandle::connect(&candidate_url, None).unwrap();
 
-        wait_for_health(&baseline_handle, Duration::from_secs(5))
+        wait_for_health(&mut baseline_handle, Duration::from_secs(5))
             .await
             .unwrap();
-        wait_for_health(&candidate_handle, Duration::from_secs(5))
+        wait_for_health(&mut candidate_handle, Duration::from_secs(5))
             .await
             .unwrap();
 
@@ -544,8 +810,14 @@ mod harness_integration_tests {
             Duration::from_secs(5),
             5,
             Duration::from_millis(10),
+            1,
             Duration::from_millis(0),
             1_000,
+            Duration::from_secs(30),
+            false,
+            None,
+            false,
+            Sampling::Flat,
         )
         .await;
 
@@ -560,4 +832,265 @@ mod harness_integration_tests {
         baseline_task.abort();
         candidate_task.abort();
     }
+
+    /// Test that a harness held by a stale orchestrator can only be taken over
+    /// with an explicit forced claim.
+    #[tokio::test]
+    async fn test_forced_claim_takes_over_stale_claim() {
+        let baseline_port = find_free_port();
+        let candidate_port = find_free_port();
+
+        let baseline_registry = create_test_registry(100);
+        let candidate_registry = create_test_registry(100);
+
+        let baseline_task = tokio::spawn(async move {
+            run_harness_async(baseline_registry, baseline_port)
+                .await
+                .unwrap();
+        });
+        let candidate_task = tokio::spawn(async move {
+            run_harness_async(candidate_registry, candidate_port)
+                .await
+                .unwrap();
+        });
+
+        let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
+        let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
+
+        // Simulate an orchestrator that claimed the baseline and went away.
+        let mut stale = HarnessHandle::connect(&baseline_url, None).unwrap();
+        wait_for_health(&mut stale, Duration::from_secs(5))
+            .await
+            .unwrap();
+        stale.claim(Duration::from_secs(60), false).await.unwrap();
+
+        let result = run_with_urls(
+            &baseline_url,
+            &candidate_url,
+            Duration::from_secs(5),
+            2,
+            Duration::from_millis(1),
+            1,
+            Duration::from_millis(0),
+            1_000,
+            Duration::from_secs(30),
+            false,
+            None,
+            false,
+            Sampling::Flat,
+        )
+        .await;
+        assert!(
+            matches!(result, Err(OrchestratorError::ClaimError(_))),
+            "expected claim conflict, got: {:?}",
+            result.map(|s| s.len())
+        );
+
+        let samples = run_with_urls(
+            &baseline_url,
+            &candidate_url,
+            Duration::from_secs(5),
+            2,
+            Duration::from_millis(1),
+            1,
+            Duration::from_millis(0),
+            1_000,
+            Duration::from_secs(30),
+            true,
+            None,
+            false,
+            Sampling::Flat,
+        )
+        .await
+        .unwrap();
+        assert_eq!(samples[0].baseline_samples.len(), 2);
+
+        drop(stale);
+        baseline_task.abort();
+        candidate_task.abort();
+    }
+
+    /// Test that a handle whose lease is lost stops working, even once the
+    /// harness is unclaimed and would otherwise serve anyone.
+    #[tokio::test]
+    async fn test_lost_claim_fails_later_requests() {
+        let port = find_free_port();
+        let registry = create_test_registry(100);
+        let harness_task = tokio::spawn(async move {
+            run_harness_async(registry, port).await.unwrap();
+        });
+        let url = format!("http://127.0.0.1:{}", port);
+
+        let mut holder = HarnessHandle::connect(&url, None).unwrap();
+        wait_for_health(&mut holder, Duration::from_secs(5))
+            .await
+            .unwrap();
+        holder
+            .claim(Duration::from_millis(300), false)
+            .await
+            .unwrap();
+
+        // Another orchestrator takes over and leaves the harness unclaimed.
+        let mut other = HarnessHandle::connect(&url, None).unwrap();
+        wait_for_health(&mut other, Duration::from_secs(5))
+            .await
+            .unwrap();
+        other.claim(Duration::from_secs(30), true).await.unwrap();
+        other.release().await.unwrap();
+
+        // Give the holder's heartbeat (every 100ms) time to notice.
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let result = holder.list_benchmarks().await;
+        assert!(
+            matches!(result, Err(OrchestratorError::ClaimError(_))),
+            "expected lost claim, got: {:?}",
+            result
+        );
+
+        harness_task.abort();
+    }
+
+    /// Test that a token-protected harness rejects an unauthenticated
+    /// orchestrator up front and works once the token is supplied.
+    #[tokio::test]
+    async fn test_token_protected_harness() {
+        let port = find_free_port();
+        let registry = create_test_registry(100);
+        let config = HarnessConfig::new(port).with_auth_token("s3cret");
+
+        let harness_task = tokio::spawn(async move {
+            run_harness_with_config_async(registry, config)
+                .await
+                .unwrap();
+        });
+
+        let url = format!("http://127.0.0.1:{}", port);
+
+        // Without the token, health checks fail as soon as the harness is up
+        // instead of retrying until the timeout.
+        let mut anonymous = HarnessHandle::connect(&url, None).unwrap();
+        let start = Instant::now();
+        let result = wait_for_health(&mut anonymous, Duration::from_secs(5)).await;
+        assert!(
+            matches!(result, Err(OrchestratorError::Unauthorized { .. })),
+            "expected unauthorized, got: {:?}",
+            result
+        );
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let mut authed = HarnessHandle::connect(&url, Some("s3cret")).unwrap();
+        wait_for_health(&mut authed, Duration::from_secs(5))
+            .await
+            .unwrap();
+        authed.claim(Duration::from_secs(30), false).await.unwrap();
+        let benchmarks = authed.list_benchmarks().await.unwrap();
+        assert_eq!(benchmarks, vec!["test_bench".to_string()]);
+
+        let _ = authed.shutdown().await;
+        harness_task.abort();
+    }
+
+    /// Serve a stand-in for a pre-versioning (v1) harness: no protocol
+    /// version in `/health`, one iteration per `/run`, no `/run_batch`.
+    /// Returns the number of `/run` requests served so far.
+    async fn spawn_v1_harness(port: u16) -> std::sync::Arc<std::sync::atomic::AtomicU64> {
+        use axum::routing::{get, post};
+        use axum::Json;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+
+        let runs = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&runs);
+        let app = axum::Router::new()
+            .route(
+                "/health",
+                get(|| async { Json(serde_json::json!({ "status": "healthy" })) }),
+            )
+            .route(
+                "/benchmarks",
+                get(|| async { Json(serde_json::json!({ "benchmarks": ["test_bench"] })) }),
+            )
+            .route(
+                "/run",
+                post(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async { Json(serde_json::json!({ "success": true, "duration_ns": 1_000 })) }
+                }),
+            )
+            .route(
+                "/claim",
+                post(|| async { Json(serde_json::json!({ "success": true })) }),
+            )
+            .route(
+                "/release",
+                post(|| async { Json(serde_json::json!({ "success": true })) }),
+            );
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+            .await
+            .unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        runs
+    }
+
+    /// Test that a current orchestrator negotiates down to v1 and emulates
+    /// iterations and batches with one `/run` per iteration.
+    #[tokio::test]
+    async fn test_v1_harness_is_emulated() {
+        let baseline_port = find_free_port();
+        let candidate_port = find_free_port();
+
+        let v1_runs = spawn_v1_harness(baseline_port).await;
+        let candidate_registry = create_test_registry(10);
+        let candidate_task = tokio::spawn(async move {
+            run_harness_async(candidate_registry, candidate_port)
+                .await
+                .unwrap();
+        });
+
+        let baseline_url = format!("http://127.0.0.1:{}", baseline_port);
+        let candidate_url = format!("http://127.0.0.1:{}", candidate_port);
+
+        let mut legacy = HarnessHandle::connect(&baseline_url, None).unwrap();
+        wait_for_health(&mut legacy, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(legacy.protocol_version(), 1);
+        assert_eq!(
+            legacy.run_iteration("test_bench", 3).await.unwrap(),
+            Duration::from_nanos(3_000)
+        );
+        assert_eq!(v1_runs.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+        let samples = run_with_urls(
+            &baseline_url,
+            &candidate_url,
+            Duration::from_secs(5),
+            4,
+            Duration::from_millis(1),
+            2,
+            Duration::from_millis(0),
+            1_000,
+            Duration::from_secs(30),
+            false,
+            None,
+            false,
+            Sampling::Flat,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(samples[0].baseline_samples.len(), 4);
+        assert_eq!(samples[0].candidate_samples.len(), 4);
+        assert!(samples[0].baseline_info.is_none());
+        assert!(samples[0].candidate_info.is_some());
+        assert!(samples[0]
+            .baseline_samples
+            .iter()
+            .all(|sample| *sample == Duration::from_nanos(1_000)));
+        // 3 direct + 1 calibration + 4 samples, each at n = 1.
+        assert_eq!(v1_runs.load(std::sync::atomic::Ordering::SeqCst), 8);
+
+        candidate_task.abort();
+    }
 }